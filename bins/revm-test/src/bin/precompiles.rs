@@ -0,0 +1,145 @@
+//! Measures execution time of the precompiles over representative inputs and reports
+//! nanoseconds per gas, used to calibrate gas costs of custom precompile sets.
+//!
+//! Precompiles are taken from the latest spec with `P256VERIFY` and the ed25519 verify
+//! precompile added, backends depend on the enabled features of revm.
+use revm::{
+    precompile::{ed25519, secp256r1, u64_to_address, Precompiles},
+    primitives::{hex, Address, Bytes, Env},
+};
+use std::time::{Duration, Instant};
+
+/// Address the ed25519 verify precompile is measured at.
+const ED25519_VERIFY: u64 = 0x53b;
+
+/// Minimum time each precompile is run for.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(1);
+
+fn main() {
+    let precompiles = Precompiles::latest()
+        .clone()
+        .with_precompile(secp256r1::P256VERIFY)
+        .with_precompile(ed25519::ed25519_verify(u64_to_address(ED25519_VERIFY)));
+    let env = Env::default();
+
+    println!(
+        "{:<24} {:>10} {:>12} {:>10}",
+        "precompile", "gas", "ns/call", "ns/gas"
+    );
+    for (name, address, input) in inputs() {
+        let Some(precompile) = precompiles.get(&address) else {
+            println!("{name:<24} not in the precompile set");
+            continue;
+        };
+        let mut precompile = precompile.clone();
+        let gas = match precompile.call(&input, u64::MAX, &env) {
+            Ok((gas, _)) => gas,
+            Err(e) => {
+                println!("{name:<24} failed: {e}");
+                continue;
+            }
+        };
+
+        let mut calls = 0u32;
+        let start = Instant::now();
+        while start.elapsed() < MEASUREMENT_TIME {
+            let _ = precompile.call(&input, u64::MAX, &env);
+            calls += 1;
+        }
+        let ns_per_call = start.elapsed().as_nanos() as f64 / calls as f64;
+        println!(
+            "{name:<24} {gas:>10} {ns_per_call:>12.0} {:>10.2}",
+            ns_per_call / gas.max(1) as f64
+        );
+    }
+}
+
+/// Name, address and input of every measured case.
+fn inputs() -> Vec<(&'static str, Address, Bytes)> {
+    let ecrecover = hex!(
+        "456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3"
+        "000000000000000000000000000000000000000000000000000000000000001c"
+        "9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608"
+        "4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada"
+    );
+    let bn128_add = hex!(
+        "18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9"
+        "063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266"
+        "07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed"
+        "06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7"
+    );
+    let bn128_mul = hex!(
+        "2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7"
+        "21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204"
+        "00000000000000000000000000000000000000000000000011138ce750fa15c2"
+    );
+    let bn128_pair = hex!(
+        "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59"
+        "3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41"
+        "209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7"
+        "04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678"
+        "2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d"
+        "120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550"
+        "111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c"
+        "2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411"
+        "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"
+        "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"
+        "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"
+        "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+    );
+    let p256_verify = hex!(
+        "9082a6391351e92890ea193cefe6b8594f34cab50b7161ec4616834b15cd9a51"
+        "e830cac017e20da44f6f111b17002f4b5ae2e4325e7536a0a9b87f9db783ebd8"
+        "979f04e5b3546d770b0bb6dd5221913e16eea0cc96f4650cda96082dcf121c9b"
+        "26bad900c81fcd985dd3aaa38b6df28d616ee27f57e0186948928f70ddb7cb17"
+        "3f0299ce287a75ffe3ab0e348934cce4e07f2f6402bb169df31054ba9810ac01"
+    );
+    // RFC 8032 test 1, empty message.
+    let ed25519_verify = hex!(
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+        "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+    );
+    let mut ed25519_verify_kb = ed25519_verify.to_vec();
+    ed25519_verify_kb.extend([0xab; 1024]);
+
+    // 32 byte base, exponent and modulus.
+    let mut modexp = vec![0u8; 96];
+    for length in modexp.chunks_mut(32) {
+        length[31] = 32;
+    }
+    modexp.extend([0xff; 96]);
+
+    // 12 rounds over zero state and message, final block.
+    let mut blake2 = vec![0u8; 213];
+    blake2[3] = 12;
+    blake2[212] = 1;
+
+    let kb = vec![0xab; 1024];
+    vec![
+        ("ecrecover", u64_to_address(1), ecrecover.into()),
+        ("sha256 1KiB", u64_to_address(2), kb.clone().into()),
+        ("ripemd160 1KiB", u64_to_address(3), kb.clone().into()),
+        ("identity 1KiB", u64_to_address(4), kb.into()),
+        ("modexp 32 byte", u64_to_address(5), modexp.into()),
+        ("bn128 add", u64_to_address(6), bn128_add.into()),
+        ("bn128 mul", u64_to_address(7), bn128_mul.into()),
+        (
+            "bn128 pairing 2 pairs",
+            u64_to_address(8),
+            bn128_pair.into(),
+        ),
+        ("blake2f 12 rounds", u64_to_address(9), blake2.into()),
+        ("p256verify", secp256r1::P256VERIFY.0, p256_verify.into()),
+        (
+            "ed25519 verify",
+            u64_to_address(ED25519_VERIFY),
+            ed25519_verify.into(),
+        ),
+        (
+            "ed25519 verify 1KiB",
+            u64_to_address(ED25519_VERIFY),
+            ed25519_verify_kb.into(),
+        ),
+    ]
+}
@@ -2,10 +2,12 @@
 
 mod calc;
 mod constants;
+mod witness;
 
 pub use calc::*;
 pub use constants::*;
 use revm_primitives::{Spec, SpecId::LONDON};
+pub use witness::*;
 
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -88,7 +90,15 @@ impl Gas {
     /// Related to EIP-3529: Reduction in refunds
     pub fn set_final_refund<SPEC: Spec>(&mut self) {
         let max_refund_quotient = if SPEC::enabled(LONDON) { 5 } else { 2 };
-        self.refunded = (self.refunded() as u64).min(self.spend() / max_refund_quotient) as i64;
+        self.set_final_refund_with_quotient(max_refund_quotient);
+    }
+
+    /// Set a refund value for final refund, limited to the `max_refund_quotient`th part of gas spend.
+    ///
+    /// Quotient of zero is treated as one.
+    pub fn set_final_refund_with_quotient(&mut self, max_refund_quotient: u64) {
+        self.refunded =
+            (self.refunded() as u64).min(self.spend() / max_refund_quotient.max(1)) as i64;
     }
 
     /// Set a refund value
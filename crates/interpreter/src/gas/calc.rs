@@ -1,17 +1,32 @@
 use super::constants::*;
 use crate::inner_models::SelfDestructResult;
-use crate::primitives::{Address, Spec, SpecId::*, U256};
+use crate::primitives::{Address, RefundPolicy, Spec, SpecId::*, U256};
 use std::vec::Vec;
 
-#[allow(clippy::collapsible_else_if)]
 pub fn sstore_refund<SPEC: Spec>(original: U256, current: U256, new: U256) -> i64 {
+    sstore_refund_with_policy::<SPEC>(
+        RefundPolicy::from_spec_id(SPEC::SPEC_ID),
+        original,
+        current,
+        new,
+    )
+}
+
+/// `SSTORE` refund with the refund policy that is independent of the spec.
+#[allow(clippy::collapsible_else_if)]
+pub fn sstore_refund_with_policy<SPEC: Spec>(
+    policy: RefundPolicy,
+    original: U256,
+    current: U256,
+    new: U256,
+) -> i64 {
+    // EIP-3529: Reduction in refunds
+    let sstore_clears_schedule = if policy.is_eip3529() {
+        (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
+    } else {
+        REFUND_SSTORE_CLEARS
+    };
     if SPEC::enabled(ISTANBUL) {
-        // EIP-3529: Reduction in refunds
-        let sstore_clears_schedule = if SPEC::enabled(LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
-        } else {
-            REFUND_SSTORE_CLEARS
-        };
         if current == new {
             0
         } else {
@@ -46,7 +61,7 @@ pub fn sstore_refund<SPEC: Spec>(original: U256, current: U256, new: U256) -> i6
         }
     } else {
         if current != U256::ZERO && new == U256::ZERO {
-            REFUND_SSTORE_CLEARS
+            sstore_clears_schedule
         } else {
             0
         }
@@ -55,14 +70,14 @@ pub fn sstore_refund<SPEC: Spec>(original: U256, current: U256, new: U256) -> i6
 
 #[inline]
 pub fn create2_cost(len: usize) -> Option<u64> {
-    let base = CREATE;
-    // ceil(len / 32.0)
-    let len = len as u64;
-    let sha_addup_base = (len / 32) + u64::from((len % 32) != 0);
-    let sha_addup = KECCAK256WORD.checked_mul(sha_addup_base)?;
-    let gas = base.checked_add(sha_addup)?;
+    CREATE.checked_add(create2_hash_cost(len)?)
+}
 
-    Some(gas)
+/// EIP-1014: Skinny CREATE2, cost of hashing the initcode, it is charged on top of the create cost.
+#[inline]
+pub fn create2_hash_cost(len: usize) -> Option<u64> {
+    // ceil(len / 32.0)
+    KECCAK256WORD.checked_mul((len as u64).div_ceil(32))
 }
 
 #[inline]
@@ -344,10 +359,14 @@ pub fn memory_gas(a: usize) -> u64 {
 
 /// Initial gas that is deducted for transaction to be included.
 /// Initial gas contains initial stipend gas, gas for access list and input data.
+///
+/// `create_gas` is the base cost of the contract creation that a create transaction pays on
+/// top of the transaction stipend.
 pub fn validate_initial_tx_gas<SPEC: Spec>(
     input: &[u8],
     is_create: bool,
     access_list: &[(Address, Vec<U256>)],
+    create_gas: u64,
 ) -> u64 {
     let mut initial_gas = 0;
     let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
@@ -371,7 +390,7 @@ pub fn validate_initial_tx_gas<SPEC: Spec>(
     initial_gas += if is_create {
         if SPEC::enabled(HOMESTEAD) {
             // EIP-2: Homestead Hard-fork Changes
-            53000
+            21000 + create_gas
         } else {
             21000
         }
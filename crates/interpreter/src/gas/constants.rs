@@ -20,6 +20,13 @@ pub const COPY: u64 = 3;
 pub const BLOCKHASH: u64 = 20;
 pub const CODEDEPOSIT: u64 = 200;
 
+/// EIP-4200: Static relative jumps
+pub const CONDITION_JUMP_GAS: u64 = 4;
+/// EIP-4750: EOF - Functions
+pub const RETF_GAS: u64 = 3;
+/// EIP-7480: EOF - Data section access instructions
+pub const DATA_LOAD_GAS: u64 = 4;
+
 /// EIP-1884: Repricing for trie-size-dependent opcodes
 pub const INSTANBUL_SLOAD_GAS: u64 = 800;
 pub const SSTORE_SET: u64 = 20000;
@@ -42,3 +49,19 @@ pub const WARM_SSTORE_RESET: u64 = SSTORE_RESET - COLD_SLOAD_COST;
 pub const INITCODE_WORD_COST: u64 = 2;
 
 pub const CALL_STIPEND: u64 = 2300;
+/// EIP-7069: Gas the caller keeps at least after `EXTCALL`.
+pub const MIN_RETAINED_GAS: u64 = 5000;
+/// EIP-7069: `EXTCALL` fails without the call if the callee would get less gas.
+pub const MIN_CALLEE_GAS: u64 = CALL_STIPEND;
+
+/// EIP-4762: Statelessness gas cost changes
+pub const WITNESS_BRANCH_COST: u64 = 1900;
+pub const WITNESS_CHUNK_COST: u64 = 200;
+pub const SUBTREE_EDIT_COST: u64 = 3000;
+pub const CHUNK_EDIT_COST: u64 = 500;
+pub const CHUNK_FILL_COST: u64 = 6200;
+
+/// EIP-3074: `AUTH` static cost, the cold authority surcharge is charged on top.
+pub const AUTH: u64 = 3100;
+/// EIP-3074: `AUTHCALL` value transfer cost, there is no stipend given to the callee.
+pub const AUTHCALL_VALUE: u64 = CALLVALUE - CALL_STIPEND;
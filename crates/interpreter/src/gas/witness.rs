@@ -0,0 +1,200 @@
+//! EIP-4762: Statelessness gas cost changes.
+
+use super::constants::*;
+use crate::primitives::{Address, HashSet, U256};
+
+/// Sub index of the account version, nonce, balance and code size leaf.
+pub const BASIC_DATA_LEAF_KEY: u8 = 0;
+/// Sub index of the account code hash leaf.
+pub const CODEHASH_LEAF_KEY: u8 = 1;
+/// Position of the first storage slot kept in the account header.
+pub const HEADER_STORAGE_OFFSET: u64 = 64;
+/// Position of the first code chunk in the account header.
+pub const CODE_OFFSET: u64 = 128;
+/// Number of leaves of a tree node.
+pub const VERKLE_NODE_WIDTH: u64 = 256;
+/// Code bytes in one code chunk, the first byte of the chunk is the push data offset.
+pub const CODE_CHUNK_SIZE: usize = 31;
+
+/// Leaf of the account in the stateless tree.
+///
+/// Gas only depends on which branches and leaves are accessed, so the leaf is identified by
+/// the account with its tree index and sub index instead of the tree key hash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WitnessKey {
+    /// Account of the leaf.
+    pub address: Address,
+    /// Index of the branch (stem) of the leaf.
+    pub tree_index: U256,
+    /// Index of the leaf in its branch.
+    pub sub_index: u8,
+}
+
+impl WitnessKey {
+    /// Leaf of the account version, nonce, balance and code size.
+    pub const fn basic_data(address: Address) -> Self {
+        Self {
+            address,
+            tree_index: U256::ZERO,
+            sub_index: BASIC_DATA_LEAF_KEY,
+        }
+    }
+
+    /// Leaf of the account code hash.
+    pub const fn code_hash(address: Address) -> Self {
+        Self {
+            address,
+            tree_index: U256::ZERO,
+            sub_index: CODEHASH_LEAF_KEY,
+        }
+    }
+
+    /// Leaf of the code chunk with the index `chunk`.
+    pub fn code_chunk(address: Address, chunk: u64) -> Self {
+        let position = CODE_OFFSET + chunk;
+        Self {
+            address,
+            tree_index: U256::from(position / VERKLE_NODE_WIDTH),
+            sub_index: (position % VERKLE_NODE_WIDTH) as u8,
+        }
+    }
+
+    /// Leaf of the storage `slot`, the first slots are kept in the account header.
+    pub fn storage_slot(address: Address, slot: U256) -> Self {
+        if slot < U256::from(CODE_OFFSET - HEADER_STORAGE_OFFSET) {
+            return Self {
+                address,
+                tree_index: U256::ZERO,
+                sub_index: (HEADER_STORAGE_OFFSET + slot.to::<u64>()) as u8,
+            };
+        }
+        // main storage starts at `256^31`, which is aligned to the node width.
+        Self {
+            address,
+            tree_index: (U256::from(1) << 240) + (slot >> 8),
+            sub_index: slot.byte(0),
+        }
+    }
+}
+
+/// Branches and leaves accessed by the transaction, it charges every access only once.
+///
+/// Accesses are not reverted with the frame that made them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessWitness {
+    /// Read branches, by the account and tree index.
+    pub branches: HashSet<(Address, U256)>,
+    /// Read leaves.
+    pub leaves: HashSet<WitnessKey>,
+    /// Written branches, by the account and tree index.
+    pub edited_branches: HashSet<(Address, U256)>,
+    /// Written leaves.
+    pub edited_leaves: HashSet<WitnessKey>,
+}
+
+impl AccessWitness {
+    /// Records reading the `key` and returns the gas of the newly read branch and leaf.
+    pub fn read(&mut self, key: WitnessKey) -> u64 {
+        let mut gas = 0;
+        if self.branches.insert((key.address, key.tree_index)) {
+            gas += WITNESS_BRANCH_COST;
+        }
+        if self.leaves.insert(key) {
+            gas += WITNESS_CHUNK_COST;
+        }
+        gas
+    }
+
+    /// Records writing the `key`, `fill` if the leaf had no value before.
+    ///
+    /// Write is also a read, returns the gas of both.
+    pub fn write(&mut self, key: WitnessKey, fill: bool) -> u64 {
+        let mut gas = self.read(key);
+        if self.edited_branches.insert((key.address, key.tree_index)) {
+            gas += SUBTREE_EDIT_COST;
+        }
+        if self.edited_leaves.insert(key) {
+            gas += CHUNK_EDIT_COST;
+            if fill {
+                gas += CHUNK_FILL_COST;
+            }
+        }
+        gas
+    }
+
+    /// Clears all accesses, called at the end of the transaction.
+    pub fn clear(&mut self) {
+        self.branches.clear();
+        self.leaves.clear();
+        self.edited_branches.clear();
+        self.edited_leaves.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn witness_keys() {
+        let address = Address::repeat_byte(1);
+        assert_eq!(
+            WitnessKey::storage_slot(address, U256::from(63)),
+            WitnessKey {
+                address,
+                tree_index: U256::ZERO,
+                sub_index: 127,
+            }
+        );
+        assert_eq!(
+            WitnessKey::storage_slot(address, U256::from(0x1234)),
+            WitnessKey {
+                address,
+                tree_index: (U256::from(1) << 240) + U256::from(0x12),
+                sub_index: 0x34,
+            }
+        );
+        assert_eq!(
+            WitnessKey::storage_slot(address, U256::MAX).tree_index,
+            (U256::from(1) << 240) + (U256::MAX >> 8)
+        );
+        assert_eq!(
+            WitnessKey::code_chunk(address, 127),
+            WitnessKey {
+                address,
+                tree_index: U256::ZERO,
+                sub_index: 255,
+            }
+        );
+        assert_eq!(
+            WitnessKey::code_chunk(address, 128).tree_index,
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn access_witness_charges_once() {
+        let address = Address::repeat_byte(1);
+        let mut witness = AccessWitness::default();
+        assert_eq!(
+            witness.read(WitnessKey::basic_data(address)),
+            WITNESS_BRANCH_COST + WITNESS_CHUNK_COST
+        );
+        assert_eq!(
+            witness.read(WitnessKey::code_hash(address)),
+            WITNESS_CHUNK_COST
+        );
+        assert_eq!(witness.read(WitnessKey::code_hash(address)), 0);
+        assert_eq!(
+            witness.write(WitnessKey::basic_data(address), true),
+            SUBTREE_EDIT_COST + CHUNK_EDIT_COST + CHUNK_FILL_COST
+        );
+        assert_eq!(
+            witness.write(WitnessKey::code_hash(address), true),
+            CHUNK_EDIT_COST + CHUNK_FILL_COST
+        );
+        assert_eq!(witness.write(WitnessKey::code_hash(address), true), 0);
+    }
+}
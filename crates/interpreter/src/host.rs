@@ -1,7 +1,10 @@
 use crate::{
+    gas::WitnessKey,
     primitives::{Address, Bytecode, Env, Log, B256, U256},
     SelfDestructResult,
 };
+#[cfg(feature = "experimental-eips")]
+use revm_primitives::alloy_primitives::B512;
 
 mod dummy;
 pub use dummy::DummyHost;
@@ -50,6 +53,47 @@ pub trait Host {
 
     /// Mark `address` to be deleted, with funds transferred to `target`.
     fn selfdestruct(&mut self, address: Address, target: Address) -> Option<SelfDestructResult>;
+
+    /// EIP-4762: Record reading the witness `key` and return its gas.
+    ///
+    /// Default returns `0`, the host does not track a witness.
+    #[inline]
+    fn witness_read(&mut self, key: WitnessKey) -> u64 {
+        let _ = key;
+        0
+    }
+
+    /// EIP-4762: Record writing the witness `key`, `fill` if it had no value, and return its gas.
+    ///
+    /// Default returns `0`, the host does not track a witness.
+    #[inline]
+    fn witness_write(&mut self, key: WitnessKey, fill: bool) -> u64 {
+        let _ = key;
+        let _ = fill;
+        0
+    }
+
+    /// Get nonce of `address` and if the account is cold.
+    ///
+    /// Default returns `None`, `AUTH` then fails as the host does not support it.
+    #[cfg(feature = "experimental-eips")]
+    #[inline]
+    fn nonce(&mut self, address: Address) -> Option<(u64, bool)> {
+        let _ = address;
+        None
+    }
+
+    /// Recover the signer of the `msg` hash, `None` if the signature is invalid.
+    ///
+    /// Default returns `None`, no signature is then valid.
+    #[cfg(feature = "experimental-eips")]
+    #[inline]
+    fn ecrecover(&mut self, sig: &B512, recid: u8, msg: &B256) -> Option<Address> {
+        let _ = sig;
+        let _ = recid;
+        let _ = msg;
+        None
+    }
 }
 
 /// Represents the result of an `sstore` operation.
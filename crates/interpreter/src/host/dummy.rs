@@ -1,5 +1,6 @@
 use crate::primitives::{hash_map::Entry, Bytecode, HashMap, U256};
 use crate::{
+    gas::{AccessWitness, WitnessKey},
     primitives::{Address, Env, Log, B256, KECCAK_EMPTY},
     Host, SStoreResult, SelfDestructResult,
 };
@@ -12,6 +13,7 @@ pub struct DummyHost {
     pub storage: HashMap<U256, U256>,
     pub transient_storage: HashMap<U256, U256>,
     pub log: Vec<Log>,
+    pub access_witness: AccessWitness,
 }
 
 impl DummyHost {
@@ -29,6 +31,7 @@ impl DummyHost {
     pub fn clear(&mut self) {
         self.storage.clear();
         self.log.clear();
+        self.access_witness.clear();
     }
 }
 
@@ -119,4 +122,31 @@ impl Host for DummyHost {
     fn selfdestruct(&mut self, _address: Address, _target: Address) -> Option<SelfDestructResult> {
         panic!("Selfdestruct is not supported for this host")
     }
+
+    #[inline]
+    fn witness_read(&mut self, key: WitnessKey) -> u64 {
+        self.access_witness.read(key)
+    }
+
+    #[inline]
+    fn witness_write(&mut self, key: WitnessKey, fill: bool) -> u64 {
+        self.access_witness.write(key, fill)
+    }
+
+    #[cfg(feature = "experimental-eips")]
+    #[inline]
+    fn nonce(&mut self, _address: Address) -> Option<(u64, bool)> {
+        Some((0, false))
+    }
+
+    #[cfg(feature = "experimental-eips")]
+    #[inline]
+    fn ecrecover(
+        &mut self,
+        _sig: &revm_primitives::alloy_primitives::B512,
+        _recid: u8,
+        _msg: &B256,
+    ) -> Option<Address> {
+        None
+    }
 }
@@ -35,6 +35,8 @@ pub struct CreateInputs {
     pub value: U256,
     /// The init code of the contract.
     pub init_code: Bytes,
+    /// The call data of the EOF initcode, empty for the legacy create.
+    pub input: Bytes,
     /// The gas limit of the call.
     pub gas_limit: u64,
 }
@@ -85,6 +87,7 @@ impl CreateInputs {
             scheme,
             value: tx_env.value,
             init_code: tx_env.data.clone(),
+            input: Bytes::new(),
             gas_limit,
         })
     }
@@ -98,7 +101,7 @@ impl CreateInputs {
     pub fn created_address(&self, nonce: u64) -> Address {
         match self.scheme {
             CreateScheme::Create => self.caller.create(nonce),
-            CreateScheme::Create2 { salt } => self
+            CreateScheme::Create2 { salt } | CreateScheme::EofCreate { salt } => self
                 .caller
                 .create2_from_code(salt.to_be_bytes(), &self.init_code),
         }
@@ -117,6 +120,12 @@ pub enum CallScheme {
     DelegateCall,
     /// `STATICCALL`
     StaticCall,
+    /// EIP-7069: `EXTCALL`
+    ExtCall,
+    /// EIP-7069: `EXTDELEGATECALL`
+    ExtDelegateCall,
+    /// EIP-7069: `EXTSTATICCALL`
+    ExtStaticCall,
 }
 
 /// Context of a runtime call.
@@ -10,11 +10,17 @@ pub enum InstructionResult {
     Stop,
     Return,
     SelfDestruct,
+    /// EIP-7620: EOF initcode returned the container to deploy.
+    ReturnContract,
 
     // revert codes
     Revert = 0x10, // revert opcode
     CallTooDeep,
     OutOfFunds,
+    /// EIP-7069: `EXTDELEGATECALL` to the legacy bytecode.
+    InvalidExtDelegateCallTarget,
+    /// EIP-7620: initcode of the EOF create is not a valid initcontainer.
+    InvalidEOFInitCode,
 
     // Actions
     CallOrCreate = 0x20,
@@ -44,6 +50,18 @@ pub enum InstructionResult {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitCodeSizeLimit,
+    /// EOF opcode in the legacy bytecode.
+    EOFOpcodeDisabledInLegacy,
+    /// EIP-4750: `CALLF` exceeds the depth of the function return stack.
+    EOFFunctionStackOverflow,
+    /// EIP-7069: `EXTCALL` target address has nonzero high bytes.
+    InvalidEXTCALLTarget,
+    /// EIP-7620: `RETURNCONTRACT` auxiliary data makes the data section larger than `u16::MAX`.
+    EofAuxDataOverflow,
+    /// EIP-7620: data section is shorter than declared after appending the auxiliary data.
+    EofAuxDataTooSmall,
+    /// EIP-3074: `AUTHCALL` without a successful `AUTH` in the current frame.
+    AuthorizedNotSet,
 
     /// Fatal external error. Returned by database.
     FatalExternalError,
@@ -55,6 +73,7 @@ impl From<SuccessReason> for InstructionResult {
             SuccessReason::Return => InstructionResult::Return,
             SuccessReason::Stop => InstructionResult::Stop,
             SuccessReason::SelfDestruct => InstructionResult::SelfDestruct,
+            SuccessReason::EofReturnContract => InstructionResult::ReturnContract,
         }
     }
 }
@@ -87,6 +106,11 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::CallNotAllowedInsideStatic => Self::CallNotAllowedInsideStatic,
             HaltReason::OutOfFunds => Self::OutOfFunds,
             HaltReason::CallTooDeep => Self::CallTooDeep,
+            HaltReason::EOFFunctionStackOverflow => Self::EOFFunctionStackOverflow,
+            HaltReason::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            HaltReason::EofAuxDataOverflow => Self::EofAuxDataOverflow,
+            HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
+            HaltReason::AuthorizedNotSet => Self::AuthorizedNotSet,
             #[cfg(feature = "optimism")]
             HaltReason::FailedDeposit => Self::FatalExternalError,
         }
@@ -100,13 +124,18 @@ macro_rules! return_ok {
             | InstructionResult::Stop
             | InstructionResult::Return
             | InstructionResult::SelfDestruct
+            | InstructionResult::ReturnContract
     };
 }
 
 #[macro_export]
 macro_rules! return_revert {
     () => {
-        InstructionResult::Revert | InstructionResult::CallTooDeep | InstructionResult::OutOfFunds
+        InstructionResult::Revert
+            | InstructionResult::CallTooDeep
+            | InstructionResult::OutOfFunds
+            | InstructionResult::InvalidExtDelegateCallTarget
+            | InstructionResult::InvalidEOFInitCode
     };
 }
 
@@ -134,6 +163,12 @@ macro_rules! return_error {
             | InstructionResult::CreateContractSizeLimit
             | InstructionResult::CreateContractStartingWithEF
             | InstructionResult::CreateInitCodeSizeLimit
+            | InstructionResult::EOFOpcodeDisabledInLegacy
+            | InstructionResult::EOFFunctionStackOverflow
+            | InstructionResult::InvalidEXTCALLTarget
+            | InstructionResult::EofAuxDataOverflow
+            | InstructionResult::EofAuxDataTooSmall
+            | InstructionResult::AuthorizedNotSet
             | InstructionResult::FatalExternalError
     };
 }
@@ -215,7 +250,10 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::Stop => Self::Success(SuccessReason::Stop),
             InstructionResult::Return => Self::Success(SuccessReason::Return),
             InstructionResult::SelfDestruct => Self::Success(SuccessReason::SelfDestruct),
-            InstructionResult::Revert => Self::Revert,
+            InstructionResult::ReturnContract => Self::Success(SuccessReason::EofReturnContract),
+            InstructionResult::Revert
+            | InstructionResult::InvalidExtDelegateCallTarget
+            | InstructionResult::InvalidEOFInitCode => Self::Revert,
             InstructionResult::CallOrCreate => Self::InternalCallOrCreate, // used only in interpreter loop
             InstructionResult::CallTooDeep => Self::Halt(HaltReason::CallTooDeep), // not gonna happen for first call
             InstructionResult::OutOfFunds => Self::Halt(HaltReason::OutOfFunds), // Check for first call is done separately.
@@ -230,7 +268,9 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::InvalidOperandOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::InvalidOperand))
             }
-            InstructionResult::OpcodeNotFound => Self::Halt(HaltReason::OpcodeNotFound),
+            InstructionResult::OpcodeNotFound | InstructionResult::EOFOpcodeDisabledInLegacy => {
+                Self::Halt(HaltReason::OpcodeNotFound)
+            }
             InstructionResult::CallNotAllowedInsideStatic => {
                 Self::Halt(HaltReason::CallNotAllowedInsideStatic)
             } // first call is not static call
@@ -254,6 +294,13 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::CreateInitCodeSizeLimit => {
                 Self::Halt(HaltReason::CreateInitCodeSizeLimit)
             }
+            InstructionResult::EOFFunctionStackOverflow => {
+                Self::Halt(HaltReason::EOFFunctionStackOverflow)
+            }
+            InstructionResult::InvalidEXTCALLTarget => Self::Halt(HaltReason::InvalidEXTCALLTarget),
+            InstructionResult::EofAuxDataOverflow => Self::Halt(HaltReason::EofAuxDataOverflow),
+            InstructionResult::EofAuxDataTooSmall => Self::Halt(HaltReason::EofAuxDataTooSmall),
+            InstructionResult::AuthorizedNotSet => Self::Halt(HaltReason::AuthorizedNotSet),
             InstructionResult::FatalExternalError => Self::FatalExternalError,
         }
     }
@@ -280,6 +327,7 @@ mod tests {
             InstructionResult::Stop,
             InstructionResult::Return,
             InstructionResult::SelfDestruct,
+            InstructionResult::ReturnContract,
         ];
 
         for result in ok_results {
@@ -292,6 +340,8 @@ mod tests {
             InstructionResult::Revert,
             InstructionResult::CallTooDeep,
             InstructionResult::OutOfFunds,
+            InstructionResult::InvalidExtDelegateCallTarget,
+            InstructionResult::InvalidEOFInitCode,
         ];
 
         for result in revert_results {
@@ -322,6 +372,12 @@ mod tests {
             InstructionResult::CreateContractSizeLimit,
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
+            InstructionResult::EOFOpcodeDisabledInLegacy,
+            InstructionResult::EOFFunctionStackOverflow,
+            InstructionResult::InvalidEXTCALLTarget,
+            InstructionResult::EofAuxDataOverflow,
+            InstructionResult::EofAuxDataTooSmall,
+            InstructionResult::AuthorizedNotSet,
             InstructionResult::FatalExternalError,
         ];
 
@@ -6,6 +6,7 @@ pub mod macros;
 pub mod arithmetic;
 pub mod bitwise;
 pub mod control;
+pub mod data;
 pub mod host;
 pub mod host_env;
 pub mod i256;
@@ -1,9 +1,115 @@
 use crate::{
     gas,
-    primitives::{Bytes, Spec, U256},
-    Host, InstructionResult, Interpreter, InterpreterResult,
+    primitives::{Bytes, Eof, Spec, U256},
+    Host, InstructionResult, Interpreter, InterpreterResult, FUNCTION_STACK_LIMIT, STACK_LIMIT,
 };
 
+/// Reads the big endian `i16` immediate at the instruction pointer.
+///
+/// # Safety
+///
+/// EOF validation guarantees that the immediate bytes are inside the code section.
+#[inline(always)]
+unsafe fn read_i16(ptr: *const u8) -> isize {
+    i16::from_be_bytes(core::slice::from_raw_parts(ptr, 2).try_into().unwrap()) as isize
+}
+
+/// Reads the big endian `u16` immediate at the instruction pointer, see [read_i16].
+#[inline(always)]
+unsafe fn read_u16(ptr: *const u8) -> usize {
+    u16::from_be_bytes(core::slice::from_raw_parts(ptr, 2).try_into().unwrap()) as usize
+}
+
+/// EIP-4200: Static relative jumps
+pub fn rjump<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::BASE);
+    // Offset is relative to the end of the instruction, the instruction pointer is already
+    // past the opcode.
+    let offset = unsafe { read_i16(interpreter.instruction_pointer) } + 2;
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.offset(offset) };
+}
+
+pub fn rjumpi<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::CONDITION_JUMP_GAS);
+    pop!(interpreter, condition);
+    let mut offset = 2;
+    if condition != U256::ZERO {
+        offset += unsafe { read_i16(interpreter.instruction_pointer) };
+    }
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.offset(offset) };
+}
+
+pub fn rjumpv<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::CONDITION_JUMP_GAS);
+    pop!(interpreter, case);
+    let case = as_usize_saturated!(case);
+    let max_index = unsafe { *interpreter.instruction_pointer } as usize;
+    // max index byte and two bytes per offset.
+    let mut offset = (max_index as isize + 1) * 2 + 1;
+    if case <= max_index {
+        offset += unsafe { read_i16(interpreter.instruction_pointer.add(1 + case * 2)) };
+    }
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.offset(offset) };
+}
+
+/// EIP-4750: EOF - Functions
+pub fn callf<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::LOW);
+    let idx = unsafe { read_u16(interpreter.instruction_pointer) };
+    if interpreter.function_stack.return_stack_len() >= FUNCTION_STACK_LIMIT {
+        interpreter.instruction_result = InstructionResult::EOFFunctionStackOverflow;
+        return;
+    }
+    if !function_stack_fits(interpreter, idx) {
+        interpreter.instruction_result = InstructionResult::StackOverflow;
+        return;
+    }
+    // Return after the two immediate bytes.
+    let pc = interpreter.program_counter() + 2;
+    interpreter.function_stack.push(pc, idx);
+    interpreter.load_eof_code(idx, 0);
+}
+
+pub fn retf<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::RETF_GAS);
+    let Some(frame) = interpreter.function_stack.pop() else {
+        // validation does not allow `RETF` in the first code section.
+        panic!("function return stack is empty in RETF");
+    };
+    interpreter.load_eof_code(frame.idx, frame.pc);
+}
+
+/// EIP-6206: EOF - JUMPF and non-returning functions
+pub fn jumpf<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::LOW);
+    let idx = unsafe { read_u16(interpreter.instruction_pointer) };
+    if !function_stack_fits(interpreter, idx) {
+        interpreter.instruction_result = InstructionResult::StackOverflow;
+        return;
+    }
+    interpreter.function_stack.set_current_code_idx(idx);
+    interpreter.load_eof_code(idx, 0);
+}
+
+/// Returns whether the max stack height of the code section fits into the stack.
+#[inline]
+fn function_stack_fits(interpreter: &Interpreter, idx: usize) -> bool {
+    let types = &interpreter
+        .contract
+        .bytecode
+        .eof()
+        .expect("bytecode is EOF")
+        .body
+        .types_section[idx];
+    interpreter.stack.len() + types.max_stack_size as usize - types.inputs as usize <= STACK_LIMIT
+}
+
 pub fn jump<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::MID);
     pop!(interpreter, dest);
@@ -67,6 +173,50 @@ pub fn ret<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
     return_inner(interpreter, InstructionResult::Return);
 }
 
+/// EIP-7620: `RETURNCONTRACT` ends the initcode with the container section to deploy, the
+/// auxiliary data from memory is appended to its data section.
+pub fn return_contract<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    let idx = unsafe { *interpreter.instruction_pointer } as usize;
+    pop!(interpreter, aux_data_offset, aux_data_len);
+    let aux_data_len = as_usize_or_fail!(interpreter, aux_data_len);
+    let mut aux_data: &[u8] = &[];
+    if aux_data_len != 0 {
+        let aux_data_offset = as_usize_or_fail!(interpreter, aux_data_offset);
+        shared_memory_resize!(interpreter, aux_data_offset, aux_data_len);
+        aux_data = interpreter
+            .shared_memory
+            .slice(aux_data_offset, aux_data_len);
+    }
+
+    let container = &interpreter
+        .contract
+        .bytecode
+        .eof()
+        .expect("bytecode is EOF")
+        .body
+        .container_section[idx];
+    let container = Eof::decode(container.clone()).expect("subcontainers are validated");
+    let data_size = container.data().len() + aux_data.len();
+    if data_size > u16::MAX as usize {
+        interpreter.instruction_result = InstructionResult::EofAuxDataOverflow;
+        return;
+    }
+    let Some(container) = container.with_aux_data(aux_data) else {
+        interpreter.instruction_result = InstructionResult::EofAuxDataTooSmall;
+        return;
+    };
+
+    interpreter.instruction_result = InstructionResult::ReturnContract;
+    interpreter.next_action = crate::InterpreterAction::Return {
+        result: InterpreterResult {
+            output: container.raw,
+            gas: interpreter.gas,
+            result: InstructionResult::ReturnContract,
+        },
+    };
+}
+
 /// EIP-140: REVERT instruction
 pub fn revert<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
     check!(interpreter, BYZANTIUM);
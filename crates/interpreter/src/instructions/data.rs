@@ -0,0 +1,79 @@
+use crate::{
+    gas,
+    primitives::{B256, U256},
+    Host, InstructionResult, Interpreter,
+};
+
+/// Returns the data section of the EOF bytecode.
+macro_rules! eof_data {
+    ($interp:expr) => {
+        $interp
+            .contract
+            .bytecode
+            .eof()
+            .expect("bytecode is EOF")
+            .data()
+    };
+}
+
+/// Reads 32 bytes of the data at the offset, padded with zeros past the end.
+#[inline]
+fn load_word(data: &[u8], offset: usize) -> B256 {
+    let mut word = B256::ZERO;
+    if offset < data.len() {
+        let len = 32.min(data.len() - offset);
+        word[..len].copy_from_slice(&data[offset..offset + len]);
+    }
+    word
+}
+
+/// EIP-7480: EOF - Data section access instructions
+pub fn data_load<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::DATA_LOAD_GAS);
+    pop!(interpreter, offset);
+    let offset = as_usize_saturated!(offset);
+    let word = load_word(eof_data!(interpreter), offset);
+    push_b256!(interpreter, word);
+}
+
+pub fn data_loadn<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::VERYLOW);
+    // SAFETY: EOF validation guarantees the two immediate bytes.
+    let offset = unsafe {
+        u16::from_be_bytes(
+            core::slice::from_raw_parts(interpreter.instruction_pointer, 2)
+                .try_into()
+                .unwrap(),
+        )
+    } as usize;
+    let word = load_word(eof_data!(interpreter), offset);
+    push_b256!(interpreter, word);
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(2) };
+}
+
+pub fn data_size<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::BASE);
+    let size = eof_data!(interpreter).len();
+    push!(interpreter, U256::from(size));
+}
+
+pub fn data_copy<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    pop!(interpreter, memory_offset, offset, len);
+    let len = as_usize_or_fail!(interpreter, len);
+    gas_or_fail!(interpreter, gas::verylowcopy_cost(len as u64));
+    if len == 0 {
+        return;
+    }
+    let memory_offset = as_usize_or_fail!(interpreter, memory_offset);
+    let offset = as_usize_saturated!(offset);
+    shared_memory_resize!(interpreter, memory_offset, len);
+
+    // Note: this can't panic because we resized memory to fit.
+    interpreter
+        .shared_memory
+        .set_data(memory_offset, offset, len, eof_data!(interpreter));
+}
@@ -1,16 +1,26 @@
+#[cfg(feature = "experimental-eips")]
+mod auth;
 mod call_helpers;
+mod stateless;
 
-pub use call_helpers::{calc_call_gas, get_memory_input_and_out_ranges};
+#[cfg(feature = "experimental-eips")]
+pub use auth::{auth, authcall};
+
+pub use call_helpers::{
+    calc_call_gas, calc_extcall_gas, get_memory_input, get_memory_input_and_out_ranges,
+    pop_extcall_target_address,
+};
+pub use stateless::{code_chunks_gas, code_chunks_instruction, value_transfer_gas};
 
 use crate::{
-    gas::{self, COLD_ACCOUNT_ACCESS_COST, WARM_STORAGE_READ_COST},
+    gas::{self, WitnessKey},
     interpreter::{Interpreter, InterpreterAction},
     primitives::{Address, Bytes, Log, LogData, Spec, SpecId::*, B256, U256},
     CallContext, CallInputs, CallScheme, CreateInputs, CreateScheme, Host, InstructionResult,
-    SStoreResult, Transfer, MAX_INITCODE_SIZE,
+    SStoreResult, Transfer,
 };
 use core::cmp::min;
-use revm_primitives::BLOCK_HASH_HISTORY;
+use revm_primitives::{BLOCKHASH_SERVE_WINDOW, BLOCK_HASH_HISTORY};
 use std::{boxed::Box, vec::Vec};
 
 pub fn balance<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
@@ -19,17 +29,24 @@ pub fn balance<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H)
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    let stateless = SPEC::enabled(VERKLE);
     gas!(
         interpreter,
         if SPEC::enabled(ISTANBUL) {
             // EIP-1884: Repricing for trie-size-dependent opcodes
-            gas::account_access_gas::<SPEC>(is_cold)
+            gas::account_access_gas::<SPEC>(is_cold && !stateless)
         } else if SPEC::enabled(TANGERINE) {
             400
         } else {
             20
         }
     );
+    if stateless {
+        gas!(
+            interpreter,
+            host.witness_read(WitnessKey::basic_data(address))
+        );
+    }
     push!(interpreter, balance);
 }
 
@@ -50,20 +67,23 @@ pub fn extcodesize<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mu
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    let stateless = SPEC::enabled(VERKLE);
     if SPEC::enabled(BERLIN) {
         gas!(
             interpreter,
-            if is_cold {
-                COLD_ACCOUNT_ACCESS_COST
-            } else {
-                WARM_STORAGE_READ_COST
-            }
+            gas::account_access_gas::<SPEC>(is_cold && !stateless)
         );
     } else if SPEC::enabled(TANGERINE) {
         gas!(interpreter, 700);
     } else {
         gas!(interpreter, 20);
     }
+    if stateless {
+        gas!(
+            interpreter,
+            host.witness_read(WitnessKey::basic_data(address))
+        );
+    }
 
     push!(interpreter, U256::from(code.len()));
 }
@@ -76,20 +96,23 @@ pub fn extcodehash<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mu
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    let stateless = SPEC::enabled(VERKLE);
     if SPEC::enabled(BERLIN) {
         gas!(
             interpreter,
-            if is_cold {
-                COLD_ACCOUNT_ACCESS_COST
-            } else {
-                WARM_STORAGE_READ_COST
-            }
+            gas::account_access_gas::<SPEC>(is_cold && !stateless)
         );
     } else if SPEC::enabled(ISTANBUL) {
         gas!(interpreter, 700);
     } else {
         gas!(interpreter, 400);
     }
+    if stateless {
+        gas!(
+            interpreter,
+            host.witness_read(WitnessKey::code_hash(address))
+        );
+    }
     push_b256!(interpreter, code_hash);
 }
 
@@ -103,15 +126,28 @@ pub fn extcodecopy<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mu
     };
 
     let len = as_usize_or_fail!(interpreter, len_u256);
+    let stateless = SPEC::enabled(VERKLE);
     gas_or_fail!(
         interpreter,
-        gas::extcodecopy_cost::<SPEC>(len as u64, is_cold)
+        gas::extcodecopy_cost::<SPEC>(len as u64, is_cold && !stateless)
     );
+    if stateless {
+        gas!(
+            interpreter,
+            host.witness_read(WitnessKey::basic_data(address))
+        );
+    }
     if len == 0 {
         return;
     }
     let memory_offset = as_usize_or_fail!(interpreter, memory_offset);
     let code_offset = min(as_usize_saturated!(code_offset), code.len());
+    if stateless {
+        gas!(
+            interpreter,
+            code_chunks_gas(host, address, code.len(), code_offset, len)
+        );
+    }
     shared_memory_resize!(interpreter, memory_offset, len);
 
     // Note: this can't panic because we resized memory to fit.
@@ -120,14 +156,21 @@ pub fn extcodecopy<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mu
         .set_data(memory_offset, code_offset, len, code.bytes());
 }
 
-pub fn blockhash<H: Host>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn blockhash<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BLOCKHASH);
     pop_top!(interpreter, number);
 
+    // EIP-2935: Serve historical block hashes from state
+    let window = if SPEC::enabled(PRAGUE) {
+        BLOCKHASH_SERVE_WINDOW
+    } else {
+        BLOCK_HASH_HISTORY
+    };
+
     if let Some(diff) = host.env().block.number.checked_sub(*number) {
         let diff = as_usize_saturated!(diff);
         // blockhash should push zero if number is same as current block number.
-        if diff <= BLOCK_HASH_HISTORY && diff != 0 {
+        if diff <= window && diff != 0 {
             let Some(hash) = host.block_hash(*number) else {
                 interpreter.instruction_result = InstructionResult::FatalExternalError;
                 return;
@@ -146,7 +189,12 @@ pub fn sload<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    gas!(interpreter, gas::sload_cost::<SPEC>(is_cold));
+    let stateless = SPEC::enabled(VERKLE);
+    gas!(interpreter, gas::sload_cost::<SPEC>(is_cold && !stateless));
+    if stateless {
+        let key = WitnessKey::storage_slot(interpreter.contract.address, index);
+        gas!(interpreter, host.witness_read(key));
+    }
     push!(interpreter, value);
 }
 
@@ -164,11 +212,28 @@ pub fn sstore<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H)
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    let stateless = SPEC::enabled(VERKLE);
     gas_or_fail!(interpreter, {
         let remaining_gas = interpreter.gas.remaining();
-        gas::sstore_cost::<SPEC>(original, old, new, remaining_gas, is_cold)
+        let cost = gas::sstore_cost::<SPEC>(original, old, new, remaining_gas, is_cold);
+        // EIP-4762: Statelessness gas cost changes, writes are charged by the witness.
+        cost.map(|cost| {
+            if stateless {
+                gas::WARM_STORAGE_READ_COST
+            } else {
+                cost
+            }
+        })
     });
-    refund!(interpreter, gas::sstore_refund::<SPEC>(original, old, new));
+    if stateless {
+        let key = WitnessKey::storage_slot(interpreter.contract.address, index);
+        gas!(interpreter, host.witness_write(key, original == U256::ZERO));
+    }
+    let refund_policy = host.env().cfg.chain.refund_policy_for(SPEC::SPEC_ID);
+    refund!(
+        interpreter,
+        gas::sstore_refund_with_policy::<SPEC>(refund_policy, original, old, new)
+    );
 }
 
 /// EIP-1153: Transient storage opcodes
@@ -231,13 +296,16 @@ pub fn selfdestruct<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &m
     check_staticcall!(interpreter);
     pop_address!(interpreter, target);
 
-    let Some(res) = host.selfdestruct(interpreter.contract.address, target) else {
+    let Some(mut res) = host.selfdestruct(interpreter.contract.address, target) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    // EIP-4762: Statelessness gas cost changes, cold access is charged by the witness.
+    res.is_cold &= !SPEC::enabled(VERKLE);
 
     // EIP-3529: Reduction in refunds
-    if !SPEC::enabled(LONDON) && !res.previously_destroyed {
+    let refund_policy = host.env().cfg.chain.refund_policy_for(SPEC::SPEC_ID);
+    if !refund_policy.is_eip3529() && !res.previously_destroyed {
         refund!(interpreter, gas::SELFDESTRUCT)
     }
     gas!(interpreter, gas::selfdestruct_cost::<SPEC>(res));
@@ -263,14 +331,14 @@ pub fn create<const IS_CREATE2: bool, H: Host, SPEC: Spec>(
     if len != 0 {
         // EIP-3860: Limit and meter initcode
         if SPEC::enabled(SHANGHAI) {
-            // Limit is set as double of max contract bytecode size
-            let max_initcode_size = host
+            // By default limit is set as double of max contract bytecode size
+            if host
                 .env()
                 .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if len > max_initcode_size {
+                .chain
+                .max_initcode_size
+                .is_some_and(|limit| len > limit)
+            {
                 interpreter.instruction_result = InstructionResult::CreateInitCodeSizeLimit;
                 return;
             }
@@ -285,10 +353,11 @@ pub fn create<const IS_CREATE2: bool, H: Host, SPEC: Spec>(
     // EIP-1014: Skinny CREATE2
     let scheme = if IS_CREATE2 {
         pop!(interpreter, salt);
-        gas_or_fail!(interpreter, gas::create2_cost(len));
+        gas!(interpreter, host.env().cfg.chain.create_gas);
+        gas_or_fail!(interpreter, gas::create2_hash_cost(len));
         CreateScheme::Create2 { salt }
     } else {
-        gas!(interpreter, gas::CREATE);
+        gas!(interpreter, host.env().cfg.chain.create_gas);
         CreateScheme::Create
     };
 
@@ -297,7 +366,7 @@ pub fn create<const IS_CREATE2: bool, H: Host, SPEC: Spec>(
     // EIP-150: Gas cost changes for IO-heavy operations
     if SPEC::enabled(TANGERINE) {
         // take remaining gas and deduce l64 part of it.
-        gas_limit -= gas_limit / 64
+        gas_limit = host.env().cfg.chain.max_forwarded_gas(gas_limit);
     }
     gas!(interpreter, gas_limit);
 
@@ -308,6 +377,91 @@ pub fn create<const IS_CREATE2: bool, H: Host, SPEC: Spec>(
             scheme,
             value,
             init_code: code,
+            input: Bytes::new(),
+            gas_limit,
+        }),
+    };
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
+
+/// EIP-7620: `EOFCREATE` deploys the initcontainer of the container section.
+///
+/// Pushes the created address or `0` on failure.
+pub fn eofcreate<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    require_eof!(interpreter);
+    check_staticcall!(interpreter);
+    let idx = unsafe { *interpreter.instruction_pointer } as usize;
+    pop!(interpreter, value, salt);
+    let Some(input) = get_memory_input(interpreter) else {
+        return;
+    };
+    let initcode = interpreter
+        .contract
+        .bytecode
+        .eof()
+        .expect("bytecode is EOF")
+        .body
+        .container_section[idx]
+        .clone();
+    gas!(interpreter, host.env().cfg.chain.create_gas);
+    gas_or_fail!(interpreter, gas::create2_hash_cost(initcode.len()));
+
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
+    eof_create_inner(interpreter, host, initcode, input, value, salt);
+}
+
+/// EIP-7620: `TXCREATE` deploys the initcontainer of the transaction with the given hash.
+///
+/// Pushes `0` without creating if the transaction has no such initcontainer.
+pub fn txcreate<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    require_eof!(interpreter);
+    check_staticcall!(interpreter);
+    pop!(interpreter, initcode_hash, value, salt);
+    let Some(input) = get_memory_input(interpreter) else {
+        return;
+    };
+    let Some(initcode) = host
+        .env()
+        .tx
+        .eof_initcode(B256::from(initcode_hash))
+        .cloned()
+    else {
+        gas!(interpreter, host.env().cfg.chain.create_gas);
+        push!(interpreter, U256::ZERO);
+        return;
+    };
+    gas!(interpreter, host.env().cfg.chain.create_gas);
+    gas_or_fail!(interpreter, gas::create2_hash_cost(initcode.len()));
+    // The initcontainer is validated when the frame is created.
+    gas!(interpreter, gas::initcode_cost(initcode.len() as u64));
+
+    eof_create_inner(interpreter, host, initcode, input, value, salt);
+}
+
+/// Requests the EOF create with all but one 64th of the remaining gas, see
+/// [crate::primitives::ChainConfig::retained_gas_divisor].
+fn eof_create_inner<H: Host>(
+    interpreter: &mut Interpreter,
+    host: &mut H,
+    initcode: Bytes,
+    input: Bytes,
+    value: U256,
+    salt: U256,
+) {
+    let gas_limit = host
+        .env()
+        .cfg
+        .chain
+        .max_forwarded_gas(interpreter.gas().remaining());
+    gas!(interpreter, gas_limit);
+
+    interpreter.next_action = InterpreterAction::Create {
+        inputs: Box::new(CreateInputs {
+            caller: interpreter.contract.address,
+            scheme: CreateScheme::EofCreate { salt },
+            value,
+            init_code: initcode,
+            input,
             gas_limit,
         }),
     };
@@ -520,3 +674,128 @@ pub fn static_call<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mu
     };
     interpreter.instruction_result = InstructionResult::CallOrCreate;
 }
+
+/// EIP-7069: Revamped CALL instructions
+///
+/// Pushes `0` on success, `1` on revert or failure before the call and `2` on failure of the
+/// callee, output is only in the return data.
+pub fn extcall<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    require_eof!(interpreter);
+    let Some(target) = pop_extcall_target_address(interpreter) else {
+        return;
+    };
+    let Some(input) = get_memory_input(interpreter) else {
+        return;
+    };
+    pop!(interpreter, value);
+    let has_transfer = value != U256::ZERO;
+    if interpreter.is_static && has_transfer {
+        interpreter.instruction_result = InstructionResult::CallNotAllowedInsideStatic;
+        return;
+    }
+    let Some(gas_limit) = calc_extcall_gas::<H, SPEC>(interpreter, host, target, has_transfer)
+    else {
+        return;
+    };
+
+    interpreter.next_action = InterpreterAction::Call {
+        inputs: Box::new(CallInputs {
+            contract: target,
+            transfer: Transfer {
+                source: interpreter.contract.address,
+                target,
+                value,
+            },
+            input,
+            gas_limit,
+            context: CallContext {
+                address: target,
+                caller: interpreter.contract.address,
+                code_address: target,
+                apparent_value: value,
+                scheme: CallScheme::ExtCall,
+            },
+            is_static: interpreter.is_static,
+            return_memory_offset: 0..0,
+        }),
+    };
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
+
+/// EIP-7069: `EXTDELEGATECALL`, the target has to be EOF bytecode.
+pub fn extdelegatecall<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    require_eof!(interpreter);
+    let Some(target) = pop_extcall_target_address(interpreter) else {
+        return;
+    };
+    let Some(input) = get_memory_input(interpreter) else {
+        return;
+    };
+    let Some(gas_limit) = calc_extcall_gas::<H, SPEC>(interpreter, host, target, false) else {
+        return;
+    };
+
+    interpreter.next_action = InterpreterAction::Call {
+        inputs: Box::new(CallInputs {
+            contract: target,
+            // This is dummy send for StaticCall and DelegateCall,
+            // it should do nothing and not touch anything.
+            transfer: Transfer {
+                source: interpreter.contract.address,
+                target: interpreter.contract.address,
+                value: U256::ZERO,
+            },
+            input,
+            gas_limit,
+            context: CallContext {
+                address: interpreter.contract.address,
+                caller: interpreter.contract.caller,
+                code_address: target,
+                apparent_value: interpreter.contract.value,
+                scheme: CallScheme::ExtDelegateCall,
+            },
+            is_static: interpreter.is_static,
+            return_memory_offset: 0..0,
+        }),
+    };
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
+
+/// EIP-7069: `EXTSTATICCALL`
+pub fn extstaticcall<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    require_eof!(interpreter);
+    let Some(target) = pop_extcall_target_address(interpreter) else {
+        return;
+    };
+    let Some(input) = get_memory_input(interpreter) else {
+        return;
+    };
+    let Some(gas_limit) = calc_extcall_gas::<H, SPEC>(interpreter, host, target, false) else {
+        return;
+    };
+
+    interpreter.next_action = InterpreterAction::Call {
+        inputs: Box::new(CallInputs {
+            contract: target,
+            // This is dummy send for StaticCall and DelegateCall,
+            // it should do nothing and not touch anything.
+            transfer: Transfer {
+                source: interpreter.contract.address,
+                target: interpreter.contract.address,
+                value: U256::ZERO,
+            },
+            input,
+            gas_limit,
+            context: CallContext {
+                address: target,
+                caller: interpreter.contract.address,
+                code_address: target,
+                apparent_value: U256::ZERO,
+                scheme: CallScheme::ExtStaticCall,
+            },
+            is_static: true,
+            return_memory_offset: 0..0,
+        }),
+    };
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
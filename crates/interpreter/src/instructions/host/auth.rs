@@ -0,0 +1,154 @@
+use super::get_memory_input_and_out_ranges;
+use crate::{
+    gas,
+    interpreter::{Interpreter, InterpreterAction},
+    primitives::{alloy_primitives::B512, keccak256, Address, Spec, B256, U256},
+    CallContext, CallInputs, CallScheme, Host, InstructionResult, Transfer,
+};
+use std::boxed::Box;
+
+/// EIP-3074: Prefix of the message signed by the authority.
+const AUTH_MAGIC: u8 = 0x04;
+
+/// EIP-3074: Size of the `AUTH` memory input, `yParity || r || s || commit`.
+const AUTH_INPUT_LEN: usize = 97;
+
+/// Signatures with `s` greater than half of the secp256k1 curve order are malleable.
+const SECP256K1N_HALF: U256 = U256::from_limbs([
+    0xDFE92F46681B20A0,
+    0x5D576E7357A4501D,
+    0xFFFFFFFFFFFFFFFF,
+    0x7FFFFFFFFFFFFFFF,
+]);
+
+/// EIP-3074: Sets `authorized` to the `authority` if it signed the commit for this invoker.
+///
+/// Pushes `1` on success, otherwise `authorized` is unset and `0` is pushed.
+pub fn auth<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    check!(interpreter, PRAGUE);
+    pop_address!(interpreter, authority);
+    pop!(interpreter, offset, len);
+    let len = as_usize_or_fail!(interpreter, len);
+
+    // bytes past the input are ignored, missing ones are zero.
+    let mut input = [0u8; AUTH_INPUT_LEN];
+    if len != 0 {
+        let offset = as_usize_or_fail!(interpreter, offset);
+        shared_memory_resize!(interpreter, offset, len);
+        let read = len.min(AUTH_INPUT_LEN);
+        input[..read].copy_from_slice(interpreter.shared_memory.slice(offset, read));
+    }
+
+    let Some((code, is_cold)) = host.code(authority) else {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+        return;
+    };
+    gas!(
+        interpreter,
+        gas::AUTH
+            + if is_cold {
+                gas::COLD_ACCOUNT_ACCESS_COST
+            } else {
+                0
+            }
+    );
+
+    interpreter.authorized = None;
+    let y_parity = input[0];
+    let sig = B512::from_slice(&input[1..65]);
+    let s = U256::from_be_slice(&input[33..65]);
+    if !code.is_empty() || y_parity > 1 || s > SECP256K1N_HALF {
+        push!(interpreter, U256::ZERO);
+        return;
+    }
+
+    let Some((nonce, _)) = host.nonce(authority) else {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+        return;
+    };
+    let mut msg = [0u8; 129];
+    msg[0] = AUTH_MAGIC;
+    msg[1..33].copy_from_slice(&U256::from(host.env().cfg.chain_id).to_be_bytes::<32>());
+    msg[33..65].copy_from_slice(&U256::from(nonce).to_be_bytes::<32>());
+    msg[65..97].copy_from_slice(interpreter.contract.address.into_word().as_slice());
+    msg[97..].copy_from_slice(&input[65..]);
+
+    if host.ecrecover(&sig, y_parity, &keccak256(msg)) == Some(authority) {
+        interpreter.authorized = Some(authority);
+        push!(interpreter, U256::from(1));
+    } else {
+        push!(interpreter, U256::ZERO);
+    }
+}
+
+/// EIP-3074: `CALL` with the `authorized` account as the caller and the value source.
+///
+/// The callee gets no stipend and a zero gas operand forwards all available gas.
+pub fn authcall<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    check!(interpreter, PRAGUE);
+    pop!(interpreter, local_gas_limit);
+    pop_address!(interpreter, to);
+    let local_gas_limit = u64::try_from(local_gas_limit).unwrap_or(u64::MAX);
+
+    pop!(interpreter, value);
+    if interpreter.is_static && value != U256::ZERO {
+        interpreter.instruction_result = InstructionResult::CallNotAllowedInsideStatic;
+        return;
+    }
+    let Some(authorized) = interpreter.authorized else {
+        interpreter.instruction_result = InstructionResult::AuthorizedNotSet;
+        return;
+    };
+
+    let Some((input, return_memory_offset)) = get_memory_input_and_out_ranges(interpreter) else {
+        return;
+    };
+
+    let Some((is_cold, exist)) = host.load_account(to) else {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+        return;
+    };
+    let transfers_value = value != U256::ZERO;
+    let call_cost = gas::call_cost::<SPEC>(transfers_value, !exist, is_cold, false, true)
+        + if transfers_value {
+            gas::AUTHCALL_VALUE
+        } else {
+            0
+        };
+    gas!(interpreter, call_cost);
+
+    let remaining = interpreter.gas().remaining();
+    let available = host.env().cfg.chain.max_forwarded_gas(remaining);
+    let gas_limit = match local_gas_limit {
+        0 => available,
+        gas_limit if gas_limit > available => {
+            interpreter.instruction_result = InstructionResult::OutOfGas;
+            return;
+        }
+        gas_limit => gas_limit,
+    };
+    gas!(interpreter, gas_limit);
+
+    interpreter.next_action = InterpreterAction::Call {
+        inputs: Box::new(CallInputs {
+            contract: to,
+            transfer: Transfer {
+                source: authorized,
+                target: to,
+                value,
+            },
+            input,
+            gas_limit,
+            context: CallContext {
+                address: to,
+                caller: authorized,
+                code_address: to,
+                apparent_value: value,
+                scheme: CallScheme::Call,
+            },
+            is_static: interpreter.is_static,
+            return_memory_offset,
+        }),
+    };
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
@@ -1,7 +1,8 @@
+use super::value_transfer_gas;
 use crate::{
-    gas::{self},
+    gas::{self, WitnessKey},
     interpreter::Interpreter,
-    primitives::{Address, Bytes, Spec, SpecId::*},
+    primitives::{Address, Bytes, Spec, SpecId::*, B256, U256},
     Host, InstructionResult,
 };
 use core::{cmp::min, ops::Range};
@@ -47,26 +48,119 @@ pub fn calc_call_gas<H: Host, SPEC: Spec>(
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return None;
     };
-    let is_new = !exist;
+    // EIP-4762: Statelessness gas cost changes, cold access and new account are charged by
+    // the witness.
+    let stateless = SPEC::enabled(VERKLE);
+    let is_new = !exist && !stateless;
 
     let call_cost = gas::call_cost::<SPEC>(
         has_transfer,
         is_new,
-        is_cold,
+        is_cold && !stateless,
         is_call_or_callcode,
         is_call_or_staticcall,
     );
 
     gas!(interpreter, call_cost, None);
 
+    if stateless {
+        let mut witness_gas = host.witness_read(WitnessKey::basic_data(to));
+        if has_transfer {
+            // `CALLCODE` transfers the value to the caller itself.
+            let caller = interpreter.contract.address;
+            let target = if is_call_or_staticcall { to } else { caller };
+            witness_gas += value_transfer_gas(host, caller, target, exist || target == caller);
+        }
+        gas!(interpreter, witness_gas, None);
+    }
+
     // EIP-150: Gas cost changes for IO-heavy operations
     let gas_limit = if SPEC::enabled(TANGERINE) {
         let gas = interpreter.gas().remaining();
         // take l64 part of gas_limit
-        min(gas - gas / 64, local_gas_limit)
+        min(host.env().cfg.chain.max_forwarded_gas(gas), local_gas_limit)
     } else {
         local_gas_limit
     };
 
     Some(gas_limit)
 }
+
+/// Pops the target address of the EIP-7069 call, it has to fit into 20 bytes.
+#[inline]
+pub fn pop_extcall_target_address(interpreter: &mut Interpreter) -> Option<Address> {
+    pop_ret!(interpreter, target, None);
+    let target = B256::from(target);
+    if target[..12].iter().any(|byte| *byte != 0) {
+        interpreter.instruction_result = InstructionResult::InvalidEXTCALLTarget;
+        return None;
+    }
+    Some(Address::from_word(target))
+}
+
+/// Pops the input range of the EIP-7069 call and returns the input from memory.
+#[inline]
+pub fn get_memory_input(interpreter: &mut Interpreter) -> Option<Bytes> {
+    pop_ret!(interpreter, input_offset, input_size, None);
+    let input_size = as_usize_or_fail_ret!(interpreter, input_size, None);
+    if input_size == 0 {
+        return Some(Bytes::new());
+    }
+    let input_offset = as_usize_or_fail_ret!(interpreter, input_offset, None);
+    shared_memory_resize!(interpreter, input_offset, input_size, None);
+    Some(Bytes::copy_from_slice(
+        interpreter.shared_memory.slice(input_offset, input_size),
+    ))
+}
+
+/// Charges the EIP-7069 call cost and returns the gas limit of the callee.
+///
+/// Callee gets all but the `max(gas / 64, MIN_RETAINED_GAS)` of the remaining gas, where the
+/// divisor is given by the [crate::primitives::ChainConfig::retained_gas_divisor]. If it
+/// would get less than `MIN_CALLEE_GAS` the call fails without running, pushes `1` and
+/// returns `None`.
+#[inline]
+pub fn calc_extcall_gas<H: Host, SPEC: Spec>(
+    interpreter: &mut Interpreter,
+    host: &mut H,
+    target: Address,
+    transfers_value: bool,
+) -> Option<u64> {
+    let Some((is_cold, exist)) = host.load_account(target) else {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+        return None;
+    };
+    // EIP-4762: Statelessness gas cost changes, cold access and new account are charged by
+    // the witness.
+    let stateless = SPEC::enabled(VERKLE);
+    // value transfer and new account costs are only charged with value.
+    let call_cost = gas::call_cost::<SPEC>(
+        transfers_value,
+        !exist && !stateless,
+        is_cold && !stateless,
+        true,
+        true,
+    );
+    gas!(interpreter, call_cost, None);
+    if stateless {
+        let mut witness_gas = host.witness_read(WitnessKey::basic_data(target));
+        if transfers_value {
+            let caller = interpreter.contract.address;
+            witness_gas += value_transfer_gas(host, caller, target, exist);
+        }
+        gas!(interpreter, witness_gas, None);
+    }
+
+    let remaining = interpreter.gas().remaining();
+    let retained = remaining - host.env().cfg.chain.max_forwarded_gas(remaining);
+    let gas_limit = remaining.saturating_sub(retained.max(gas::MIN_RETAINED_GAS));
+    if gas_limit < gas::MIN_CALLEE_GAS {
+        interpreter.return_data_buffer = Bytes::new();
+        if let Err(result) = interpreter.stack.push(U256::from(1)) {
+            interpreter.instruction_result = result;
+        }
+        return None;
+    }
+    gas!(interpreter, gas_limit, None);
+    Some(gas_limit)
+}
@@ -0,0 +1,70 @@
+use crate::{
+    gas::{WitnessKey, CODE_CHUNK_SIZE},
+    opcode::{BoxedInstruction, Instruction, PUSH0, PUSH1, PUSH32},
+    primitives::Address,
+    Host, InstructionResult, Interpreter,
+};
+use core::cmp::min;
+use std::boxed::Box;
+
+/// EIP-4762: Gas of reading the code chunks of `address` that cover `offset..offset + len`.
+///
+/// Chunks past the end of the code are not part of the witness and are free.
+#[inline]
+pub fn code_chunks_gas<H: Host>(
+    host: &mut H,
+    address: Address,
+    code_len: usize,
+    offset: usize,
+    len: usize,
+) -> u64 {
+    let end = min(offset.saturating_add(len), code_len);
+    if offset >= end {
+        return 0;
+    }
+    (offset / CODE_CHUNK_SIZE..=(end - 1) / CODE_CHUNK_SIZE)
+        .map(|chunk| host.witness_read(WitnessKey::code_chunk(address, chunk as u64)))
+        .sum()
+}
+
+/// EIP-4762: Wraps `instruction` to charge the code chunks of the executed opcode and of its
+/// `PUSH` data before it runs.
+///
+/// Initcode and EOF code are not chunked into the tree and are not charged.
+pub fn code_chunks_instruction<'a, H: Host + 'a>(
+    instruction: Instruction<H>,
+) -> BoxedInstruction<'a, H> {
+    Box::new(move |interpreter: &mut Interpreter, host: &mut H| {
+        if let (Some(address), None) = (
+            interpreter.contract.code_address,
+            interpreter.contract.bytecode.eof(),
+        ) {
+            // instruction pointer is already past the opcode.
+            let pc = interpreter.program_counter() - 1;
+            let opcode = interpreter.contract.bytecode.bytecode()[pc];
+            let push_len = if (PUSH1..=PUSH32).contains(&opcode) {
+                (opcode - PUSH0) as usize
+            } else {
+                0
+            };
+            let code_len = interpreter.contract.bytecode.len();
+            gas!(
+                interpreter,
+                code_chunks_gas(host, address, code_len, pc, 1 + push_len)
+            );
+        }
+        instruction(interpreter, host)
+    })
+}
+
+/// EIP-4762: Gas of the value transfer, it writes the balances of both accounts.
+#[inline]
+pub fn value_transfer_gas<H: Host>(
+    host: &mut H,
+    from: Address,
+    to: Address,
+    to_exists: bool,
+) -> u64 {
+    host.witness_write(WitnessKey::basic_data(from), false)
+        + host.witness_write(WitnessKey::basic_data(to), !to_exists)
+}
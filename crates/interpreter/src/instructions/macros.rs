@@ -7,6 +7,15 @@ macro_rules! check_staticcall {
     };
 }
 
+macro_rules! require_eof {
+    ($interp:expr) => {
+        if !$interp.is_eof {
+            $interp.instruction_result = InstructionResult::EOFOpcodeDisabledInLegacy;
+            return;
+        }
+    };
+}
+
 macro_rules! check {
     ($interp:expr, $min:ident) => {
         // TODO: Force const-eval on the condition with a `const {}` block once they are stable
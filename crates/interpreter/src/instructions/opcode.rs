@@ -28,6 +28,7 @@ pub type BoxedInstructionTable<'a, H> = [BoxedInstruction<'a, H>; 256];
 /// Note that `Plain` variant gives us 10-20% faster Interpreter execution.
 ///
 /// Boxed variant can be used to wrap plain function pointer with closure.
+#[allow(clippy::large_enum_variant)]
 pub enum InstructionTables<'a, H> {
     Plain(InstructionTable<H>),
     Boxed(BoxedInstructionTable<'a, H>),
@@ -96,11 +97,19 @@ macro_rules! opcodes {
                 map[$val] = Some(stringify!($name));
             )*
             let _ = prev;
+            #[cfg(feature = "experimental-eips")]
+            {
+                map[AUTH as usize] = Some("AUTH");
+            }
             map
         };
 
         /// Returns the instruction function for the given opcode and spec.
         pub const fn instruction<H: Host, SPEC: Spec>(opcode: u8) -> Instruction<H> {
+            #[cfg(feature = "experimental-eips")]
+            if let Some(f) = experimental_instruction::<H, SPEC>(opcode) {
+                return f;
+            }
             match opcode {
                 $($name => $f,)*
                 _ => control::unknown,
@@ -181,7 +190,7 @@ opcodes! {
     0x3D => RETURNDATASIZE => system::returndatasize::<H, SPEC>,
     0x3E => RETURNDATACOPY => system::returndatacopy::<H, SPEC>,
     0x3F => EXTCODEHASH    => host::extcodehash::<H, SPEC>,
-    0x40 => BLOCKHASH      => host::blockhash,
+    0x40 => BLOCKHASH      => host::blockhash::<H, SPEC>,
     0x41 => COINBASE       => host_env::coinbase,
     0x42 => TIMESTAMP      => host_env::timestamp,
     0x43 => NUMBER         => host_env::number,
@@ -329,10 +338,10 @@ opcodes! {
     // 0xCD
     // 0xCE
     // 0xCF
-    // 0xD0
-    // 0xD1
-    // 0xD2
-    // 0xD3
+    0xD0 => DATALOAD  => data::data_load,
+    0xD1 => DATALOADN => data::data_loadn,
+    0xD2 => DATASIZE  => data::data_size,
+    0xD3 => DATACOPY  => data::data_copy,
     // 0xD4
     // 0xD5
     // 0xD6
@@ -345,21 +354,21 @@ opcodes! {
     // 0xDD
     // 0xDE
     // 0xDF
-    // 0xE0
-    // 0xE1
-    // 0xE2
-    // 0xE3
-    // 0xE4
-    // 0xE5
-    // 0xE6
-    // 0xE7
-    // 0xE8
+    0xE0 => RJUMP  => control::rjump,
+    0xE1 => RJUMPI => control::rjumpi,
+    0xE2 => RJUMPV => control::rjumpv,
+    0xE3 => CALLF  => control::callf,
+    0xE4 => RETF   => control::retf,
+    0xE5 => JUMPF  => control::jumpf,
+    0xE6 => DUPN     => stack::dupn,
+    0xE7 => SWAPN    => stack::swapn,
+    0xE8 => EXCHANGE => stack::exchange,
     // 0xE9
     // 0xEA
     // 0xEB
-    // 0xEC
-    // 0xED
-    // 0xEE
+    0xEC => EOFCREATE      => host::eofcreate::<H, SPEC>,
+    0xED => TXCREATE       => host::txcreate::<H, SPEC>,
+    0xEE => RETURNCONTRACT => control::return_contract,
     // 0xEF
     0xF0 => CREATE       => host::create::<false, H, SPEC>,
     0xF1 => CALL         => host::call::<H, SPEC>,
@@ -368,17 +377,62 @@ opcodes! {
     0xF4 => DELEGATECALL => host::delegate_call::<H, SPEC>,
     0xF5 => CREATE2      => host::create::<true, H, SPEC>,
     // 0xF6
-    // 0xF7
-    // 0xF8
-    // 0xF9
+    0xF7 => RETURNDATALOAD => system::returndataload,
+    0xF8 => EXTCALL      => host::extcall::<H, SPEC>,
+    0xF9 => EXTDELEGATECALL => host::extdelegatecall::<H, SPEC>,
     0xFA => STATICCALL   => host::static_call::<H, SPEC>,
-    // 0xFB
+    0xFB => EXTSTATICCALL => host::extstaticcall::<H, SPEC>,
     // 0xFC
     0xFD => REVERT       => control::revert::<H, SPEC>,
     0xFE => INVALID      => control::invalid,
     0xFF => SELFDESTRUCT => host::selfdestruct::<H, SPEC>,
 }
 
+/// The EIP-3074 `0xF6` ("AUTH") opcode.
+#[cfg(feature = "experimental-eips")]
+pub const AUTH: u8 = 0xF6;
+/// The EIP-3074 `0xF7` ("AUTHCALL") opcode, it is `RETURNDATALOAD` once EOF is enabled.
+#[cfg(feature = "experimental-eips")]
+pub const AUTHCALL: u8 = 0xF7;
+
+/// Returns the instruction of the experimental opcodes that are not in the table above.
+///
+/// EIP-3074 opcodes are available until EOF, which reuses `0xF7` for `RETURNDATALOAD`.
+#[cfg(feature = "experimental-eips")]
+const fn experimental_instruction<H: Host, SPEC: Spec>(opcode: u8) -> Option<Instruction<H>> {
+    if SpecId::enabled(SPEC::SPEC_ID, SpecId::PRAGUE_EOF) {
+        return None;
+    }
+    match opcode {
+        AUTH => Some(host::auth::<H, SPEC>),
+        AUTHCALL => Some(host::authcall::<H, SPEC>),
+        _ => None,
+    }
+}
+
+/// Returns the name of the opcode in the given spec, `None` if the opcode is not defined in it.
+///
+/// Unlike [OPCODE_JUMPMAP] the EOF opcodes are only named from [SpecId::PRAGUE_EOF], before it
+/// `0xF7` is `AUTHCALL` with the `experimental-eips` feature.
+pub const fn spec_opcode_name(opcode: u8, spec: SpecId) -> Option<&'static str> {
+    if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+        return OPCODE_JUMPMAP[opcode as usize];
+    }
+    match opcode {
+        DATALOAD..=DATACOPY
+        | RJUMP..=EXCHANGE
+        | EOFCREATE..=RETURNCONTRACT
+        | EXTCALL
+        | EXTDELEGATECALL
+        | EXTSTATICCALL => None,
+        #[cfg(feature = "experimental-eips")]
+        AUTHCALL => Some("AUTHCALL"),
+        #[cfg(not(feature = "experimental-eips"))]
+        RETURNDATALOAD => None,
+        _ => OPCODE_JUMPMAP[opcode as usize],
+    }
+}
+
 /// An EVM opcode.
 ///
 /// This is always a valid opcode, as declared in the [`opcode`][self] module or the
@@ -807,10 +861,34 @@ const fn opcode_gas_info(opcode: u8, spec: SpecId) -> OpInfo {
         0xCD => OpInfo::none(),
         0xCE => OpInfo::none(),
         0xCF => OpInfo::none(),
-        0xD0 => OpInfo::none(),
-        0xD1 => OpInfo::none(),
-        0xD2 => OpInfo::none(),
-        0xD3 => OpInfo::none(),
+        DATALOAD => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::DATA_LOAD_GAS)
+            } else {
+                OpInfo::none()
+            }
+        }
+        DATALOADN => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::VERYLOW)
+            } else {
+                OpInfo::none()
+            }
+        }
+        DATASIZE => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::BASE)
+            } else {
+                OpInfo::none()
+            }
+        }
+        DATACOPY => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::dynamic_gas()
+            } else {
+                OpInfo::none()
+            }
+        }
         0xD4 => OpInfo::none(),
         0xD5 => OpInfo::none(),
         0xD6 => OpInfo::none(),
@@ -823,21 +901,93 @@ const fn opcode_gas_info(opcode: u8, spec: SpecId) -> OpInfo {
         0xDD => OpInfo::none(),
         0xDE => OpInfo::none(),
         0xDF => OpInfo::none(),
-        0xE0 => OpInfo::none(),
-        0xE1 => OpInfo::none(),
-        0xE2 => OpInfo::none(),
-        0xE3 => OpInfo::none(),
-        0xE4 => OpInfo::none(),
-        0xE5 => OpInfo::none(),
-        0xE6 => OpInfo::none(),
-        0xE7 => OpInfo::none(),
-        0xE8 => OpInfo::none(),
+        RJUMP => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::BASE)
+            } else {
+                OpInfo::none()
+            }
+        }
+        RJUMPI => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::CONDITION_JUMP_GAS)
+            } else {
+                OpInfo::none()
+            }
+        }
+        RJUMPV => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::CONDITION_JUMP_GAS)
+            } else {
+                OpInfo::none()
+            }
+        }
+        CALLF => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::LOW)
+            } else {
+                OpInfo::none()
+            }
+        }
+        RETF => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::RETF_GAS)
+            } else {
+                OpInfo::none()
+            }
+        }
+        JUMPF => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(gas::LOW)
+            } else {
+                OpInfo::none()
+            }
+        }
+        DUPN => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::VERYLOW)
+            } else {
+                OpInfo::none()
+            }
+        }
+        SWAPN => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::VERYLOW)
+            } else {
+                OpInfo::none()
+            }
+        }
+        EXCHANGE => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::VERYLOW)
+            } else {
+                OpInfo::none()
+            }
+        }
         0xE9 => OpInfo::none(),
         0xEA => OpInfo::none(),
         0xEB => OpInfo::none(),
-        0xEC => OpInfo::none(),
-        0xED => OpInfo::none(),
-        0xEE => OpInfo::none(),
+        EOFCREATE => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
+        TXCREATE => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
+        RETURNCONTRACT => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
         0xEF => OpInfo::none(),
         CREATE => OpInfo::gas_block_end(0),
         CALL => OpInfo::gas_block_end(0),
@@ -845,12 +995,42 @@ const fn opcode_gas_info(opcode: u8, spec: SpecId) -> OpInfo {
         RETURN => OpInfo::gas_block_end(0),
         DELEGATECALL => OpInfo::gas_block_end(0),
         CREATE2 => OpInfo::gas_block_end(0),
+        #[cfg(feature = "experimental-eips")]
+        AUTH => OpInfo::dynamic_gas(),
+        #[cfg(not(feature = "experimental-eips"))]
         0xF6 => OpInfo::none(),
-        0xF7 => OpInfo::none(),
-        0xF8 => OpInfo::none(),
-        0xF9 => OpInfo::none(),
+        RETURNDATALOAD => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas(gas::VERYLOW)
+            } else if cfg!(feature = "experimental-eips") {
+                // AUTHCALL
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
+        EXTCALL => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
+        EXTDELEGATECALL => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
         STATICCALL => OpInfo::gas_block_end(0),
-        0xFB => OpInfo::none(),
+        EXTSTATICCALL => {
+            if SpecId::enabled(spec, SpecId::PRAGUE_EOF) {
+                OpInfo::gas_block_end(0)
+            } else {
+                OpInfo::none()
+            }
+        }
         0xFC => OpInfo::none(),
         REVERT => OpInfo::gas_block_end(0),
         INVALID => OpInfo::gas_block_end(0),
@@ -900,6 +1080,11 @@ pub const fn spec_opcode_gas(spec_id: SpecId) -> &'static [OpInfo; 256] {
                     const TABLE: &[OpInfo;256] = &make_gas_table(SpecId::ECOTONE);
                     TABLE
                 }
+                #[cfg(feature = "optimism")]
+                SpecId::FJORD => {
+                    const TABLE: &[OpInfo;256] = &make_gas_table(SpecId::FJORD);
+                    TABLE
+                }
             }
         };
     }
@@ -923,6 +1108,9 @@ pub const fn spec_opcode_gas(spec_id: SpecId) -> &'static [OpInfo; 256] {
         MERGE,
         SHANGHAI,
         CANCUN,
+        PRAGUE,
+        PRAGUE_EOF,
         LATEST,
+        VERKLE,
     )
 }
@@ -39,14 +39,50 @@ pub fn push<const N: usize, H: Host>(interpreter: &mut Interpreter, _host: &mut
 
 pub fn dup<const N: usize, H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
-    if let Err(result) = interpreter.stack.dup::<N>() {
+    if let Err(result) = interpreter.stack.dup(N) {
         interpreter.instruction_result = result;
     }
 }
 
 pub fn swap<const N: usize, H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
-    if let Err(result) = interpreter.stack.swap::<N>() {
+    if let Err(result) = interpreter.stack.swap(N) {
         interpreter.instruction_result = result;
     }
 }
+
+/// EIP-663: SWAPN, DUPN and EXCHANGE instructions
+pub fn dupn<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::VERYLOW);
+    let imm = unsafe { *interpreter.instruction_pointer };
+    if let Err(result) = interpreter.stack.dup(imm as usize + 1) {
+        interpreter.instruction_result = result;
+        return;
+    }
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
+}
+
+pub fn swapn<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::VERYLOW);
+    let imm = unsafe { *interpreter.instruction_pointer };
+    if let Err(result) = interpreter.stack.swap(imm as usize + 1) {
+        interpreter.instruction_result = result;
+        return;
+    }
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
+}
+
+pub fn exchange<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::VERYLOW);
+    let imm = unsafe { *interpreter.instruction_pointer };
+    let n = (imm >> 4) as usize + 1;
+    let m = (imm & 0x0F) as usize + 1;
+    if let Err(result) = interpreter.stack.exchange(n, m) {
+        interpreter.instruction_result = result;
+        return;
+    }
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
+}
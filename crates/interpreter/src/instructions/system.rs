@@ -132,6 +132,21 @@ pub fn returndatacopy<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, _host:
     }
 }
 
+/// EIP-7069: Revamped CALL instructions
+pub fn returndataload<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
+    require_eof!(interpreter);
+    gas!(interpreter, gas::VERYLOW);
+    pop!(interpreter, offset);
+    let offset = as_usize_saturated!(offset);
+    let buffer = &interpreter.return_data_buffer;
+    let mut load = B256::ZERO;
+    if offset < buffer.len() {
+        let len = 32.min(buffer.len() - offset);
+        load[..len].copy_from_slice(&buffer[offset..offset + len]);
+    }
+    push_b256!(interpreter, load);
+}
+
 pub fn gas<H: Host>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(interpreter, U256::from(interpreter.gas.remaining()));
@@ -1,10 +1,12 @@
 pub mod analysis;
 mod contract;
+mod function_stack;
 mod shared_memory;
 mod stack;
 
 pub use analysis::BytecodeLocked;
 pub use contract::Contract;
+pub use function_stack::{FunctionReturnFrame, FunctionStack, FUNCTION_STACK_LIMIT};
 pub use shared_memory::{next_multiple_of_32, SharedMemory};
 pub use stack::{Stack, STACK_LIMIT};
 
@@ -13,6 +15,8 @@ use crate::{
     CreateInputs, CreateOutcome, Gas, Host, InstructionResult,
 };
 use core::cmp::min;
+#[cfg(feature = "experimental-eips")]
+use revm_primitives::Address;
 use revm_primitives::U256;
 use std::borrow::ToOwned;
 use std::boxed::Box;
@@ -50,6 +54,15 @@ pub struct Interpreter {
     /// Set inside CALL or CREATE instructions and RETURN or REVERT instructions. Additionally those instructions will set
     /// InstructionResult to CallOrCreate/Return/Revert so we know the reason.
     pub next_action: InterpreterAction,
+    /// Whether the bytecode is an EOF container.
+    pub is_eof: bool,
+    /// EOF function return stack, empty for the legacy bytecode.
+    pub function_stack: FunctionStack,
+    /// EIP-3074: Account authorized by `AUTH` to be the caller of `AUTHCALL`.
+    ///
+    /// It is unset at the start of every frame.
+    #[cfg(feature = "experimental-eips")]
+    pub authorized: Option<Address>,
 }
 
 /// The result of an interpreter operation.
@@ -116,9 +129,15 @@ impl InterpreterAction {
 
 impl Interpreter {
     /// Create new interpreter
+    ///
+    /// EOF bytecode starts at the first code section.
     pub fn new(contract: Box<Contract>, gas_limit: u64, is_static: bool) -> Self {
+        let (instruction_pointer, is_eof) = match contract.bytecode.eof() {
+            Some(eof) => (eof.body.code_section[0].as_ptr(), true),
+            None => (contract.bytecode.as_ptr(), false),
+        };
         Self {
-            instruction_pointer: contract.bytecode.as_ptr(),
+            instruction_pointer,
             contract,
             gas: Gas::new(gas_limit),
             instruction_result: InstructionResult::Continue,
@@ -127,9 +146,31 @@ impl Interpreter {
             shared_memory: EMPTY_SHARED_MEMORY,
             stack: Stack::new(),
             next_action: InterpreterAction::None,
+            is_eof,
+            function_stack: FunctionStack::new(),
+            #[cfg(feature = "experimental-eips")]
+            authorized: None,
         }
     }
 
+    /// Sets the instruction pointer to the `pc` of the EOF code section.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytecode is not EOF or it has no code section with the index.
+    #[inline]
+    pub fn load_eof_code(&mut self, idx: usize, pc: usize) {
+        let code = &self
+            .contract
+            .bytecode
+            .eof()
+            .expect("bytecode is EOF")
+            .body
+            .code_section[idx];
+        // SAFETY: `pc` is validated to be inside the code section.
+        self.instruction_pointer = unsafe { code.as_ptr().add(pc) };
+    }
+
     /// Inserts the output of a `create` call into the interpreter.
     ///
     /// This function is used after a `create` call has been executed. It processes the outcome
@@ -209,6 +250,9 @@ impl Interpreter {
     /// - `return_revert!()`: Handles a revert by only updating the gas usage and shared memory.
     /// - `InstructionResult::FatalExternalError`: Sets the instruction result to a fatal external error.
     /// - Any other result: No specific action is taken.
+    ///
+    /// EOF calls don't write to memory and push the EIP-7069 status code: `0` on success, `1`
+    /// on revert and `2` on failure.
     pub fn insert_call_outcome(
         &mut self,
         shared_memory: &mut SharedMemory,
@@ -228,19 +272,27 @@ impl Interpreter {
                 let refunded = call_outcome.gas().refunded();
                 self.gas.erase_cost(remaining);
                 self.gas.record_refund(refunded);
-                shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
-                push!(self, U256::from(1));
+                if self.is_eof {
+                    push!(self, U256::ZERO);
+                } else {
+                    shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
+                    push!(self, U256::from(1));
+                }
             }
             return_revert!() => {
                 self.gas.erase_cost(call_outcome.gas().remaining());
-                shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
-                push!(self, U256::ZERO);
+                if self.is_eof {
+                    push!(self, U256::from(1));
+                } else {
+                    shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
+                    push!(self, U256::ZERO);
+                }
             }
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_call_outcome");
             }
             _ => {
-                push!(self, U256::ZERO);
+                push!(self, U256::from(if self.is_eof { 2 } else { 0 }));
             }
         }
     }
@@ -270,14 +322,17 @@ impl Interpreter {
     }
 
     /// Returns the current program counter.
+    ///
+    /// For EOF bytecode it is relative to the start of the current code section.
     #[inline]
     pub fn program_counter(&self) -> usize {
+        let start = match self.contract.bytecode.eof() {
+            Some(eof) => eof.body.code_section[self.function_stack.current_code_idx].as_ptr(),
+            None => self.contract.bytecode.as_ptr(),
+        };
         // SAFETY: `instruction_pointer` should be at an offset from the start of the bytecode.
         // In practice this is always true unless a caller modifies the `instruction_pointer` field manually.
-        unsafe {
-            self.instruction_pointer
-                .offset_from(self.contract.bytecode.as_ptr()) as usize
-        }
+        unsafe { self.instruction_pointer.offset_from(start) as usize }
     }
 
     /// Executes the instruction at the current instruction pointer.
@@ -1,10 +1,12 @@
 use crate::opcode;
 use crate::primitives::{
     bitvec::prelude::{bitvec, BitVec, Lsb0},
-    keccak256, Bytecode, BytecodeState, Bytes, JumpMap, B256, KECCAK_EMPTY,
+    eof::{EofDecodeError, TypesSection, EOF_MAGIC_BYTES},
+    keccak256, Bytecode, BytecodeState, Bytes, Eof, JumpMap, B256, KECCAK_EMPTY,
 };
+use crate::STACK_LIMIT;
 use core::fmt;
-use std::sync::Arc;
+use std::{sync::Arc, vec, vec::Vec};
 
 /// Perform bytecode analysis.
 ///
@@ -64,6 +66,7 @@ pub struct BytecodeLocked {
     bytecode: Bytes,
     original_len: usize,
     jump_map: JumpMap,
+    eof: Option<Arc<Eof>>,
 }
 
 impl fmt::Debug for BytecodeLocked {
@@ -75,6 +78,7 @@ impl fmt::Debug for BytecodeLocked {
                 "jump_map",
                 &crate::primitives::hex::encode(self.jump_map.as_slice()),
             )
+            .field("eof", &self.eof)
             .finish()
     }
 }
@@ -93,14 +97,20 @@ impl TryFrom<Bytecode> for BytecodeLocked {
 
     #[inline]
     fn try_from(bytecode: Bytecode) -> Result<Self, Self::Error> {
-        if let BytecodeState::Analysed { len, jump_map } = bytecode.state {
-            Ok(BytecodeLocked {
+        match bytecode.state {
+            BytecodeState::Analysed { len, jump_map } => Ok(BytecodeLocked {
                 bytecode: bytecode.bytecode,
                 original_len: len,
                 jump_map,
-            })
-        } else {
-            Err(())
+                eof: None,
+            }),
+            BytecodeState::Eof(eof) => Ok(BytecodeLocked {
+                original_len: bytecode.bytecode.len(),
+                bytecode: bytecode.bytecode,
+                jump_map: JumpMap::default(),
+                eof: Some(eof),
+            }),
+            _ => Err(()),
         }
     }
 }
@@ -136,12 +146,16 @@ impl BytecodeLocked {
 
     #[inline]
     pub fn unlock(self) -> Bytecode {
-        Bytecode {
-            bytecode: self.bytecode,
-            state: BytecodeState::Analysed {
+        let state = match self.eof {
+            Some(eof) => BytecodeState::Eof(eof),
+            None => BytecodeState::Analysed {
                 len: self.original_len,
                 jump_map: self.jump_map,
             },
+        };
+        Bytecode {
+            bytecode: self.bytecode,
+            state,
         }
     }
 
@@ -176,4 +190,675 @@ impl BytecodeLocked {
     pub fn jump_map(&self) -> &JumpMap {
         &self.jump_map
     }
+
+    /// Returns the EOF container if the bytecode is EOF.
+    #[inline]
+    pub fn eof(&self) -> Option<&Arc<Eof>> {
+        self.eof.as_ref()
+    }
+}
+
+/// Decodes and validates the container if the bytecode starts with the EOF magic.
+///
+/// Invalid containers are returned as is, they run as legacy bytecode and halt on the `0xEF`
+/// opcode.
+pub fn to_eof(bytecode: Bytecode) -> Bytecode {
+    if bytecode.is_eof() || !bytecode.bytes().starts_with(&EOF_MAGIC_BYTES) {
+        return bytecode;
+    }
+    match validate_raw_eof(bytecode.original_bytes()) {
+        Ok(eof) => Bytecode::new_eof(eof),
+        Err(_) => bytecode,
+    }
+}
+
+/// Errors of the EOF container decoding or validation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EofError {
+    Decode(EofDecodeError),
+    Validation(EofValidationError),
+}
+
+impl From<EofDecodeError> for EofError {
+    fn from(error: EofDecodeError) -> Self {
+        EofError::Decode(error)
+    }
+}
+
+impl From<EofValidationError> for EofError {
+    fn from(error: EofValidationError) -> Self {
+        EofError::Validation(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EofError {}
+
+impl fmt::Display for EofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EofError::Decode(e) => write!(f, "EOF decode error: {e}"),
+            EofError::Validation(e) => write!(f, "EOF validation error: {e:?}"),
+        }
+    }
+}
+
+/// Errors of the EOF code validation, see [validate_eof].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EofValidationError {
+    /// Data section is shorter than declared, only allowed for the undeployed subcontainers.
+    DataNotFilled,
+    /// First code section takes inputs or returns.
+    InvalidFirstTypesSection,
+    /// Code section has more than 127 inputs or outputs, or max stack height above 1023.
+    InvalidTypesSection,
+    /// Opcode is undefined or deprecated in EOF.
+    UnknownOpcode,
+    /// Code ends inside the immediate bytes of the last instruction.
+    MissingImmediateBytes,
+    /// Relative jump target is outside of the code section.
+    InvalidJumpTarget,
+    /// Relative jump target is inside the immediate bytes.
+    JumpToImmediateBytes,
+    /// `DATALOADN` reads past the end of the data section.
+    InvalidDataLoadN,
+    /// `EOFCREATE` or `RETURNCONTRACT` target container section does not exist.
+    InvalidContainerIndex,
+    /// Container section is referenced by both `EOFCREATE` and `RETURNCONTRACT`.
+    ContainerKindConflict,
+    /// Container section is not referenced by `EOFCREATE` or `RETURNCONTRACT`.
+    UnreferencedContainer,
+    /// `RETURN` or `STOP` in the initcode container.
+    ReturnInInitcode,
+    /// `RETURNCONTRACT` in the runtime container.
+    ReturnContractInRuntime,
+    /// `CALLF` or `JUMPF` target code section does not exist.
+    InvalidCodeSection,
+    /// `CALLF` to the non-returning code section.
+    CallfToNonReturning,
+    /// `RETF` or `JUMPF` to the returning code section inside the non-returning code section.
+    ReturnInNonReturning,
+    /// Returning code section has no `RETF` or `JUMPF` to the returning code section.
+    NoReturnInReturning,
+    /// Last instruction can fall through the end of code section.
+    MissingTerminatingInstruction,
+    /// Instruction is not reachable from the start of code section.
+    UnreachableCode,
+    /// Code section is not reachable from the first code section.
+    UnreachableCodeSection,
+    /// Instruction can take more items than the stack has.
+    StackUnderflow,
+    /// Stack height can exceed the limit.
+    StackOverflow,
+    /// Stack height differs between the backward jump and its target.
+    InvalidBackwardJumpStack,
+    /// Stack height of `RETF` or `JUMPF` does not match the outputs of the code section.
+    InvalidReturnStack,
+    /// Max stack height of the types section differs from the computed one.
+    MaxStackMismatch,
+}
+
+/// Kind of the container, decides how its code can end the execution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EofContainerKind {
+    /// Deployed code, ends with `RETURN` or `STOP`.
+    Runtime,
+    /// EIP-7620: initcode of `EOFCREATE` and `TXCREATE`, ends with `RETURNCONTRACT`.
+    Initcode,
+}
+
+/// Decodes and validates the top level container, the data section has to be complete.
+pub fn validate_raw_eof(raw: Bytes) -> Result<Eof, EofError> {
+    validate_raw_eof_kind(raw, EofContainerKind::Runtime)
+}
+
+/// Decodes and validates the top level initcode container, see [validate_raw_eof].
+pub fn validate_raw_eof_initcode(raw: Bytes) -> Result<Eof, EofError> {
+    validate_raw_eof_kind(raw, EofContainerKind::Initcode)
+}
+
+fn validate_raw_eof_kind(raw: Bytes, kind: EofContainerKind) -> Result<Eof, EofError> {
+    let eof = Eof::decode(raw)?;
+    if !eof.body.is_data_filled {
+        return Err(EofValidationError::DataNotFilled.into());
+    }
+    validate_eof_container(&eof, kind)?;
+    Ok(eof)
+}
+
+/// Validates the code sections and the subcontainers of the decoded runtime container.
+pub fn validate_eof(eof: &Eof) -> Result<(), EofError> {
+    validate_eof_container(eof, EofContainerKind::Runtime)
+}
+
+/// Validates the code sections and the subcontainers of the decoded container.
+///
+/// Implements EIP-3670 code validation, EIP-4200 relative jumps, EIP-4750 and EIP-6206
+/// functions, EIP-5450 stack validation, the immediates of EIP-663 and EIP-7480 and the
+/// EIP-7620 container kinds. Subcontainers are initcode if `EOFCREATE` references them and
+/// runtime if `RETURNCONTRACT` does.
+pub fn validate_eof_container(eof: &Eof, kind: EofContainerKind) -> Result<(), EofError> {
+    let types = &eof.body.types_section;
+    if types[0].inputs != 0 || types[0].is_returning() {
+        return Err(EofValidationError::InvalidFirstTypesSection.into());
+    }
+    for section in types {
+        if section.inputs > 127
+            || (section.outputs > 127 && section.is_returning())
+            || section.max_stack_size as usize >= STACK_LIMIT
+        {
+            return Err(EofValidationError::InvalidTypesSection.into());
+        }
+    }
+
+    let mut containers = vec![None; eof.body.container_section.len()];
+    let referenced = (0..types.len())
+        .map(|idx| validate_eof_code(eof, idx, kind, &mut containers))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut reached = vec![false; types.len()];
+    reached[0] = true;
+    let mut queue = vec![0];
+    while let Some(idx) = queue.pop() {
+        for &next in &referenced[idx] {
+            if !reached[next] {
+                reached[next] = true;
+                queue.push(next);
+            }
+        }
+    }
+    if reached.contains(&false) {
+        return Err(EofValidationError::UnreachableCodeSection.into());
+    }
+
+    for (container, kind) in eof.body.container_section.iter().zip(containers) {
+        let kind = kind.ok_or(EofValidationError::UnreferencedContainer)?;
+        validate_eof_container(&Eof::decode(container.clone())?, kind)?;
+    }
+    Ok(())
+}
+
+/// Stack inputs, outputs and immediate size of the opcode that is valid in EOF code.
+#[derive(Clone, Copy, Debug)]
+struct EofOpInfo {
+    inputs: u8,
+    outputs: u8,
+    immediate: u8,
+    terminating: bool,
+}
+
+impl EofOpInfo {
+    const fn new(inputs: u8, outputs: u8) -> Self {
+        Self {
+            inputs,
+            outputs,
+            immediate: 0,
+            terminating: false,
+        }
+    }
+
+    const fn immediate(self, immediate: u8) -> Self {
+        Self { immediate, ..self }
+    }
+
+    const fn terminating(inputs: u8) -> Self {
+        Self {
+            terminating: true,
+            ..Self::new(inputs, 0)
+        }
+    }
+}
+
+/// Returns the info of the opcode, `None` if it is undefined or deprecated in EOF.
+///
+/// `RJUMPV` has one immediate byte with the maximum index followed by two bytes per offset,
+/// stack effect of `CALLF`, `RETF` and `JUMPF` depends on the types section and the one of
+/// `DUPN`, `SWAPN` and `EXCHANGE` on the immediate.
+const fn eof_opcode_info(opcode: u8) -> Option<EofOpInfo> {
+    use opcode::*;
+    let info = match opcode {
+        STOP | INVALID | RETF => EofOpInfo::terminating(0),
+        RETURN | REVERT => EofOpInfo::terminating(2),
+        RETURNCONTRACT => EofOpInfo::terminating(2).immediate(1),
+        JUMPF => EofOpInfo::terminating(0).immediate(2),
+        ADD | MUL | SUB | DIV | SDIV | MOD | SMOD | EXP | SIGNEXTEND | LT | GT | SLT | SGT | EQ
+        | AND | OR | XOR | BYTE | SHL | SHR | SAR | KECCAK256 => EofOpInfo::new(2, 1),
+        ADDMOD | MULMOD | EXTDELEGATECALL | EXTSTATICCALL => EofOpInfo::new(3, 1),
+        EXTCALL => EofOpInfo::new(4, 1),
+        EOFCREATE => EofOpInfo::new(4, 1).immediate(1),
+        TXCREATE => EofOpInfo::new(5, 1),
+        ISZERO | NOT | BALANCE | CALLDATALOAD | BLOCKHASH | BLOBHASH | MLOAD | SLOAD | TLOAD
+        | RETURNDATALOAD | DATALOAD => EofOpInfo::new(1, 1),
+        ADDRESS | ORIGIN | CALLER | CALLVALUE | CALLDATASIZE | GASPRICE | RETURNDATASIZE
+        | COINBASE | TIMESTAMP | NUMBER | DIFFICULTY | GASLIMIT | CHAINID | SELFBALANCE
+        | BASEFEE | BLOBBASEFEE | MSIZE | PUSH0 | DATASIZE => EofOpInfo::new(0, 1),
+        DATALOADN => EofOpInfo::new(0, 1).immediate(2),
+        CALLDATACOPY | RETURNDATACOPY | MCOPY | DATACOPY => EofOpInfo::new(3, 0),
+        MSTORE | MSTORE8 | SSTORE | TSTORE => EofOpInfo::new(2, 0),
+        POP => EofOpInfo::new(1, 0),
+        JUMPDEST => EofOpInfo::new(0, 0),
+        PUSH1..=PUSH32 => EofOpInfo::new(0, 1).immediate(opcode - PUSH0),
+        DUP1..=DUP16 => EofOpInfo::new(opcode - DUP1 + 1, opcode - DUP1 + 2),
+        SWAP1..=SWAP16 => EofOpInfo::new(opcode - SWAP1 + 2, opcode - SWAP1 + 2),
+        LOG0..=LOG4 => EofOpInfo::new(opcode - LOG0 + 2, 0),
+        RJUMP => EofOpInfo::new(0, 0).immediate(2),
+        RJUMPI => EofOpInfo::new(1, 0).immediate(2),
+        RJUMPV => EofOpInfo::new(1, 0).immediate(1),
+        CALLF => EofOpInfo::new(0, 0).immediate(2),
+        DUPN | SWAPN | EXCHANGE => EofOpInfo::new(0, 0).immediate(1),
+        _ => return None,
+    };
+    Some(info)
+}
+
+/// Returns the size of the immediate bytes, the code has to contain the `RJUMPV` max index.
+#[inline]
+fn immediate_size(code: &[u8], pc: usize, info: EofOpInfo) -> Option<usize> {
+    if code[pc] == opcode::RJUMPV {
+        return code
+            .get(pc + 1)
+            .map(|&max_index| 1 + (max_index as usize + 1) * 2);
+    }
+    Some(info.immediate as usize)
+}
+
+/// Reads the big endian `i16` offset, target is relative to the end of the instruction.
+#[inline]
+fn relative_target(end: usize, offset: &[u8]) -> isize {
+    end as isize + i16::from_be_bytes([offset[0], offset[1]]) as isize
+}
+
+/// Validates the code section, returns the code sections it calls or jumps to.
+///
+/// Kinds of the container sections it references are recorded in `containers`.
+fn validate_eof_code(
+    eof: &Eof,
+    idx: usize,
+    kind: EofContainerKind,
+    containers: &mut [Option<EofContainerKind>],
+) -> Result<Vec<usize>, EofValidationError> {
+    let code = &eof.body.code_section[idx][..];
+    let types = &eof.body.types_section[..];
+    let this = types[idx];
+    let mut is_immediate = vec![false; code.len()];
+    let mut jump_targets = Vec::new();
+    let mut referenced = Vec::new();
+    let mut has_return = false;
+    let mut last_info = None;
+
+    let mut pc = 0;
+    while pc < code.len() {
+        let opcode = code[pc];
+        let info = eof_opcode_info(opcode).ok_or(EofValidationError::UnknownOpcode)?;
+        let end = immediate_size(code, pc, info)
+            .map(|size| pc + 1 + size)
+            .filter(|&end| end <= code.len())
+            .ok_or(EofValidationError::MissingImmediateBytes)?;
+        is_immediate[pc + 1..end].fill(true);
+        let immediate = &code[pc + 1..end];
+
+        match opcode {
+            opcode::RJUMP | opcode::RJUMPI => jump_targets.push(relative_target(end, immediate)),
+            opcode::RJUMPV => {
+                jump_targets.extend(
+                    immediate[1..]
+                        .chunks_exact(2)
+                        .map(|offset| relative_target(end, offset)),
+                );
+            }
+            opcode::CALLF | opcode::JUMPF => {
+                let target = u16::from_be_bytes([immediate[0], immediate[1]]) as usize;
+                let target_types = types
+                    .get(target)
+                    .ok_or(EofValidationError::InvalidCodeSection)?;
+                if target_types.is_returning() {
+                    if opcode == opcode::JUMPF {
+                        if !this.is_returning() {
+                            return Err(EofValidationError::ReturnInNonReturning);
+                        }
+                        has_return = true;
+                    }
+                } else if opcode == opcode::CALLF {
+                    return Err(EofValidationError::CallfToNonReturning);
+                }
+                referenced.push(target);
+            }
+            opcode::RETF => {
+                if !this.is_returning() {
+                    return Err(EofValidationError::ReturnInNonReturning);
+                }
+                has_return = true;
+            }
+            opcode::DATALOADN => {
+                let offset = u16::from_be_bytes([immediate[0], immediate[1]]) as usize;
+                if offset + 32 > eof.header.data_size as usize {
+                    return Err(EofValidationError::InvalidDataLoadN);
+                }
+            }
+            opcode::RETURN | opcode::STOP if kind == EofContainerKind::Initcode => {
+                return Err(EofValidationError::ReturnInInitcode);
+            }
+            opcode::EOFCREATE | opcode::RETURNCONTRACT => {
+                let target_kind = if opcode == opcode::EOFCREATE {
+                    EofContainerKind::Initcode
+                } else if kind == EofContainerKind::Runtime {
+                    return Err(EofValidationError::ReturnContractInRuntime);
+                } else {
+                    EofContainerKind::Runtime
+                };
+                let container = containers
+                    .get_mut(immediate[0] as usize)
+                    .ok_or(EofValidationError::InvalidContainerIndex)?;
+                if container
+                    .replace(target_kind)
+                    .is_some_and(|k| k != target_kind)
+                {
+                    return Err(EofValidationError::ContainerKindConflict);
+                }
+            }
+            _ => {}
+        }
+        last_info = Some((opcode, info));
+        pc = end;
+    }
+
+    match last_info {
+        Some((opcode, info)) if info.terminating || opcode == opcode::RJUMP => {}
+        _ => return Err(EofValidationError::MissingTerminatingInstruction),
+    }
+    if this.is_returning() && !has_return {
+        return Err(EofValidationError::NoReturnInReturning);
+    }
+    for target in jump_targets {
+        if target < 0 || target as usize >= code.len() {
+            return Err(EofValidationError::InvalidJumpTarget);
+        }
+        if is_immediate[target as usize] {
+            return Err(EofValidationError::JumpToImmediateBytes);
+        }
+    }
+
+    validate_eof_stack(code, this, types)?;
+    Ok(referenced)
+}
+
+/// Validates the stack heights of the code section in a single forward pass.
+///
+/// Every instruction has the range of possible stack heights, forward jumps extend the range
+/// of the target and backward jumps have to match it. Instruction without the range is not
+/// reachable from the start of the code section.
+fn validate_eof_stack(
+    code: &[u8],
+    this: TypesSection,
+    types: &[TypesSection],
+) -> Result<(), EofValidationError> {
+    let mut heights: Vec<Option<(usize, usize)>> = vec![None; code.len()];
+    heights[0] = Some((this.inputs as usize, this.inputs as usize));
+    let mut max_height = this.inputs as usize;
+
+    let mut pc = 0;
+    while pc < code.len() {
+        let opcode = code[pc];
+        let info = eof_opcode_info(opcode).expect("opcodes are validated");
+        let end = pc + 1 + immediate_size(code, pc, info).expect("immediates are validated");
+        let immediate = &code[pc + 1..end];
+        let (min, max) = heights[pc].ok_or(EofValidationError::UnreachableCode)?;
+
+        let (inputs, outputs) = match opcode {
+            opcode::CALLF | opcode::JUMPF => {
+                let target = types[u16::from_be_bytes([immediate[0], immediate[1]]) as usize];
+                if max + target.max_stack_size as usize - target.inputs as usize > STACK_LIMIT {
+                    return Err(EofValidationError::StackOverflow);
+                }
+                if opcode == opcode::JUMPF && target.is_returning() {
+                    // stack has the outputs of this section that are not the outputs of the
+                    // target and the inputs of the target.
+                    let expected = (this.outputs as usize + target.inputs as usize)
+                        .checked_sub(target.outputs as usize)
+                        .ok_or(EofValidationError::InvalidReturnStack)?;
+                    if min != expected || max != expected {
+                        return Err(EofValidationError::InvalidReturnStack);
+                    }
+                }
+                let outputs = if target.is_returning() {
+                    target.outputs
+                } else {
+                    0
+                };
+                (target.inputs as usize, outputs as usize)
+            }
+            opcode::RETF => {
+                if min != this.outputs as usize || max != this.outputs as usize {
+                    return Err(EofValidationError::InvalidReturnStack);
+                }
+                (0, 0)
+            }
+            opcode::DUPN => (immediate[0] as usize + 1, immediate[0] as usize + 2),
+            opcode::SWAPN => (immediate[0] as usize + 2, immediate[0] as usize + 2),
+            opcode::EXCHANGE => {
+                let n = (immediate[0] >> 4) as usize + 1;
+                let m = (immediate[0] & 0x0F) as usize + 1;
+                (n + m + 1, n + m + 1)
+            }
+            _ => (info.inputs as usize, info.outputs as usize),
+        };
+        if min < inputs {
+            return Err(EofValidationError::StackUnderflow);
+        }
+        let next = (min - inputs + outputs, max - inputs + outputs);
+        max_height = max_height.max(next.1);
+        if max_height >= STACK_LIMIT {
+            return Err(EofValidationError::StackOverflow);
+        }
+
+        let mut merge = |target: usize| {
+            if target <= pc {
+                if heights[target] != Some(next) {
+                    return Err(EofValidationError::InvalidBackwardJumpStack);
+                }
+            } else {
+                heights[target] = Some(match heights[target] {
+                    Some((min, max)) => (min.min(next.0), max.max(next.1)),
+                    None => next,
+                });
+            }
+            Ok(())
+        };
+        match opcode {
+            opcode::RJUMP => merge(relative_target(end, immediate) as usize)?,
+            opcode::RJUMPI => {
+                merge(end)?;
+                merge(relative_target(end, immediate) as usize)?;
+            }
+            opcode::RJUMPV => {
+                merge(end)?;
+                for offset in immediate[1..].chunks_exact(2) {
+                    merge(relative_target(end, offset) as usize)?;
+                }
+            }
+            _ if info.terminating => {}
+            _ => merge(end)?,
+        }
+        pc = end;
+    }
+
+    if max_height != this.max_stack_size as usize {
+        return Err(EofValidationError::MaxStackMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{bytes, hex};
+
+    /// Inputs, outputs and max stack height with the code of the section.
+    type Section<'a> = ((u8, u8, u16), &'a [u8]);
+
+    /// Encodes the container with the code sections and their types.
+    fn container(sections: &[Section<'_>]) -> Bytes {
+        let mut raw = vec![0xEF, 0x00, 0x01, 0x01];
+        raw.extend((sections.len() as u16 * 4).to_be_bytes());
+        raw.push(0x02);
+        raw.extend((sections.len() as u16).to_be_bytes());
+        for (_, code) in sections {
+            raw.extend((code.len() as u16).to_be_bytes());
+        }
+        raw.extend([0x04, 0x00, 0x00, 0x00]);
+        for ((inputs, outputs, max_stack_size), _) in sections {
+            raw.extend([*inputs, *outputs]);
+            raw.extend(max_stack_size.to_be_bytes());
+        }
+        for (_, code) in sections {
+            raw.extend(*code);
+        }
+        raw.into()
+    }
+
+    fn validate(sections: &[Section<'_>]) -> Result<(), EofError> {
+        validate_raw_eof(container(sections)).map(|_| ())
+    }
+
+    const MAIN: (u8, u8, u16) = (0, TypesSection::NON_RETURNING, 0);
+
+    #[test]
+    fn validate_eof_functions() {
+        // PUSH1 1, CALLF 1, PUSH1 0, SSTORE, STOP
+        let main = hex!("6001e30001600055 00");
+        // DUP1, RJUMPI +1, RETF, PUSH1 2, ADD, RETF
+        let add_two = hex!("80e10001e4 600201e4");
+        let raw = bytes!("ef0001010008020002000900090400000000800002010100026001e300016000550080e10001e4600201e4");
+        assert_eq!(
+            container(&[((0, 0x80, 2), &main), ((1, 1, 2), &add_two)]),
+            raw
+        );
+        let eof = validate_raw_eof(raw).unwrap();
+        assert_eq!(eof.body.code_section[1][..], add_two);
+
+        let bytecode = to_eof(Bytecode::new_raw(eof.raw.clone()));
+        assert!(bytecode.is_eof());
+        let invalid = Bytecode::new_raw(bytes!("ef0001"));
+        assert!(!to_eof(invalid).is_eof());
+
+        assert_eq!(
+            validate(&[((0, 0x80, 2), &main), ((1, 1, 3), &add_two)]),
+            Err(EofValidationError::MaxStackMismatch.into())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 2), &main), ((1, 0x80, 2), &add_two)]),
+            Err(EofValidationError::CallfToNonReturning.into())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 3), &main), ((0, 1, 2), &add_two)]),
+            Err(EofValidationError::StackUnderflow.into())
+        );
+        assert_eq!(
+            validate(&[
+                ((0, 0x80, 2), &main),
+                ((1, 1, 2), &add_two),
+                ((0, 0, 0), &[0xE4])
+            ]),
+            Err(EofValidationError::UnreachableCodeSection.into())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 0), &hex!("e30005 00"))]),
+            Err(EofValidationError::InvalidCodeSection.into())
+        );
+        assert_eq!(
+            validate(&[((0, 0, 0), &[0x00])]),
+            Err(EofValidationError::InvalidFirstTypesSection.into())
+        );
+    }
+
+    #[test]
+    fn validate_eof_code() {
+        assert_eq!(validate(&[(MAIN, &[0xE0, 0xFF, 0xFD])]), Ok(()));
+        assert_eq!(
+            validate(&[(MAIN, &[0x56])]),
+            Err(EofValidationError::UnknownOpcode.into())
+        );
+        assert_eq!(
+            validate(&[(MAIN, &[0x60])]),
+            Err(EofValidationError::MissingImmediateBytes.into())
+        );
+        assert_eq!(
+            validate(&[(MAIN, &[0x5F, 0x50])]),
+            Err(EofValidationError::MissingTerminatingInstruction.into())
+        );
+        // RJUMP into its own immediate bytes and out of the code.
+        assert_eq!(
+            validate(&[(MAIN, &[0xE0, 0xFF, 0xFE])]),
+            Err(EofValidationError::JumpToImmediateBytes.into())
+        );
+        assert_eq!(
+            validate(&[(MAIN, &[0xE0, 0x00, 0x01])]),
+            Err(EofValidationError::InvalidJumpTarget.into())
+        );
+        // RJUMP to the next instruction, then over it.
+        assert_eq!(validate(&[(MAIN, &[0xE0, 0x00, 0x00, 0x00])]), Ok(()));
+        assert_eq!(
+            validate(&[(MAIN, &[0xE0, 0x00, 0x01, 0x00, 0x00])]),
+            Err(EofValidationError::UnreachableCode.into())
+        );
+        // PUSH0, RJUMP -4 loops with the growing stack.
+        assert_eq!(
+            validate(&[((0, 0x80, 1), &[0x5F, 0xE0, 0xFF, 0xFC])]),
+            Err(EofValidationError::InvalidBackwardJumpStack.into())
+        );
+        // PUSH0, RJUMPV [0, 1], PUSH0, PUSH0, POP, STOP: heights at POP are 1 or 2.
+        assert_eq!(
+            validate(&[((0, 0x80, 2), &hex!("5f e2 01 0000 0001 5f 5f 50 00"))]),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 1), &[0x50, 0x00])]),
+            Err(EofValidationError::StackUnderflow.into())
+        );
+    }
+
+    #[test]
+    fn validate_eof_stack_immediates() {
+        // PUSH0, PUSH0, DUPN 1, SWAPN 1, STOP
+        assert_eq!(
+            validate(&[((0, 0x80, 3), &hex!("5f 5f e601 e701 00"))]),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 3), &hex!("5f 5f e602 00"))]),
+            Err(EofValidationError::StackUnderflow.into())
+        );
+        // PUSH0, PUSH0, PUSH0, EXCHANGE 1 1, STOP
+        assert_eq!(
+            validate(&[((0, 0x80, 3), &hex!("5f 5f 5f e800 00"))]),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&[((0, 0x80, 3), &hex!("5f 5f 5f e801 00"))]),
+            Err(EofValidationError::StackUnderflow.into())
+        );
+        // DATALOADN 0 with the empty data section.
+        assert_eq!(
+            validate(&[((0, 0x80, 1), &hex!("d10000 00"))]),
+            Err(EofValidationError::InvalidDataLoadN.into())
+        );
+    }
+
+    #[test]
+    fn validate_eof_container_kinds() {
+        // PUSH0, PUSH0, RETURNCONTRACT 0
+        assert_eq!(
+            validate(&[((0, 0x80, 2), &hex!("5f5f ee00"))]),
+            Err(EofValidationError::ReturnContractInRuntime.into())
+        );
+        // EOFCREATE 0 without the container section.
+        assert_eq!(
+            validate(&[((0, 0x80, 4), &hex!("5f5f5f5f ec00 00"))]),
+            Err(EofValidationError::InvalidContainerIndex.into())
+        );
+        assert_eq!(
+            validate_raw_eof_initcode(container(&[(MAIN, &[0x00])])),
+            Err(EofValidationError::ReturnInInitcode.into())
+        );
+        assert!(validate_raw_eof_initcode(container(&[(MAIN, &[0xFE])])).is_ok());
+    }
 }
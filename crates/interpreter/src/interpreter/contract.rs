@@ -18,6 +18,10 @@ pub struct Contract {
     pub caller: Address,
     /// Value send to contract.
     pub value: U256,
+    /// Address of the account the bytecode is loaded from.
+    ///
+    /// It is `None` for initcode, as it is not part of the state.
+    pub code_address: Option<Address>,
 }
 
 impl Contract {
@@ -40,24 +44,28 @@ impl Contract {
             address,
             caller,
             value,
+            code_address: None,
         }
     }
 
     /// Creates a new contract from the given [`Env`].
     #[inline]
     pub fn new_env(env: &Env, bytecode: Bytecode, hash: B256) -> Self {
-        let contract_address = match env.tx.transact_to {
-            TransactTo::Call(caller) => caller,
-            TransactTo::Create(..) => Address::ZERO,
+        let (contract_address, code_address) = match env.tx.transact_to {
+            TransactTo::Call(caller) => (caller, Some(caller)),
+            TransactTo::Create(..) => (Address::ZERO, None),
         };
-        Self::new(
-            env.tx.data.clone(),
-            bytecode,
-            hash,
-            contract_address,
-            env.tx.caller,
-            env.tx.value,
-        )
+        Self {
+            code_address,
+            ..Self::new(
+                env.tx.data.clone(),
+                bytecode,
+                hash,
+                contract_address,
+                env.tx.caller,
+                env.tx.value,
+            )
+        }
     }
 
     /// Creates a new contract from the given [`CallContext`].
@@ -68,14 +76,17 @@ impl Contract {
         hash: B256,
         call_context: &CallContext,
     ) -> Self {
-        Self::new(
-            input,
-            bytecode,
-            hash,
-            call_context.address,
-            call_context.caller,
-            call_context.apparent_value,
-        )
+        Self {
+            code_address: Some(call_context.code_address),
+            ..Self::new(
+                input,
+                bytecode,
+                hash,
+                call_context.address,
+                call_context.caller,
+                call_context.apparent_value,
+            )
+        }
     }
 
     /// Returns whether the given position is a valid jump destination.
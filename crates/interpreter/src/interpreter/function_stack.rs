@@ -0,0 +1,60 @@
+use std::vec::Vec;
+
+/// EIP-4750: Maximum depth of the function return stack.
+pub const FUNCTION_STACK_LIMIT: usize = 1024;
+
+/// Code section and program counter to return to with `RETF`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionReturnFrame {
+    /// Index of the code section.
+    pub idx: usize,
+    /// Program counter inside the code section.
+    pub pc: usize,
+}
+
+/// Return stack of the EOF functions and the currently executing code section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionStack {
+    pub return_stack: Vec<FunctionReturnFrame>,
+    pub current_code_idx: usize,
+}
+
+impl FunctionStack {
+    /// Creates an empty stack that executes the first code section.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes the return frame of the current code section and enters the new one.
+    #[inline]
+    pub fn push(&mut self, program_counter: usize, new_idx: usize) {
+        self.return_stack.push(FunctionReturnFrame {
+            idx: self.current_code_idx,
+            pc: program_counter,
+        });
+        self.current_code_idx = new_idx;
+    }
+
+    /// Pops the return frame and returns to its code section.
+    #[inline]
+    pub fn pop(&mut self) -> Option<FunctionReturnFrame> {
+        self.return_stack.pop().inspect(|frame| {
+            self.current_code_idx = frame.idx;
+        })
+    }
+
+    /// Returns the number of the return frames.
+    #[inline]
+    pub fn return_stack_len(&self) -> usize {
+        self.return_stack.len()
+    }
+
+    /// Enters the code section without the return frame, used by `JUMPF`.
+    #[inline]
+    pub fn set_current_code_idx(&mut self, idx: usize) {
+        self.current_code_idx = idx;
+    }
+}
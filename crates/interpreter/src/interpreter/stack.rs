@@ -199,11 +199,11 @@ impl Stack {
         }
     }
 
-    /// Duplicates the `N`th value from the top of the stack.
+    /// Duplicates the `n`th value from the top of the stack.
     #[inline]
-    pub fn dup<const N: usize>(&mut self) -> Result<(), InstructionResult> {
+    pub fn dup(&mut self, n: usize) -> Result<(), InstructionResult> {
         let len = self.data.len();
-        if len < N {
+        if len < n {
             Err(InstructionResult::StackUnderflow)
         } else if len + 1 > STACK_LIMIT {
             Err(InstructionResult::StackOverflow)
@@ -211,22 +211,28 @@ impl Stack {
             // SAFETY: check for out of bounds is done above and it makes this safe to do.
             unsafe {
                 let data = self.data.as_mut_ptr();
-                core::ptr::copy_nonoverlapping(data.add(len - N), data.add(len), 1);
+                core::ptr::copy_nonoverlapping(data.add(len - n), data.add(len), 1);
                 self.data.set_len(len + 1);
             }
             Ok(())
         }
     }
 
-    /// Swaps the topmost value with the `N`th value from the top.
+    /// Swaps the topmost value with the `n`th value from the top.
     #[inline]
-    pub fn swap<const N: usize>(&mut self) -> Result<(), InstructionResult> {
+    pub fn swap(&mut self, n: usize) -> Result<(), InstructionResult> {
+        self.exchange(0, n)
+    }
+
+    /// Swaps the `n`th value from the top with the `n + m`th value from the top.
+    #[inline]
+    pub fn exchange(&mut self, n: usize, m: usize) -> Result<(), InstructionResult> {
         let len = self.data.len();
-        if len <= N {
+        if len <= n + m {
             return Err(InstructionResult::StackUnderflow);
         }
-        let last = len - 1;
-        self.data.swap(last, last - N);
+        let top = len - 1;
+        self.data.swap(top - n, top - n - m);
         Ok(())
     }
 
@@ -238,7 +244,7 @@ impl Stack {
             return Ok(());
         }
 
-        let n_words = (slice.len() + 31) / 32;
+        let n_words = slice.len().div_ceil(32);
         let new_len = self.data.len() + n_words;
         if new_len > STACK_LIMIT {
             return Err(InstructionResult::StackOverflow);
@@ -283,7 +289,7 @@ impl Stack {
                 i += 1;
             }
 
-            debug_assert_eq!((i + 3) / 4, n_words, "wrote too much");
+            debug_assert_eq!(i.div_ceil(4), n_words, "wrote too much");
 
             // zero out upper bytes of last word
             let m = i % 4; // 32 / 8
@@ -389,4 +395,18 @@ mod tests {
             assert_eq!(stack.data, [U256::ZERO, U256::ZERO, U256::from(n)]);
         });
     }
+
+    #[test]
+    fn exchange() {
+        run(|stack| {
+            for i in 0..5u64 {
+                stack.push(U256::from(i)).unwrap();
+            }
+            stack.exchange(1, 2).unwrap();
+            assert_eq!(stack.data, [0, 3, 2, 1, 4].map(U256::from));
+            stack.swap(4).unwrap();
+            assert_eq!(stack.data, [4, 3, 2, 1, 0].map(U256::from));
+            assert_eq!(stack.exchange(2, 3), Err(InstructionResult::StackUnderflow));
+        });
+    }
 }
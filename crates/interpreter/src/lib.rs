@@ -30,8 +30,9 @@ pub use inner_models::*;
 pub use instruction_result::*;
 pub use instructions::{opcode, Instruction, OpCode, OPCODE_JUMPMAP};
 pub use interpreter::{
-    analysis, next_multiple_of_32, BytecodeLocked, Contract, Interpreter, InterpreterAction,
-    InterpreterResult, SharedMemory, Stack, EMPTY_SHARED_MEMORY, STACK_LIMIT,
+    analysis, next_multiple_of_32, BytecodeLocked, Contract, FunctionReturnFrame, FunctionStack,
+    Interpreter, InterpreterAction, InterpreterResult, SharedMemory, Stack, EMPTY_SHARED_MEMORY,
+    FUNCTION_STACK_LIMIT, STACK_LIMIT,
 };
 pub use primitives::{MAX_CODE_SIZE, MAX_INITCODE_SIZE};
 
@@ -0,0 +1,258 @@
+//! Custom BLAKE3 hash precompile, it is not part of any spec.
+//!
+//! Chains that want it add it to their precompile set at the address of their choice:
+//!
+//! ```
+//! # use revm_precompile::{blake3, u64_to_address, Precompiles};
+//! let precompiles = Precompiles::latest()
+//!     .clone()
+//!     .with_precompile(blake3::blake3(u64_to_address(0x53a)));
+//! ```
+//!
+//! See: <https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf>
+use super::calc_linear_cost_u32;
+use crate::{Error, Precompile, PrecompileResult, PrecompileWithAddress};
+use revm_primitives::{Address, Bytes};
+use std::vec::Vec;
+
+/// Returns the BLAKE3 precompile at the given address.
+pub const fn blake3(address: Address) -> PrecompileWithAddress {
+    PrecompileWithAddress(address, Precompile::Standard(blake3_run))
+}
+
+/// The base cost of the operation.
+pub const BLAKE3_BASE: u64 = 30;
+/// The cost per word.
+pub const BLAKE3_PER_WORD: u64 = 4;
+
+/// Returns 32 byte BLAKE3 hash of the input.
+fn blake3_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let cost = calc_linear_cost_u32(input.len(), BLAKE3_BASE, BLAKE3_PER_WORD);
+    if cost > gas_limit {
+        return Err(Error::OutOfGas);
+    }
+    Ok((cost, hash(input).to_vec().into()))
+}
+
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const CHUNK_LEN: usize = 1024;
+const BLOCK_LEN: usize = 64;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+
+/// Returns the 32 byte BLAKE3 hash of the input.
+pub fn hash(input: &[u8]) -> [u8; 32] {
+    // last chunk is the root or the rightmost leaf, empty input has one empty chunk.
+    let chunk_count = input.len().div_ceil(CHUNK_LEN).max(1);
+    let mut cv_stack: Vec<[u32; 8]> = Vec::new();
+    for (counter, chunk) in input.chunks(CHUNK_LEN).take(chunk_count - 1).enumerate() {
+        let mut cv = chunk_output(chunk, counter as u64).chaining_value();
+        // merge completed subtrees, one merge for each trailing zero of the chunk count.
+        let mut total_chunks = counter as u64 + 1;
+        while total_chunks & 1 == 0 {
+            cv = parent_output(cv_stack.pop().unwrap(), cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        cv_stack.push(cv);
+    }
+
+    let last = &input[(chunk_count - 1) * CHUNK_LEN..];
+    let mut output = chunk_output(last, chunk_count as u64 - 1);
+    while let Some(left) = cv_stack.pop() {
+        output = parent_output(left, output.chaining_value());
+    }
+
+    let words = output.root_words();
+    let mut out = [0u8; 32];
+    for (bytes, word) in out.chunks_exact_mut(4).zip(words) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+/// Input of the compression function that is not yet compressed, last one becomes the root.
+struct Output {
+    input_cv: [u32; 8],
+    block_words: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        let out = compress(
+            &self.input_cv,
+            &self.block_words,
+            self.counter,
+            self.block_len,
+            self.flags,
+        );
+        out[..8].try_into().unwrap()
+    }
+
+    /// First eight words of the root output, 32 bytes of the hash.
+    fn root_words(&self) -> [u32; 8] {
+        let out = compress(
+            &self.input_cv,
+            &self.block_words,
+            0,
+            self.block_len,
+            self.flags | ROOT,
+        );
+        out[..8].try_into().unwrap()
+    }
+}
+
+/// Compresses all blocks of the chunk except the last one, which is returned as the output.
+fn chunk_output(chunk: &[u8], counter: u64) -> Output {
+    let block_count = chunk.len().div_ceil(BLOCK_LEN).max(1);
+    let mut cv = IV;
+    for (i, block) in chunk.chunks(BLOCK_LEN).take(block_count - 1).enumerate() {
+        let flags = if i == 0 { CHUNK_START } else { 0 };
+        let out = compress(&cv, &block_words(block), counter, BLOCK_LEN as u32, flags);
+        cv = out[..8].try_into().unwrap();
+    }
+    let last = &chunk[(block_count - 1) * BLOCK_LEN..];
+    let start = if block_count == 1 { CHUNK_START } else { 0 };
+    Output {
+        input_cv: cv,
+        block_words: block_words(last),
+        counter,
+        block_len: last.len() as u32,
+        flags: start | CHUNK_END,
+    }
+}
+
+fn parent_output(left: [u32; 8], right: [u32; 8]) -> Output {
+    let mut block_words = [0u32; 16];
+    block_words[..8].copy_from_slice(&left);
+    block_words[8..].copy_from_slice(&right);
+    Output {
+        input_cv: IV,
+        block_words,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT,
+    }
+}
+
+/// Little endian words of the block, padded with zeros.
+fn block_words(block: &[u8]) -> [u32; 16] {
+    let mut bytes = [0u8; BLOCK_LEN];
+    bytes[..block.len()].copy_from_slice(block);
+    let mut words = [0u32; 16];
+    for (word, bytes) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words
+}
+
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+fn round(state: &mut [u32; 16], m: &[u32; 16]) {
+    // mix the columns.
+    g(state, 0, 4, 8, 12, m[0], m[1]);
+    g(state, 1, 5, 9, 13, m[2], m[3]);
+    g(state, 2, 6, 10, 14, m[4], m[5]);
+    g(state, 3, 7, 11, 15, m[6], m[7]);
+    // mix the diagonals.
+    g(state, 0, 5, 10, 15, m[8], m[9]);
+    g(state, 1, 6, 11, 12, m[10], m[11]);
+    g(state, 2, 7, 8, 13, m[12], m[13]);
+    g(state, 3, 4, 9, 14, m[14], m[15]);
+}
+
+fn compress(
+    cv: &[u32; 8],
+    block_words: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    let mut block = *block_words;
+    for i in 0..7 {
+        round(&mut state, &block);
+        if i < 6 {
+            block = MSG_PERMUTATION.map(|j| block[j]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn test_blake3_hash() {
+        assert_eq!(
+            hash(b""),
+            hex!("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262")
+        );
+        assert_eq!(
+            hash(b"abc"),
+            hex!("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")
+        );
+        // multi chunk inputs of the official test vectors.
+        let input = |len: usize| (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        assert_eq!(
+            hash(&input(1025)),
+            hex!("d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444")
+        );
+        assert_eq!(
+            hash(&input(102400)),
+            hex!("bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085")
+        );
+    }
+
+    #[test]
+    fn test_blake3_precompile() {
+        let input = Bytes::from_static(b"abc");
+        let (gas, output) = blake3_run(&input, 34).unwrap();
+        assert_eq!(gas, 34);
+        assert_eq!(output[..], hash(b"abc"));
+        assert_eq!(blake3_run(&input, 33), Err(Error::OutOfGas));
+    }
+}
@@ -180,7 +180,7 @@ fn run_pair(
         return Err(Error::OutOfGas);
     }
 
-    if input.len() % PAIR_ELEMENT_LEN != 0 {
+    if !input.len().is_multiple_of(PAIR_ELEMENT_LEN) {
         return Err(Error::Bn128PairLength);
     }
 
@@ -0,0 +1,128 @@
+//! Custom ed25519 signature verification precompile, it is not part of any spec.
+//!
+//! Chains bridging to ecosystems that sign with ed25519 add it to their precompile set at the
+//! address of their choice:
+//!
+//! ```
+//! # use revm_precompile::{ed25519, u64_to_address, Precompiles};
+//! let precompiles = Precompiles::latest()
+//!     .clone()
+//!     .with_precompile(ed25519::ed25519_verify(u64_to_address(0x53b)));
+//! ```
+//!
+//! See: <https://datatracker.ietf.org/doc/html/rfc8032>
+use super::calc_linear_cost_u32;
+use crate::{Error, Precompile, PrecompileResult, PrecompileWithAddress};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use revm_primitives::{Address, Bytes, B256};
+
+/// Returns the ed25519 verification precompile at the given address.
+pub const fn ed25519_verify(address: Address) -> PrecompileWithAddress {
+    PrecompileWithAddress(address, Precompile::Standard(ed25519_verify_run))
+}
+
+/// Gas cost of the signature verification.
+pub const ED25519_VERIFY_BASE: u64 = 2000;
+/// Gas cost per word of the message.
+pub const ED25519_VERIFY_PER_WORD: u64 = 12;
+
+/// Input is the 32 byte public key, the 64 byte signature and the message.
+///
+/// Returns 32 bytes with the value one if the signature is valid and empty output otherwise,
+/// invalid input is not an error.
+fn ed25519_verify_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let message_len = input.len().saturating_sub(96);
+    let cost = calc_linear_cost_u32(message_len, ED25519_VERIFY_BASE, ED25519_VERIFY_PER_WORD);
+    if cost > gas_limit {
+        return Err(Error::OutOfGas);
+    }
+    let output = if input.len() >= 96 && verify(&input[..32], &input[32..96], &input[96..]) {
+        B256::with_last_byte(1).into()
+    } else {
+        Bytes::new()
+    };
+    Ok((cost, output))
+}
+
+/// Returns true if the signature of the message is valid for the public key.
+///
+/// Verification follows RFC 8032 as implemented by `ed25519-dalek`, non-canonical `S` and
+/// public keys that are not on the curve are rejected.
+pub fn verify(public_key: &[u8], signature: &[u8], message: &[u8]) -> bool {
+    let Ok(public_key) = public_key.try_into() else {
+        return false;
+    };
+    let Ok(public_key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+    public_key.verify(message, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{hex, U256};
+
+    /// Order of the base point.
+    const L: U256 = U256::from_be_bytes(hex!(
+        "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed"
+    ));
+
+    // key and signature made with openssl.
+    const PUBLIC_KEY: [u8; 32] =
+        hex!("6138e60bc2b213beee84eb144624147148ff015749bcc7ba19d77eab0e6684bc");
+    const SIGNATURE: [u8; 64] = hex!(
+        "0d4d92e2caf648878438f9b8725eb1ae923287bf420302552099c3a6dfa7ac58"
+        "ee8563ac89c3da7f0e5c7b161679dc8d94c477869f132d45c135dc147c8f9d03"
+    );
+    const MESSAGE: &[u8] = b"revm ed25519 verify";
+
+    #[test]
+    fn test_ed25519_verify() {
+        assert!(verify(&PUBLIC_KEY, &SIGNATURE, MESSAGE));
+        assert!(!verify(&PUBLIC_KEY, &SIGNATURE, b"revm ed25519 verifY"));
+        let mut signature = SIGNATURE;
+        signature[0] ^= 1;
+        assert!(!verify(&PUBLIC_KEY, &signature, MESSAGE));
+
+        // non-canonical `S`, `S + L` verifies the same equation.
+        let mut signature = SIGNATURE;
+        let s = U256::from_le_slice(&SIGNATURE[32..]) + L;
+        signature[32..].copy_from_slice(&s.to_le_bytes::<32>());
+        assert!(!verify(&PUBLIC_KEY, &signature, MESSAGE));
+
+        // y = 2 is not on the curve.
+        let mut public_key = [0; 32];
+        public_key[0] = 2;
+        assert!(!verify(&public_key, &SIGNATURE, MESSAGE));
+
+        // wrong lengths.
+        assert!(!verify(&PUBLIC_KEY[..31], &SIGNATURE, MESSAGE));
+        assert!(!verify(&PUBLIC_KEY, &SIGNATURE[..63], MESSAGE));
+
+        // RFC 8032 test 1, empty message.
+        assert!(verify(
+            &hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"),
+            &hex!(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+                "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            ),
+            b""
+        ));
+    }
+
+    #[test]
+    fn test_ed25519_verify_precompile() {
+        let input: Bytes = [&PUBLIC_KEY[..], &SIGNATURE, MESSAGE].concat().into();
+        let (gas, output) = ed25519_verify_run(&input, 2012).unwrap();
+        assert_eq!(gas, ED25519_VERIFY_BASE + ED25519_VERIFY_PER_WORD);
+        assert_eq!(output, Bytes::from(B256::with_last_byte(1)));
+        assert_eq!(ed25519_verify_run(&input, 2011), Err(Error::OutOfGas));
+
+        let (_, output) = ed25519_verify_run(&input.slice(..95), 2000).unwrap();
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+//! Precompile wrapper that replaces the gas cost of the precompile.
+//!
+//! Used by chains that price precompiles differently from Ethereum mainnet.
+use crate::{calc_linear_cost_u32, Error, Precompile, PrecompileResult};
+use core::fmt;
+use revm_primitives::{Bytes, Env, StatefulPrecompileMut};
+use std::sync::Arc;
+
+/// Gas cost of the precompile for the given input.
+pub type GasCostFn = Arc<dyn Fn(&Bytes) -> u64 + Send + Sync>;
+
+/// Precompile with the gas cost replaced by the cost function.
+///
+/// Cost is checked against the gas limit before the inner precompile is run, the inner
+/// precompile runs without the gas limit and its own cost is ignored.
+#[derive(Clone)]
+pub struct GasOverride {
+    precompile: Precompile,
+    cost: GasCostFn,
+}
+
+impl fmt::Debug for GasOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GasOverride")
+            .field("precompile", &self.precompile)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GasOverride {
+    /// Creates new precompile with the cost function.
+    pub fn new(
+        precompile: Precompile,
+        cost: impl Fn(&Bytes) -> u64 + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            precompile,
+            cost: Arc::new(cost),
+        }
+    }
+
+    /// Creates new precompile with the cost of `base + word * ceil(len / 32)`, same
+    /// formula as the hash and identity precompiles use.
+    pub fn linear(precompile: Precompile, base: u64, word: u64) -> Self {
+        Self::new(precompile, move |input| {
+            calc_linear_cost_u32(input.len(), base, word)
+        })
+    }
+
+    /// Returns the wrapped precompile.
+    pub fn precompile(&self) -> &Precompile {
+        &self.precompile
+    }
+}
+
+impl StatefulPrecompileMut for GasOverride {
+    fn call_mut(&mut self, bytes: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        let cost = (self.cost)(bytes);
+        if cost > gas_limit {
+            return Err(Error::OutOfGas);
+        }
+        let (_, output) = self.precompile.call(bytes, u64::MAX, env)?;
+        Ok((cost, output))
+    }
+}
+
+impl From<GasOverride> for Precompile {
+    fn from(precompile: GasOverride) -> Self {
+        Precompile::new_stateful_mut(precompile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{u64_to_address, Precompiles};
+
+    #[test]
+    fn test_sha256_gas_override() {
+        let sha256 = u64_to_address(2);
+        let mut precompiles = Precompiles::latest().clone();
+        assert!(precompiles.override_gas(&sha256, |input| 10 + input.len() as u64));
+        assert!(!precompiles.override_gas(&u64_to_address(0x100), |_| 0));
+
+        let precompile = precompiles.get_mut(&sha256).unwrap();
+        let input = Bytes::from_static(&[0; 40]);
+        let (gas, output) = precompile.call(&input, 50, &Env::default()).unwrap();
+        assert_eq!(gas, 50);
+        assert_eq!(output.len(), 32);
+        assert_eq!(
+            precompile.call(&input, 49, &Env::default()),
+            Err(Error::OutOfGas)
+        );
+    }
+}
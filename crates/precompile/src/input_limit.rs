@@ -0,0 +1,71 @@
+//! Precompile wrapper that rejects inputs longer than the limit.
+//!
+//! Used by simulators to reject adversarial inputs, e.g. multi-megabyte `MODEXP` or pairing
+//! inputs, before the precompile computes the gas cost.
+use crate::{Error, Precompile, PrecompileResult};
+use revm_primitives::{Bytes, Env, StatefulPrecompileMut};
+
+/// Precompile that fails with [Error::InputTooLong] if the input is longer than the limit.
+#[derive(Clone, Debug)]
+pub struct InputLimit {
+    precompile: Precompile,
+    max_len: usize,
+}
+
+impl InputLimit {
+    /// Creates new precompile with the maximum input length in bytes.
+    pub fn new(precompile: Precompile, max_len: usize) -> Self {
+        Self {
+            precompile,
+            max_len,
+        }
+    }
+
+    /// Returns the maximum input length in bytes.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns the wrapped precompile.
+    pub fn precompile(&self) -> &Precompile {
+        &self.precompile
+    }
+}
+
+impl StatefulPrecompileMut for InputLimit {
+    fn call_mut(&mut self, bytes: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        if bytes.len() > self.max_len {
+            return Err(Error::InputTooLong);
+        }
+        self.precompile.call(bytes, gas_limit, env)
+    }
+}
+
+impl From<InputLimit> for Precompile {
+    fn from(precompile: InputLimit) -> Self {
+        Precompile::new_stateful_mut(precompile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{u64_to_address, Precompiles};
+
+    #[test]
+    fn test_modexp_input_limit() {
+        let modexp = u64_to_address(5);
+        let mut precompiles = Precompiles::latest().clone();
+        assert!(precompiles.limit_input(&modexp, 96));
+
+        let precompile = precompiles.get_mut(&modexp).unwrap();
+        let env = Env::default();
+        assert!(precompile
+            .call(&Bytes::from(vec![0; 96]), 1_000, &env)
+            .is_ok());
+        assert_eq!(
+            precompile.call(&Bytes::from(vec![0; 97]), 1_000, &env),
+            Err(Error::InputTooLong)
+        );
+    }
+}
@@ -11,16 +11,24 @@
 extern crate alloc as std;
 
 mod blake2;
+pub mod blake3;
 mod bn128;
+#[cfg(feature = "ed25519")]
+pub mod ed25519;
+pub mod gas_override;
 mod hash;
 mod identity;
+pub mod input_limit;
 #[cfg(feature = "c-kzg")]
 pub mod kzg_point_evaluation;
 mod modexp;
 mod secp256k1;
+pub mod secp256r1;
 pub mod utilities;
 
 use core::hash::Hash;
+pub use gas_override::GasOverride;
+pub use input_limit::InputLimit;
 use once_cell::race::OnceBox;
 #[doc(hidden)]
 pub use revm_primitives as primitives;
@@ -28,10 +36,11 @@ pub use revm_primitives::{
     precompile::{PrecompileError as Error, *},
     Address, Bytes, HashMap, Log, B256,
 };
+pub use secp256k1::recover_address;
 use std::{boxed::Box, vec::Vec};
 
 pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
-    (len as u64 + 32 - 1) / 32 * word + base
+    (len as u64).div_ceil(32) * word + base
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -65,6 +74,8 @@ impl Precompiles {
             PrecompileSpecId::ISTANBUL => Self::istanbul(),
             PrecompileSpecId::BERLIN => Self::berlin(),
             PrecompileSpecId::CANCUN => Self::cancun(),
+            #[cfg(feature = "optimism")]
+            PrecompileSpecId::FJORD => Self::fjord(),
             PrecompileSpecId::LATEST => Self::latest(),
         }
     }
@@ -157,6 +168,22 @@ impl Precompiles {
         })
     }
 
+    /// Returns precompiles for the Fjord spec of OP-Stack chains.
+    ///
+    /// Cancun precompiles with the RIP-7212 `P256VERIFY` precompile.
+    #[cfg(feature = "optimism")]
+    pub fn fjord() -> &'static Self {
+        static INSTANCE: OnceBox<Precompiles> = OnceBox::new();
+        INSTANCE.get_or_init(|| {
+            let mut precompiles = Self::cancun().clone();
+            precompiles.extend([
+                // RIP-7212: Precompile for secp256r1 Curve Support
+                secp256r1::P256VERIFY,
+            ]);
+            Box::new(precompiles)
+        })
+    }
+
     /// Returns the precompiles for the latest spec.
     pub fn latest() -> &'static Self {
         Self::cancun()
@@ -208,6 +235,84 @@ impl Precompiles {
     pub fn extend(&mut self, other: impl IntoIterator<Item = PrecompileWithAddress>) {
         self.inner.extend(other.into_iter().map(Into::into));
     }
+
+    /// Inserts the precompile, returns the precompile it replaced.
+    #[inline]
+    pub fn insert(&mut self, precompile: PrecompileWithAddress) -> Option<Precompile> {
+        self.inner.insert(precompile.0, precompile.1)
+    }
+
+    /// Removes the precompile at the given address and returns it.
+    #[inline]
+    pub fn remove(&mut self, address: &Address) -> Option<Precompile> {
+        self.inner.remove(address)
+    }
+
+    /// Returns the precompiles with the given precompile inserted or replaced.
+    ///
+    /// Used to build custom set starting from the spec set:
+    ///
+    /// ```
+    /// # use revm_precompile::{secp256r1, u64_to_address, PrecompileSpecId, Precompiles};
+    /// let precompiles = Precompiles::new(PrecompileSpecId::CANCUN)
+    ///     .clone()
+    ///     .with_precompile(secp256r1::P256VERIFY)
+    ///     .without_precompile(&u64_to_address(9));
+    /// assert!(precompiles.contains(&u64_to_address(0x100)));
+    /// ```
+    pub fn with_precompile(mut self, precompile: PrecompileWithAddress) -> Self {
+        self.insert(precompile);
+        self
+    }
+
+    /// Returns the precompiles without the precompile at the given address.
+    pub fn without_precompile(mut self, address: &Address) -> Self {
+        self.remove(address);
+        self
+    }
+
+    /// Moves the precompile to the new address, precompile already at the new address is replaced.
+    ///
+    /// Returns false if there is no precompile at the old address.
+    ///
+    /// ```
+    /// # use revm_precompile::{u64_to_address, Precompiles};
+    /// let mut precompiles = Precompiles::berlin().clone();
+    /// assert!(precompiles.remap(&u64_to_address(2), u64_to_address(0x1002)));
+    /// assert!(!precompiles.contains(&u64_to_address(2)));
+    /// assert!(precompiles.contains(&u64_to_address(0x1002)));
+    /// ```
+    pub fn remap(&mut self, from: &Address, to: Address) -> bool {
+        let Some(precompile) = self.inner.remove(from) else {
+            return false;
+        };
+        self.inner.insert(to, precompile);
+        true
+    }
+
+    /// Limits the input length of the precompile at the given address, see [InputLimit].
+    /// Returns false if there is no precompile at the address.
+    pub fn limit_input(&mut self, address: &Address, max_len: usize) -> bool {
+        let Some(precompile) = self.inner.get_mut(address) else {
+            return false;
+        };
+        *precompile = InputLimit::new(precompile.clone(), max_len).into();
+        true
+    }
+
+    /// Replaces the gas cost of the precompile at the given address with the cost function,
+    /// see [GasOverride]. Returns false if there is no precompile at the address.
+    pub fn override_gas(
+        &mut self,
+        address: &Address,
+        cost: impl Fn(&Bytes) -> u64 + Send + Sync + 'static,
+    ) -> bool {
+        let Some(precompile) = self.inner.get_mut(address) else {
+            return false;
+        };
+        *precompile = GasOverride::new(precompile.clone(), cost).into();
+        true
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -232,6 +337,8 @@ pub enum PrecompileSpecId {
     ISTANBUL,
     BERLIN,
     CANCUN,
+    #[cfg(feature = "optimism")]
+    FJORD,
     LATEST,
 }
 
@@ -247,11 +354,16 @@ impl PrecompileSpecId {
             ISTANBUL | MUIR_GLACIER => Self::ISTANBUL,
             BERLIN | LONDON | ARROW_GLACIER | GRAY_GLACIER | MERGE | SHANGHAI => Self::BERLIN,
             CANCUN => Self::CANCUN,
-            LATEST => Self::LATEST,
+            #[cfg(not(feature = "optimism"))]
+            PRAGUE | PRAGUE_EOF => Self::CANCUN,
+            #[cfg(not(feature = "optimism"))]
+            LATEST | VERKLE => Self::LATEST,
             #[cfg(feature = "optimism")]
             BEDROCK | REGOLITH | CANYON => Self::BERLIN,
             #[cfg(feature = "optimism")]
             ECOTONE => Self::CANCUN,
+            #[cfg(feature = "optimism")]
+            FJORD | PRAGUE | PRAGUE_EOF | LATEST | VERKLE => Self::FJORD,
         }
     }
 }
@@ -261,9 +373,19 @@ impl PrecompileSpecId {
 /// Note that 32 + 128 = 160 = 20 bytes (the length of an address). This function is used
 /// as a convenience for specifying the addresses of the various precompiles.
 #[inline]
-const fn u64_to_address(x: u64) -> Address {
+pub const fn u64_to_address(x: u64) -> Address {
     let x = x.to_be_bytes();
     Address::new([
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
     ])
 }
+
+/// Inverse of [u64_to_address], `None` if the address does not fit into a `u64`.
+#[inline]
+pub fn address_to_u64(address: &Address) -> Option<u64> {
+    let (high, low) = address.split_at(12);
+    if high.iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(low.try_into().unwrap()))
+}
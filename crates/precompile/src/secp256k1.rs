@@ -1,5 +1,5 @@
 use crate::{utilities::right_pad, Error, Precompile, PrecompileResult, PrecompileWithAddress};
-use revm_primitives::{alloy_primitives::B512, Bytes, B256};
+use revm_primitives::{alloy_primitives::B512, Address, Bytes, B256};
 
 pub const ECRECOVER: PrecompileWithAddress = PrecompileWithAddress(
     crate::u64_to_address(1),
@@ -64,6 +64,15 @@ mod secp256k1 {
     }
 }
 
+/// Recovers the address that signed the `msg` hash, `None` if the signature is invalid.
+///
+/// `recid` has to be `0` or `1`.
+pub fn recover_address(sig: &B512, recid: u8, msg: &B256) -> Option<Address> {
+    secp256k1::ecrecover(sig, recid, msg)
+        .ok()
+        .map(Address::from_word)
+}
+
 fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     const ECRECOVER_BASE: u64 = 3_000;
 
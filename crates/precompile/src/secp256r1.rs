@@ -0,0 +1,234 @@
+//! RIP-7212 `P256VERIFY` precompile, verifies ECDSA signatures over the secp256r1 (P-256) curve.
+//!
+//! It is not part of any Ethereum mainnet spec, chains that support it add it to their
+//! precompile set with [Precompiles::extend](crate::Precompiles::extend).
+//!
+//! See: <https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md>
+use crate::{Error, Precompile, PrecompileResult, PrecompileWithAddress};
+use p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use revm_primitives::{Bytes, B256};
+
+/// `P256VERIFY` at the address `0x100` proposed by RIP-7212.
+pub const P256VERIFY: PrecompileWithAddress = PrecompileWithAddress(
+    crate::u64_to_address(0x100),
+    Precompile::Standard(p256_verify),
+);
+
+/// Gas cost of the signature verification.
+pub const P256VERIFY_BASE: u64 = 3450;
+
+/// Input is 160 bytes: message hash, `r`, `s` and the `x` and `y` coordinates of the public key.
+///
+/// Returns 32 bytes with the value one if the signature is valid and empty output otherwise,
+/// invalid input is not an error.
+fn p256_verify(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    if P256VERIFY_BASE > gas_limit {
+        return Err(Error::OutOfGas);
+    }
+    let output = if verify_impl(input) {
+        B256::with_last_byte(1).into()
+    } else {
+        Bytes::new()
+    };
+    Ok((P256VERIFY_BASE, output))
+}
+
+/// Returns true if the input is 160 bytes long and holds a valid signature.
+///
+/// Signature with `r` or `s` that is zero or not below the curve order and public key that is
+/// not on the curve or is the point at infinity are invalid. High `s` values are accepted.
+pub fn verify_impl(input: &[u8]) -> bool {
+    if input.len() != 160 {
+        return false;
+    }
+    let (hash, sig, key) = (&input[..32], &input[32..96], &input[96..]);
+
+    // public key in the uncompressed SEC1 encoding.
+    let mut encoded_key = [0u8; 65];
+    encoded_key[0] = 0x04;
+    encoded_key[1..].copy_from_slice(key);
+
+    let Ok(signature) = Signature::from_slice(sig) else {
+        return false;
+    };
+    let Ok(key) = VerifyingKey::from_sec1_bytes(&encoded_key) else {
+        return false;
+    };
+    key.verify_prehash(hash, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    // signature made with openssl over sha256 of "revm p256verify".
+    const VALID: &str = "\
+        9082a6391351e92890ea193cefe6b8594f34cab50b7161ec4616834b15cd9a51\
+        e830cac017e20da44f6f111b17002f4b5ae2e4325e7536a0a9b87f9db783ebd8\
+        979f04e5b3546d770b0bb6dd5221913e16eea0cc96f4650cda96082dcf121c9b\
+        26bad900c81fcd985dd3aaa38b6df28d616ee27f57e0186948928f70ddb7cb17\
+        3f0299ce287a75ffe3ab0e348934cce4e07f2f6402bb169df31054ba9810ac01";
+
+    // RIP-7212 reference vectors from https://github.com/daimo-eth/p256-verifier/tree/master/test-vectors
+    const REFERENCE_VALID: [&str; 5] = [
+        "\
+        4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d\
+        a73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac\
+        36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60\
+        4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3\
+        7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e",
+        "\
+        3fec5769b5cf4e310a7d150508e82fb8e3eda1c2c94c61492d3bd8aea99e06c9\
+        e22466e928fdccef0de49e3503d2657d00494a00e764fd437bdafa05f5922b1f\
+        bbb77c6817ccf50748419477e843d5bac67e6a70e97dde5a57e0c983b777e1ad\
+        31a80482dadf89de6302b1988c82c29544c9c07bb910596158f6062517eb089a\
+        2f54c9a0f348752950094d3228d3b940258c75fe2a413cb70baa21dc2e352fc5",
+        "\
+        e775723953ead4a90411a02908fd1a629db584bc600664c609061f221ef6bf7c\
+        440066c8626b49daaa7bf2bcc0b74be4f7a1e3dcf0e869f1542fe821498cbf2d\
+        e73ad398194129f635de4424a07ca715838aefe8fe69d1a391cfa70470795a80\
+        dd056866e6e1125aff94413921880c437c9e2570a28ced7267c8beef7e9b2d8d\
+        1547d76dfcf4bee592f5fefe10ddfb6aeb0991c5b9dbbee6ec80d11b17c0eb1a",
+        "\
+        b5a77e7a90aa14e0bf5f337f06f597148676424fae26e175c6e5621c34351955\
+        289f319789da424845c9eac935245fcddd805950e2f02506d09be7e411199556\
+        d262144475b1fa46ad85250728c600c53dfd10f8b3f4adf140e27241aec3c2da\
+        3a81046703fccf468b48b145f939efdbb96c3786db712b3113bb2488ef286cdc\
+        ef8afe82d200a5bb36b5462166e8ce77f2d831a52ef2135b2af188110beaefb1",
+        "\
+        858b991cfd78f16537fe6d1f4afd10273384db08bdfc843562a22b0626766686\
+        f6aec8247599f40bfe01bec0e0ecf17b4319559022d4d9bf007fe929943004eb\
+        4866760dedf31b7c691f5ce665f8aae0bda895c23595c834fecc2390a5bcc203\
+        b04afcacbb4280713287a2d0c37e23f7513fab898f2c1fefa00ec09a924c335d\
+        9b629f1d4fb71901c3e59611afbfea354d101324e894c788d1c01f00b3c251b2",
+    ];
+
+    // reference vectors with the first byte of the message hash changed.
+    const REFERENCE_WRONG_HASH: [&str; 5] = [
+        "\
+        3cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d\
+        a73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac\
+        36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60\
+        4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3\
+        7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e",
+        "\
+        afec5769b5cf4e310a7d150508e82fb8e3eda1c2c94c61492d3bd8aea99e06c9\
+        e22466e928fdccef0de49e3503d2657d00494a00e764fd437bdafa05f5922b1f\
+        bbb77c6817ccf50748419477e843d5bac67e6a70e97dde5a57e0c983b777e1ad\
+        31a80482dadf89de6302b1988c82c29544c9c07bb910596158f6062517eb089a\
+        2f54c9a0f348752950094d3228d3b940258c75fe2a413cb70baa21dc2e352fc5",
+        "\
+        f775723953ead4a90411a02908fd1a629db584bc600664c609061f221ef6bf7c\
+        440066c8626b49daaa7bf2bcc0b74be4f7a1e3dcf0e869f1542fe821498cbf2d\
+        e73ad398194129f635de4424a07ca715838aefe8fe69d1a391cfa70470795a80\
+        dd056866e6e1125aff94413921880c437c9e2570a28ced7267c8beef7e9b2d8d\
+        1547d76dfcf4bee592f5fefe10ddfb6aeb0991c5b9dbbee6ec80d11b17c0eb1a",
+        "\
+        c5a77e7a90aa14e0bf5f337f06f597148676424fae26e175c6e5621c34351955\
+        289f319789da424845c9eac935245fcddd805950e2f02506d09be7e411199556\
+        d262144475b1fa46ad85250728c600c53dfd10f8b3f4adf140e27241aec3c2da\
+        3a81046703fccf468b48b145f939efdbb96c3786db712b3113bb2488ef286cdc\
+        ef8afe82d200a5bb36b5462166e8ce77f2d831a52ef2135b2af188110beaefb1",
+        "\
+        958b991cfd78f16537fe6d1f4afd10273384db08bdfc843562a22b0626766686\
+        f6aec8247599f40bfe01bec0e0ecf17b4319559022d4d9bf007fe929943004eb\
+        4866760dedf31b7c691f5ce665f8aae0bda895c23595c834fecc2390a5bcc203\
+        b04afcacbb4280713287a2d0c37e23f7513fab898f2c1fefa00ec09a924c335d\
+        9b629f1d4fb71901c3e59611afbfea354d101324e894c788d1c01f00b3c251b2",
+    ];
+
+    /// Order of the curve.
+    const N: [u8; 32] = hex!("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+
+    fn assert_verify(input: Vec<u8>, valid: bool) {
+        let (gas, output) = p256_verify(&input.into(), 3450).unwrap();
+        assert_eq!(gas, P256VERIFY_BASE);
+        if valid {
+            assert_eq!(output, Bytes::from(B256::with_last_byte(1)));
+        } else {
+            assert!(output.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_p256_verify() {
+        let input = Bytes::from(hex::decode(VALID).unwrap());
+        let (gas, output) = p256_verify(&input, 3450).unwrap();
+        assert_eq!(gas, P256VERIFY_BASE);
+        assert_eq!(output, Bytes::from(B256::with_last_byte(1)));
+        assert_eq!(p256_verify(&input, 3449), Err(Error::OutOfGas));
+    }
+
+    #[test]
+    fn test_p256_verify_reference_vectors() {
+        for input in REFERENCE_VALID {
+            assert_verify(hex::decode(input).unwrap(), true);
+        }
+        for input in REFERENCE_WRONG_HASH {
+            assert_verify(hex::decode(input).unwrap(), false);
+        }
+    }
+
+    #[test]
+    fn test_p256_verify_invalid() {
+        let valid = hex::decode(REFERENCE_VALID[0]).unwrap();
+        let with = |range: core::ops::Range<usize>, bytes: &[u8]| {
+            let mut input = valid.clone();
+            input[range].copy_from_slice(bytes);
+            input
+        };
+        let mut wrong_key = valid.clone();
+        wrong_key[159] ^= 1;
+
+        for input in [
+            // r or s is zero.
+            with(32..64, &[0; 32]),
+            with(64..96, &[0; 32]),
+            // r or s is the curve order or above it.
+            with(32..64, &N),
+            with(64..96, &N),
+            with(32..64, &[0xff; 32]),
+            with(64..96, &[0xff; 32]),
+            // public key is not on the curve.
+            wrong_key,
+            with(96..160, &[0xaa; 64]),
+            // public key is the point at infinity.
+            with(96..160, &[0; 64]),
+            // input is not 160 bytes.
+            valid[..159].to_vec(),
+            [&valid[..], &[0]].concat(),
+            vec![],
+        ] {
+            assert_verify(input, false);
+        }
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn test_fjord_precompiles() {
+        use crate::{PrecompileSpecId, Precompiles};
+        use revm_primitives::SpecId;
+
+        let fjord = Precompiles::new(PrecompileSpecId::from_spec_id(SpecId::FJORD));
+        assert!(fjord.contains(&P256VERIFY.0));
+        let ecotone = Precompiles::new(PrecompileSpecId::from_spec_id(SpecId::ECOTONE));
+        assert!(!ecotone.contains(&P256VERIFY.0));
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn test_latest_precompiles_are_fjord() {
+        use crate::{PrecompileSpecId, Precompiles};
+        use revm_primitives::SpecId;
+
+        let latest = Precompiles::new(PrecompileSpecId::from_spec_id(SpecId::LATEST));
+        assert!(latest.contains(&P256VERIFY.0));
+        assert_eq!(
+            latest.addresses().count(),
+            Precompiles::fjord().addresses().count()
+        );
+        // the latest Ethereum set is not changed by the feature.
+        assert!(!Precompiles::latest().contains(&P256VERIFY.0));
+    }
+}
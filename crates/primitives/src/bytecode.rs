@@ -1,3 +1,7 @@
+pub mod eof;
+
+pub use eof::Eof;
+
 use crate::{hex, keccak256, Bytes, B256, KECCAK_EMPTY};
 use bitvec::{
     prelude::{bitvec, Lsb0},
@@ -49,6 +53,8 @@ pub enum BytecodeState {
     Checked { len: usize },
     /// The bytecode has been analyzed for valid jump destinations.
     Analysed { len: usize, jump_map: JumpMap },
+    /// The bytecode is a validated EOF container.
+    Eof(Arc<Eof>),
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -92,7 +98,7 @@ impl Bytecode {
         if self.is_empty() {
             KECCAK_EMPTY
         } else {
-            keccak256(&self.original_bytes())
+            keccak256(self.original_bytes())
         }
     }
 
@@ -105,6 +111,15 @@ impl Bytecode {
         }
     }
 
+    /// Creates a new EOF [`Bytecode`] from the validated container.
+    #[inline]
+    pub fn new_eof(eof: Eof) -> Self {
+        Self {
+            bytecode: eof.raw.clone(),
+            state: BytecodeState::Eof(Arc::new(eof)),
+        }
+    }
+
     /// Create new checked bytecode
     ///
     /// # Safety
@@ -128,7 +143,7 @@ impl Bytecode {
     #[inline]
     pub fn original_bytes(&self) -> Bytes {
         match self.state {
-            BytecodeState::Raw => self.bytecode.clone(),
+            BytecodeState::Raw | BytecodeState::Eof(_) => self.bytecode.clone(),
             BytecodeState::Checked { len } | BytecodeState::Analysed { len, .. } => {
                 self.bytecode.slice(0..len)
             }
@@ -139,7 +154,7 @@ impl Bytecode {
     #[inline]
     pub fn len(&self) -> usize {
         match self.state {
-            BytecodeState::Raw => self.bytecode.len(),
+            BytecodeState::Raw | BytecodeState::Eof(_) => self.bytecode.len(),
             BytecodeState::Checked { len, .. } | BytecodeState::Analysed { len, .. } => len,
         }
     }
@@ -150,6 +165,21 @@ impl Bytecode {
         self.len() == 0
     }
 
+    /// Returns the EOF container if the bytecode is EOF.
+    #[inline]
+    pub fn eof(&self) -> Option<&Arc<Eof>> {
+        match &self.state {
+            BytecodeState::Eof(eof) => Some(eof),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the bytecode is EOF.
+    #[inline]
+    pub fn is_eof(&self) -> bool {
+        matches!(self.state, BytecodeState::Eof(_))
+    }
+
     /// Returns the [`BytecodeState`].
     #[inline]
     pub fn state(&self) -> &BytecodeState {
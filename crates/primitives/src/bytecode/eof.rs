@@ -0,0 +1,410 @@
+//! EVM Object Format (EOF) container.
+//!
+//! See [EIP-3540](https://eips.ethereum.org/EIPS/eip-3540) for the container format, the
+//! contents of the container are validated in the interpreter analysis.
+use crate::{b256, Bytes, B256};
+use core::fmt;
+use std::vec::Vec;
+
+/// EOF magic, the first two bytes of every container.
+pub const EOF_MAGIC_BYTES: [u8; 2] = [0xEF, 0x00];
+/// Hash of the [EOF_MAGIC_BYTES], returned by `EXTCODEHASH` for EOF accounts.
+pub const EOF_MAGIC_HASH: B256 =
+    b256!("9dbf3648db8210552e9c4f75c6a1c3057c0ca432043bd648be15fe7be05646f5");
+/// Version of the container format.
+pub const EOF_VERSION: u8 = 1;
+
+/// Maximum number of the code sections.
+pub const EOF_MAX_CODE_SECTIONS: usize = 1024;
+/// Maximum number of the container sections.
+pub const EOF_MAX_CONTAINER_SECTIONS: usize = 256;
+
+const KIND_TERMINATOR: u8 = 0;
+const KIND_TYPES: u8 = 1;
+const KIND_CODE: u8 = 2;
+const KIND_CONTAINER: u8 = 3;
+const KIND_DATA: u8 = 4;
+
+/// Decoded EOF container.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eof {
+    pub header: EofHeader,
+    pub body: EofBody,
+    /// Encoded container, sections of the body are slices of it.
+    pub raw: Bytes,
+}
+
+/// Header of the container, sizes of all sections.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EofHeader {
+    /// Size of the types section, four bytes for every code section.
+    pub types_size: u16,
+    /// Sizes of the code sections.
+    pub code_sizes: Vec<u16>,
+    /// Sizes of the container sections.
+    pub container_sizes: Vec<u16>,
+    /// Declared size of the data section, data can be shorter before deployment.
+    pub data_size: u16,
+}
+
+impl EofHeader {
+    /// Returns the size of the encoded header.
+    pub fn size(&self) -> usize {
+        let containers = if self.container_sizes.is_empty() {
+            0
+        } else {
+            3 + 2 * self.container_sizes.len()
+        };
+        // magic, version, types, code, containers, data and terminator.
+        2 + 1 + 3 + 3 + 2 * self.code_sizes.len() + containers + 3 + 1
+    }
+
+    /// Returns the size of the body without the data section.
+    pub fn body_size_without_data(&self) -> usize {
+        self.types_size as usize
+            + self.code_sizes.iter().map(|&s| s as usize).sum::<usize>()
+            + self
+                .container_sizes
+                .iter()
+                .map(|&s| s as usize)
+                .sum::<usize>()
+    }
+}
+
+/// Body of the container.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EofBody {
+    pub types_section: Vec<TypesSection>,
+    pub code_section: Vec<Bytes>,
+    pub container_section: Vec<Bytes>,
+    pub data_section: Bytes,
+    /// Whether the data section is as long as declared in the header.
+    pub is_data_filled: bool,
+}
+
+/// Inputs, outputs and maximum stack height of the code section.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypesSection {
+    pub inputs: u8,
+    pub outputs: u8,
+    pub max_stack_size: u16,
+}
+
+impl TypesSection {
+    /// Outputs of the code section that never returns to the caller.
+    pub const NON_RETURNING: u8 = 0x80;
+
+    /// Returns whether the code section returns to the caller with `RETF`.
+    #[inline]
+    pub const fn is_returning(&self) -> bool {
+        self.outputs != Self::NON_RETURNING
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        Self {
+            inputs: bytes[0],
+            outputs: bytes[1],
+            max_stack_size: u16::from_be_bytes([bytes[2], bytes[3]]),
+        }
+    }
+}
+
+/// Errors of the [Eof::decode], the container is not well formed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EofDecodeError {
+    /// Input ends before the end of the header.
+    MissingInput,
+    /// Container does not start with [EOF_MAGIC_BYTES].
+    InvalidMagic,
+    /// Version is not [EOF_VERSION].
+    InvalidVersion,
+    /// Section kind is not in the expected order.
+    InvalidSectionKind { expected: u8, found: u8 },
+    /// Types section size is not four bytes for every code section.
+    InvalidTypesSize,
+    /// Container has no code sections or more than [EOF_MAX_CODE_SECTIONS].
+    InvalidCodeSectionCount,
+    /// Container has no container sections or more than [EOF_MAX_CONTAINER_SECTIONS] in the
+    /// declared container section.
+    InvalidContainerSectionCount,
+    /// Code or container section is empty.
+    ZeroSectionSize,
+    /// Body is shorter than the declared sections.
+    MissingBody,
+    /// Body is longer than the declared sections.
+    DanglingData,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EofDecodeError {}
+
+impl fmt::Display for EofDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingInput => f.write_str("input ends inside the header"),
+            Self::InvalidMagic => f.write_str("invalid magic"),
+            Self::InvalidVersion => f.write_str("invalid version"),
+            Self::InvalidSectionKind { expected, found } => {
+                write!(f, "expected section kind {expected}, found {found}")
+            }
+            Self::InvalidTypesSize => f.write_str("invalid types section size"),
+            Self::InvalidCodeSectionCount => f.write_str("invalid number of code sections"),
+            Self::InvalidContainerSectionCount => {
+                f.write_str("invalid number of container sections")
+            }
+            Self::ZeroSectionSize => f.write_str("empty code or container section"),
+            Self::MissingBody => f.write_str("body is shorter than the header declares"),
+            Self::DanglingData => f.write_str("body is longer than the header declares"),
+        }
+    }
+}
+
+/// Reads the header, every read checks the remaining length.
+struct HeaderReader<'a> {
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl HeaderReader<'_> {
+    fn u8(&mut self) -> Result<u8, EofDecodeError> {
+        let byte = *self
+            .input
+            .get(self.offset)
+            .ok_or(EofDecodeError::MissingInput)?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    fn u16(&mut self) -> Result<u16, EofDecodeError> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn kind(&mut self, expected: u8) -> Result<(), EofDecodeError> {
+        let found = self.u8()?;
+        if found != expected {
+            return Err(EofDecodeError::InvalidSectionKind { expected, found });
+        }
+        Ok(())
+    }
+
+    /// Reads the number of sections and their sizes, none of them can be empty.
+    fn sizes(&mut self, max: usize, error: EofDecodeError) -> Result<Vec<u16>, EofDecodeError> {
+        let count = self.u16()? as usize;
+        if count == 0 || count > max {
+            return Err(error);
+        }
+        let mut sizes = Vec::with_capacity(count);
+        for _ in 0..count {
+            let size = self.u16()?;
+            if size == 0 {
+                return Err(EofDecodeError::ZeroSectionSize);
+            }
+            sizes.push(size);
+        }
+        Ok(sizes)
+    }
+}
+
+impl Eof {
+    /// Decodes the container, the code sections are not validated.
+    ///
+    /// Data section can be shorter than declared, see [EofBody::is_data_filled].
+    pub fn decode(raw: Bytes) -> Result<Self, EofDecodeError> {
+        let mut reader = HeaderReader {
+            input: &raw,
+            offset: 0,
+        };
+        if [reader.u8()?, reader.u8()?] != EOF_MAGIC_BYTES {
+            return Err(EofDecodeError::InvalidMagic);
+        }
+        if reader.u8()? != EOF_VERSION {
+            return Err(EofDecodeError::InvalidVersion);
+        }
+
+        reader.kind(KIND_TYPES)?;
+        let types_size = reader.u16()?;
+        reader.kind(KIND_CODE)?;
+        let code_sizes = reader.sizes(
+            EOF_MAX_CODE_SECTIONS,
+            EofDecodeError::InvalidCodeSectionCount,
+        )?;
+        if types_size as usize != code_sizes.len() * 4 {
+            return Err(EofDecodeError::InvalidTypesSize);
+        }
+        let mut kind = reader.u8()?;
+        let mut container_sizes = Vec::new();
+        if kind == KIND_CONTAINER {
+            container_sizes = reader.sizes(
+                EOF_MAX_CONTAINER_SECTIONS,
+                EofDecodeError::InvalidContainerSectionCount,
+            )?;
+            kind = reader.u8()?;
+        }
+        if kind != KIND_DATA {
+            return Err(EofDecodeError::InvalidSectionKind {
+                expected: KIND_DATA,
+                found: kind,
+            });
+        }
+        let data_size = reader.u16()?;
+        reader.kind(KIND_TERMINATOR)?;
+
+        let header = EofHeader {
+            types_size,
+            code_sizes,
+            container_sizes,
+            data_size,
+        };
+        let mut offset = header.size();
+        let data_end = offset + header.body_size_without_data() + data_size as usize;
+        if raw.len() > data_end {
+            return Err(EofDecodeError::DanglingData);
+        }
+        if raw.len() < data_end - data_size as usize {
+            return Err(EofDecodeError::MissingBody);
+        }
+
+        let types_section = raw[offset..offset + types_size as usize]
+            .chunks_exact(4)
+            .map(TypesSection::decode)
+            .collect();
+        offset += types_size as usize;
+        let mut sections = |sizes: &[u16]| {
+            sizes
+                .iter()
+                .map(|&size| {
+                    let section = raw.slice(offset..offset + size as usize);
+                    offset += size as usize;
+                    section
+                })
+                .collect::<Vec<_>>()
+        };
+        let code_section = sections(&header.code_sizes);
+        let container_section = sections(&header.container_sizes);
+        let body = EofBody {
+            types_section,
+            code_section,
+            container_section,
+            data_section: raw.slice(offset..),
+            is_data_filled: raw.len() == data_end,
+        };
+
+        Ok(Self { header, body, raw })
+    }
+
+    /// Returns the size of the encoded container.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns the data section.
+    #[inline]
+    pub fn data(&self) -> &Bytes {
+        &self.body.data_section
+    }
+
+    /// Returns the container with the auxiliary data appended to the data section, the header
+    /// declares the new data size.
+    ///
+    /// Returns `None` if the data section is still shorter than declared or longer than
+    /// `u16::MAX`.
+    pub fn with_aux_data(&self, aux_data: &[u8]) -> Option<Self> {
+        let data_size = self.body.data_section.len() + aux_data.len();
+        if data_size < self.header.data_size as usize {
+            return None;
+        }
+        let data_size = u16::try_from(data_size).ok()?;
+        let mut raw = Vec::with_capacity(self.raw.len() + aux_data.len());
+        raw.extend_from_slice(&self.raw);
+        raw.extend_from_slice(aux_data);
+        // data size is followed by the terminator.
+        let offset = self.header.size() - 3;
+        raw[offset..offset + 2].copy_from_slice(&data_size.to_be_bytes());
+        Some(Self::decode(raw.into()).expect("only the data size changed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bytes, keccak256};
+
+    #[test]
+    fn decode_eof() {
+        assert_eq!(keccak256(EOF_MAGIC_BYTES), EOF_MAGIC_HASH);
+
+        // one code section with `STOP` and two bytes of data.
+        let raw = bytes!(
+            "ef000101000402000100010300010014040002000080000000"
+            "ef000101000402000100010400000000800000fe"
+            "aabb"
+        );
+        let eof = Eof::decode(raw.clone()).unwrap();
+        assert_eq!(eof.header.code_sizes, [1]);
+        assert_eq!(eof.header.container_sizes, [20]);
+        assert_eq!(eof.header.size(), 20);
+        assert_eq!(
+            eof.body.types_section,
+            [TypesSection {
+                inputs: 0,
+                outputs: TypesSection::NON_RETURNING,
+                max_stack_size: 0
+            }]
+        );
+        assert_eq!(eof.body.code_section, [bytes!("00")]);
+        assert_eq!(eof.body.container_section[0].len(), 20);
+        assert_eq!(eof.data(), &bytes!("aabb"));
+        assert!(eof.body.is_data_filled);
+
+        let truncated = Eof::decode(raw.slice(..raw.len() - 1)).unwrap();
+        assert!(!truncated.body.is_data_filled);
+
+        let mut dangling = raw.to_vec();
+        dangling.push(0);
+        assert_eq!(
+            Eof::decode(dangling.into()),
+            Err(EofDecodeError::DanglingData)
+        );
+        assert_eq!(
+            Eof::decode(raw.slice(..raw.len() - 3)),
+            Err(EofDecodeError::MissingBody)
+        );
+        assert_eq!(
+            Eof::decode(bytes!("ef0002")),
+            Err(EofDecodeError::InvalidVersion)
+        );
+        assert_eq!(
+            Eof::decode(bytes!("ef0001010008020001")),
+            Err(EofDecodeError::MissingInput)
+        );
+        assert_eq!(
+            Eof::decode(bytes!("ef000101000802000100010400000000800000")),
+            Err(EofDecodeError::InvalidTypesSize)
+        );
+        assert_eq!(
+            Eof::decode(bytes!("ef000101000402000100000400000000800000")),
+            Err(EofDecodeError::ZeroSectionSize)
+        );
+    }
+
+    #[test]
+    fn with_aux_data() {
+        // `STOP` with one of the two declared data bytes.
+        let eof = Eof::decode(bytes!("ef000101000402000100010400020000800000fe aa")).unwrap();
+        assert!(!eof.body.is_data_filled);
+        assert_eq!(eof.with_aux_data(&[]), None);
+
+        let filled = eof.with_aux_data(&[0xbb, 0xcc]).unwrap();
+        assert_eq!(filled.header.data_size, 3);
+        assert_eq!(filled.data(), &bytes!("aabbcc"));
+        assert!(filled.body.is_data_filled);
+
+        assert_eq!(eof.with_aux_data(&[0; u16::MAX as usize]), None);
+    }
+}
@@ -4,14 +4,33 @@ use crate::Address;
 /// By default limit is 0x6000 (~25kb)
 pub const MAX_CODE_SIZE: usize = 0x6000;
 
+/// EVM call stack limit.
+pub const CALL_STACK_LIMIT: u64 = 1024;
+
 /// Number of block hashes that EVM can access in the past
 pub const BLOCK_HASH_HISTORY: usize = 256;
 
+/// EIP-2935: Serve historical block hashes from state
+///
+/// Number of block hashes the history storage contract keeps in its ring buffer.
+pub const BLOCKHASH_SERVE_WINDOW: usize = 8191;
+
+/// EIP-2935: Serve historical block hashes from state
+///
+/// History storage contract, the hash of block `n` is stored in the slot `n % BLOCKHASH_SERVE_WINDOW`.
+pub const BLOCKHASH_STORAGE_ADDRESS: Address =
+    crate::address!("0000F90827F1C53a10cb7A02335B175320002935");
+
 /// EIP-3860: Limit and meter initcode
 ///
 /// Limit of maximum initcode size is 2 * MAX_CODE_SIZE
 pub const MAX_INITCODE_SIZE: usize = 2 * MAX_CODE_SIZE;
 
+/// EIP-7620: EOF - Contract Creation
+///
+/// Maximum number of the EOF initcodes of the transaction, see [`crate::TxEnv::eof_initcodes`].
+pub const MAX_INITCODE_COUNT: usize = 256;
+
 /// Precompile 3 is special in few places
 pub const PRECOMPILE3: Address =
     Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
@@ -32,3 +51,15 @@ pub const MIN_BLOB_GASPRICE: u64 = 1;
 pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3338477;
 /// First version of the blob.
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// EIP-4788: Beacon block root in the EVM
+///
+/// Beacon roots contract, the root of the parent beacon block is stored before the first
+/// transaction of the block.
+pub const BEACON_ROOTS_ADDRESS: Address =
+    crate::address!("000F3df6D732807Ef1319fB7B8bB8522d0Beac02");
+
+/// Caller of the system contracts, e.g. the EIP-4788 beacon roots contract.
+pub const SYSTEM_ADDRESS: Address = crate::address!("fffffffffffffffffffffffffffffffffffffffe");
+/// Gas limit of the call to the system contract.
+pub const SYSTEM_CALL_GAS_LIMIT: u64 = 30_000_000;
@@ -1,11 +1,13 @@
+pub mod chain_config;
 pub mod handler_cfg;
 
+pub use chain_config::{ChainConfig, RefundPolicy, SelfDestructToSelf};
 pub use handler_cfg::{CfgEnvWithHandlerCfg, EnvWithHandlerCfg, HandlerCfg};
 
 use crate::{
-    calc_blob_gasprice, Account, Address, Bytes, InvalidHeader, InvalidTransaction, Spec, SpecId,
-    B256, GAS_PER_BLOB, KECCAK_EMPTY, MAX_BLOB_NUMBER_PER_BLOCK, MAX_INITCODE_SIZE, U256,
-    VERSIONED_HASH_VERSION_KZG,
+    calc_blob_gasprice, keccak256, Account, Address, Bytes, InvalidHeader, InvalidTransaction,
+    Spec, SpecId, B256, GAS_PER_BLOB, KECCAK_EMPTY, MAX_BLOB_NUMBER_PER_BLOCK, MAX_CODE_SIZE,
+    MAX_INITCODE_COUNT, MAX_INITCODE_SIZE, U256, VERSIONED_HASH_VERSION_KZG,
 };
 use core::cmp::{min, Ordering};
 use std::boxed::Box;
@@ -115,15 +117,15 @@ impl Env {
         }
 
         // EIP-3860: Limit and meter initcode
-        if SPEC::enabled(SpecId::SHANGHAI) && self.tx.transact_to.is_create() {
-            let max_initcode_size = self
+        if SPEC::enabled(SpecId::SHANGHAI)
+            && self.tx.transact_to.is_create()
+            && self
                 .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if self.tx.data.len() > max_initcode_size {
-                return Err(InvalidTransaction::CreateInitCodeSizeLimit);
-            }
+                .chain
+                .max_initcode_size
+                .is_some_and(|limit| self.tx.data.len() > limit)
+        {
+            return Err(InvalidTransaction::CreateInitCodeSizeLimit);
         }
 
         // Check if the transaction's chain id is correct
@@ -184,6 +186,25 @@ impl Env {
             }
         }
 
+        // EIP-7620: EOF - Contract Creation
+        if !self.tx.eof_initcodes.is_empty() {
+            if !SPEC::enabled(SpecId::PRAGUE_EOF) {
+                return Err(InvalidTransaction::EofInitcodesNotSupported);
+            }
+            if self.tx.eof_initcodes.len() > MAX_INITCODE_COUNT {
+                return Err(InvalidTransaction::EofInitcodesNumberLimit);
+            }
+            let max_initcode_size = self.cfg.chain.max_initcode_size.unwrap_or(usize::MAX);
+            if self
+                .tx
+                .eof_initcodes
+                .iter()
+                .any(|initcode| initcode.is_empty() || initcode.len() > max_initcode_size)
+            {
+                return Err(InvalidTransaction::EofInitcodesSizeLimit);
+            }
+        }
+
         Ok(())
     }
 
@@ -262,9 +283,10 @@ pub struct CfgEnv {
     ///
     /// Default: Analyse
     pub perf_analyse_created_bytecodes: AnalysisKind,
-    /// If some it will effects EIP-170: Contract code size limit. Useful to increase this because of tests.
-    /// By default it is 0x6000 (~25kb).
-    pub limit_contract_code_size: Option<usize>,
+    /// Chainwide rules like the contract code size limit, creation costs, refunds and call depth.
+    ///
+    /// Default: Ethereum mainnet rules
+    pub chain: ChainConfig,
     /// A hard memory limit in bytes beyond which [crate::result::OutOfGasError::Memory] cannot be resized.
     ///
     /// In cases where the gas limit may be extraordinarily high, it is recommended to set this to
@@ -302,6 +324,18 @@ pub struct CfgEnv {
 }
 
 impl CfgEnv {
+    /// Sets the EIP-170 contract code size limit and the EIP-3860 initcode size limit to twice
+    /// of it, as the removed `limit_contract_code_size` field did. `None` sets the mainnet
+    /// limits.
+    pub fn set_limit_contract_code_size(&mut self, limit: Option<usize>) {
+        self.chain.max_code_size = Some(limit.unwrap_or(MAX_CODE_SIZE));
+        self.chain.max_initcode_size = Some(
+            limit
+                .map(|limit| limit.saturating_mul(2))
+                .unwrap_or(MAX_INITCODE_SIZE),
+        );
+    }
+
     #[cfg(feature = "optional_eip3607")]
     pub fn is_eip3607_disabled(&self) -> bool {
         self.disable_eip3607
@@ -368,7 +402,7 @@ impl Default for CfgEnv {
         Self {
             chain_id: 1,
             perf_analyse_created_bytecodes: AnalysisKind::default(),
-            limit_contract_code_size: None,
+            chain: ChainConfig::default(),
             #[cfg(feature = "c-kzg")]
             kzg_settings: crate::kzg::EnvKzgSettings::Default,
             #[cfg(feature = "memory_limit")]
@@ -537,6 +571,13 @@ pub struct TxEnv {
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
     pub max_fee_per_blob_gas: Option<U256>,
 
+    /// EOF initcontainers of the transaction that `TXCREATE` can deploy by their hash.
+    ///
+    /// Incorporated as part of the Prague EOF upgrade via [EIP-7620].
+    ///
+    /// [EIP-7620]: https://eips.ethereum.org/EIPS/eip-7620
+    pub eof_initcodes: Vec<Bytes>,
+
     #[cfg_attr(feature = "serde", serde(flatten))]
     #[cfg(feature = "optimism")]
     pub optimism: OptimismFields,
@@ -551,6 +592,14 @@ impl TxEnv {
         GAS_PER_BLOB * self.blob_hashes.len() as u64
     }
 
+    /// Returns the EOF initcode with the given hash, see [`Self::eof_initcodes`].
+    #[inline]
+    pub fn eof_initcode(&self, hash: B256) -> Option<&Bytes> {
+        self.eof_initcodes
+            .iter()
+            .find(|initcode| keccak256(initcode) == hash)
+    }
+
     /// Clears environment and resets fields to default values.
     #[inline]
     pub fn clear(&mut self) {
@@ -573,6 +622,7 @@ impl Default for TxEnv {
             access_list: Vec::new(),
             blob_hashes: Vec::new(),
             max_fee_per_blob_gas: None,
+            eof_initcodes: Vec::new(),
             #[cfg(feature = "optimism")]
             optimism: OptimismFields::default(),
         }
@@ -691,6 +741,13 @@ pub enum CreateScheme {
         /// Salt.
         salt: U256,
     },
+    /// EIP-7620: `EOFCREATE` and `TXCREATE` of the EOF initcontainer.
+    ///
+    /// Address is derived like `CREATE2`, the initcontainer gets the input as call data.
+    EofCreate {
+        /// Salt.
+        salt: U256,
+    },
 }
 
 /// What bytecode analysis to perform.
@@ -730,4 +787,15 @@ mod tests {
             Err(InvalidTransaction::AccessListNotSupported)
         );
     }
+
+    #[test]
+    fn test_set_limit_contract_code_size() {
+        let mut cfg = CfgEnv::default();
+        cfg.set_limit_contract_code_size(Some(0x100));
+        assert_eq!(cfg.chain.max_code_size, Some(0x100));
+        assert_eq!(cfg.chain.max_initcode_size, Some(0x200));
+
+        cfg.set_limit_contract_code_size(None);
+        assert_eq!(cfg.chain, ChainConfig::default());
+    }
 }
@@ -0,0 +1,201 @@
+use crate::{SpecId, CALL_STACK_LIMIT, MAX_CODE_SIZE, MAX_INITCODE_SIZE};
+
+/// Chainwide rules that differ between Ethereum mainnet and other chains.
+///
+/// The defaults follow Ethereum mainnet. Rules that are not listed here follow the spec id.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChainConfig {
+    /// EIP-170: Contract code size limit, `None` disables the limit.
+    ///
+    /// Default: 0x6000 (~25kb)
+    pub max_code_size: Option<usize>,
+    /// EIP-3860: Limit and meter initcode, `None` disables the limit but initcode is still metered.
+    ///
+    /// It applies to the create transactions, `CREATE`, `CREATE2` and the EOF initcodes of the
+    /// transaction.
+    ///
+    /// Default: 0xC000 (~49kb)
+    pub max_initcode_size: Option<usize>,
+    /// Base gas cost of the contract creation with `CREATE`, `CREATE2`, the EOF creates and
+    /// the create transaction.
+    ///
+    /// Default: 32000
+    pub create_gas: u64,
+    /// Gas cost of every byte of the deployed contract code.
+    ///
+    /// Default: 200
+    pub code_deposit_gas: u64,
+    /// Refund rules of `SSTORE`, `SELFDESTRUCT` and the maximum refund.
+    ///
+    /// If `None` it follows the spec, see [RefundPolicy::from_spec_id].
+    pub refund_policy: Option<RefundPolicy>,
+    /// Maximum refund is the gas spent divided by this quotient.
+    ///
+    /// If `None` it is given by the refund policy.
+    pub max_refund_quotient: Option<u64>,
+    /// EIP-150: Sub calls and creates get all but the remaining gas divided by this divisor,
+    /// `None` forwards all the remaining gas.
+    ///
+    /// It has no effect before Tangerine Whistle.
+    ///
+    /// Default: 64
+    pub retained_gas_divisor: Option<u64>,
+    /// Maximum depth of the call stack.
+    ///
+    /// Default: 1024
+    pub call_depth_limit: usize,
+    /// Disables EIP-161 state clearing of the touched empty accounts for chains that never
+    /// adopted it, the account existence is then the same as before Spurious Dragon.
+    ///
+    /// Database that clears the accounts on commit needs to be configured separately.
+    ///
+    /// Default: false
+    pub disable_state_clearing: bool,
+    /// What happens to the balance when `SELFDESTRUCT` targets the destroyed account itself.
+    ///
+    /// Default: burned, as in geth
+    pub selfdestruct_to_self: SelfDestructToSelf,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            max_code_size: Some(MAX_CODE_SIZE),
+            max_initcode_size: Some(MAX_INITCODE_SIZE),
+            create_gas: 32000,
+            code_deposit_gas: 200,
+            refund_policy: None,
+            max_refund_quotient: None,
+            retained_gas_divisor: Some(64),
+            call_depth_limit: CALL_STACK_LIMIT as usize,
+            disable_state_clearing: false,
+            selfdestruct_to_self: SelfDestructToSelf::Burn,
+        }
+    }
+}
+
+impl ChainConfig {
+    /// Returns the refund policy of the spec, unless it is overridden.
+    #[inline]
+    pub fn refund_policy_for(&self, spec_id: SpecId) -> RefundPolicy {
+        self.refund_policy
+            .unwrap_or_else(|| RefundPolicy::from_spec_id(spec_id))
+    }
+
+    /// Returns the gas that can be forwarded to the sub call or create out of the `remaining` gas.
+    ///
+    /// Divisor of zero is the same as `None`, see [ChainConfig::retained_gas_divisor].
+    #[inline]
+    pub fn max_forwarded_gas(&self, remaining: u64) -> u64 {
+        let retained = self
+            .retained_gas_divisor
+            .and_then(|divisor| remaining.checked_div(divisor))
+            .unwrap_or_default();
+        remaining - retained
+    }
+
+    /// Returns the maximum refund quotient of the spec, unless it is overridden.
+    #[inline]
+    pub fn refund_quotient_for(&self, spec_id: SpecId) -> u64 {
+        self.max_refund_quotient
+            .unwrap_or_else(|| self.refund_policy_for(spec_id).max_refund_quotient())
+    }
+}
+
+/// Gas refund rules, chains can keep the old refunds after adopting later hardforks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefundPolicy {
+    /// Refunds before London, clearing the storage slot refunds 15000 gas, `SELFDESTRUCT`
+    /// refunds 24000 gas and the refund is up to the half of the gas spent.
+    PreLondon,
+    /// EIP-3529: Reduction in refunds, clearing the storage slot refunds 4800 gas, `SELFDESTRUCT`
+    /// has no refund and the refund is up to the fifth of the gas spent.
+    #[default]
+    Eip3529,
+}
+
+impl RefundPolicy {
+    /// Returns the refund policy of the spec, EIP-3529 is enabled in London.
+    #[inline]
+    pub fn from_spec_id(spec_id: SpecId) -> Self {
+        if spec_id.is_enabled_in(SpecId::LONDON) {
+            Self::Eip3529
+        } else {
+            Self::PreLondon
+        }
+    }
+
+    /// Returns `true` if EIP-3529 refunds are used.
+    #[inline]
+    pub const fn is_eip3529(&self) -> bool {
+        matches!(self, Self::Eip3529)
+    }
+
+    /// Maximum refund is the gas spent divided by this quotient.
+    #[inline]
+    pub const fn max_refund_quotient(&self) -> u64 {
+        match self {
+            Self::PreLondon => 2,
+            Self::Eip3529 => 5,
+        }
+    }
+}
+
+/// Balance policy of `SELFDESTRUCT` that targets the destroyed account itself.
+///
+/// It only applies when the account would be destroyed, after Cancun (EIP-6780) selfdestruct
+/// of an account that was not created in the same transaction never changes the balance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfDestructToSelf {
+    /// Account is destroyed and its balance is burned, as it is done in geth.
+    #[default]
+    Burn,
+    /// Account is not destroyed and keeps its balance, selfdestruct to itself does not
+    /// change the state.
+    Keep,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refund_policy_follows_spec() {
+        let mut chain = ChainConfig::default();
+        assert_eq!(
+            chain.refund_policy_for(SpecId::BERLIN),
+            RefundPolicy::PreLondon
+        );
+        assert_eq!(
+            chain.refund_policy_for(SpecId::LONDON),
+            RefundPolicy::Eip3529
+        );
+        assert_eq!(chain.refund_quotient_for(SpecId::BERLIN), 2);
+
+        chain.refund_policy = Some(RefundPolicy::PreLondon);
+        assert_eq!(
+            chain.refund_policy_for(SpecId::CANCUN),
+            RefundPolicy::PreLondon
+        );
+        assert_eq!(chain.refund_quotient_for(SpecId::CANCUN), 2);
+
+        chain.max_refund_quotient = Some(10);
+        assert_eq!(chain.refund_quotient_for(SpecId::CANCUN), 10);
+    }
+
+    #[test]
+    fn max_forwarded_gas() {
+        let mut chain = ChainConfig::default();
+        assert_eq!(chain.max_forwarded_gas(6400), 6300);
+        chain.retained_gas_divisor = Some(2);
+        assert_eq!(chain.max_forwarded_gas(6400), 3200);
+        for divisor in [None, Some(0)] {
+            chain.retained_gas_divisor = divisor;
+            assert_eq!(chain.max_forwarded_gas(6400), 6400);
+        }
+    }
+}
@@ -16,13 +16,14 @@ pub mod env;
 #[cfg(feature = "c-kzg")]
 pub mod kzg;
 pub mod precompile;
+pub mod requests;
 pub mod result;
 pub mod specification;
 pub mod state;
 pub mod utilities;
 pub use alloy_primitives::{
-    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bytes,
-    FixedBytes, Log, LogData, B256, I256, U256,
+    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bloom,
+    BloomInput, Bytes, FixedBytes, Log, LogData, B256, I256, U256,
 };
 pub use bitvec;
 pub use bytecode::*;
@@ -41,6 +42,7 @@ cfg_if::cfg_if! {
 #[cfg(feature = "c-kzg")]
 pub use kzg::{EnvKzgSettings, KzgSettings};
 pub use precompile::*;
+pub use requests::*;
 pub use result::*;
 pub use specification::*;
 pub use state::*;
@@ -1,7 +1,7 @@
 use crate::{Bytes, Env};
 use core::fmt;
 use dyn_clone::DynClone;
-use std::{boxed::Box, sync::Arc};
+use std::{boxed::Box, string::String, sync::Arc};
 
 /// A precompile operation result.
 ///
@@ -125,6 +125,26 @@ pub enum PrecompileError {
     BlobMismatchedVersion,
     /// The proof verification failed.
     BlobVerifyKzgProofFailed,
+    /// Input is longer than the configured limit of the precompile.
+    InputTooLong,
+    /// Invalid input of a custom precompile. Call fails and consumes all gas, same as the
+    /// other input errors.
+    Other(String),
+    /// Failure of the precompile backend that is not caused by the input, e.g. missing
+    /// trusted setup. Transaction is aborted with [EVMError::Precompile](crate::EVMError::Precompile).
+    Fatal(String),
+}
+
+impl PrecompileError {
+    /// Returns true if the error is out of gas.
+    pub fn is_oog(&self) -> bool {
+        matches!(self, Self::OutOfGas)
+    }
+
+    /// Returns true if the error is fatal and aborts the transaction.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Fatal(_))
+    }
 }
 
 #[cfg(feature = "std")]
@@ -153,6 +173,9 @@ impl fmt::Display for PrecompileError {
             PrecompileError::BlobVerifyKzgProofFailed => {
                 write!(f, "verifying blob kzg proof failed")
             }
+            PrecompileError::InputTooLong => write!(f, "precompile input too long"),
+            PrecompileError::Other(msg) => write!(f, "{msg}"),
+            PrecompileError::Fatal(msg) => write!(f, "fatal precompile error: {msg}"),
         }
     }
 }
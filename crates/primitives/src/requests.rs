@@ -0,0 +1,150 @@
+//! Execution layer requests that are dequeued from the system contracts at the end of the block.
+//!
+//! See [EIP-7002](https://eips.ethereum.org/EIPS/eip-7002) and
+//! [EIP-7251](https://eips.ethereum.org/EIPS/eip-7251).
+use crate::{address, Address, FixedBytes};
+use std::vec::Vec;
+
+/// EIP-7002 withdrawal requests contract.
+pub const WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS: Address =
+    address!("00000961Ef480Eb55e80D19ad83579A64c007002");
+/// EIP-7251 consolidation requests contract.
+pub const CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS: Address =
+    address!("0000BBdDc7CE488642fb579F8B00f3a590007251");
+
+/// EIP-7002 withdrawal request of the validator, triggered from its withdrawal credentials.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawalRequest {
+    /// Address that requested the withdrawal.
+    pub source_address: Address,
+    /// Public key of the validator.
+    pub validator_pubkey: FixedBytes<48>,
+    /// Amount in gwei, zero requests the full exit of the validator.
+    pub amount: u64,
+}
+
+impl WithdrawalRequest {
+    /// Size of the encoded request.
+    pub const SIZE: usize = 76;
+
+    /// Decodes the request from `source_address ++ validator_pubkey ++ amount`, amount is
+    /// big endian.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        Some(Self {
+            source_address: Address::from_slice(&bytes[..20]),
+            validator_pubkey: FixedBytes::from_slice(&bytes[20..68]),
+            amount: u64::from_be_bytes(bytes[68..].try_into().unwrap()),
+        })
+    }
+}
+
+/// EIP-7251 consolidation request, moves the balance of the source validator to the target.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsolidationRequest {
+    /// Address that requested the consolidation.
+    pub source_address: Address,
+    /// Public key of the source validator.
+    pub source_pubkey: FixedBytes<48>,
+    /// Public key of the target validator.
+    pub target_pubkey: FixedBytes<48>,
+}
+
+impl ConsolidationRequest {
+    /// Size of the encoded request.
+    pub const SIZE: usize = 116;
+
+    /// Decodes the request from `source_address ++ source_pubkey ++ target_pubkey`.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        Some(Self {
+            source_address: Address::from_slice(&bytes[..20]),
+            source_pubkey: FixedBytes::from_slice(&bytes[20..68]),
+            target_pubkey: FixedBytes::from_slice(&bytes[68..]),
+        })
+    }
+}
+
+/// Requests of the block, in the order they were dequeued.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Requests {
+    /// Withdrawal requests.
+    pub withdrawals: Vec<WithdrawalRequest>,
+    /// Consolidation requests.
+    pub consolidations: Vec<ConsolidationRequest>,
+}
+
+impl Requests {
+    /// Decodes the outputs of the withdrawal and consolidation request contracts, which are
+    /// the concatenated requests.
+    ///
+    /// Returns `None` if an output is not a whole number of requests.
+    pub fn decode(withdrawals: &[u8], consolidations: &[u8]) -> Option<Self> {
+        Some(Self {
+            withdrawals: decode_all(
+                withdrawals,
+                WithdrawalRequest::SIZE,
+                WithdrawalRequest::decode,
+            )?,
+            consolidations: decode_all(
+                consolidations,
+                ConsolidationRequest::SIZE,
+                ConsolidationRequest::decode,
+            )?,
+        })
+    }
+
+    /// Returns true if there are no requests.
+    pub fn is_empty(&self) -> bool {
+        self.withdrawals.is_empty() && self.consolidations.is_empty()
+    }
+}
+
+fn decode_all<T>(bytes: &[u8], size: usize, decode: fn(&[u8]) -> Option<T>) -> Option<Vec<T>> {
+    if !bytes.len().is_multiple_of(size) {
+        return None;
+    }
+    bytes.chunks_exact(size).map(decode).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn decode_requests() {
+        let withdrawal = [[0x11; 20].as_slice(), &[0x22; 48], &1u64.to_be_bytes()].concat();
+        let consolidation = [[0x33; 20].as_slice(), &[0x44; 48], &[0x55; 48]].concat();
+        let requests = Requests::decode(&withdrawal.repeat(2), &consolidation).unwrap();
+
+        assert_eq!(requests.withdrawals.len(), 2);
+        assert_eq!(
+            requests.withdrawals[0],
+            WithdrawalRequest {
+                source_address: Address::repeat_byte(0x11),
+                validator_pubkey: FixedBytes::repeat_byte(0x22),
+                amount: 1,
+            }
+        );
+        assert_eq!(
+            requests.consolidations,
+            [ConsolidationRequest {
+                source_address: Address::repeat_byte(0x33),
+                source_pubkey: FixedBytes::repeat_byte(0x44),
+                target_pubkey: FixedBytes::repeat_byte(0x55),
+            }]
+        );
+
+        assert!(Requests::decode(&[], &[]).unwrap().is_empty());
+        assert_eq!(Requests::decode(&withdrawal[1..], &[]), None);
+        assert_eq!(Requests::decode(&hex!("00"), &consolidation), None);
+    }
+}
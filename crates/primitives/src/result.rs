@@ -135,10 +135,14 @@ pub enum EVMError<DBError> {
     Header(InvalidHeader),
     /// Database error.
     Database(DBError),
+    /// Journaled state error.
+    Journal(JournalError),
     /// Custom error.
     ///
     /// Useful for handler registers where custom logic would want to return their own custom error.
     Custom(String),
+    /// Fatal precompile error, see [PrecompileError::Fatal](crate::PrecompileError::Fatal).
+    Precompile(String),
 }
 
 #[cfg(feature = "std")]
@@ -150,7 +154,9 @@ impl<DBError: fmt::Display> fmt::Display for EVMError<DBError> {
             EVMError::Transaction(e) => write!(f, "Transaction error: {e:?}"),
             EVMError::Header(e) => write!(f, "Header error: {e:?}"),
             EVMError::Database(e) => write!(f, "Database error: {e}"),
+            EVMError::Journal(e) => write!(f, "Journal error: {e}"),
             EVMError::Custom(e) => write!(f, "Custom error: {e}"),
+            EVMError::Precompile(e) => write!(f, "Precompile error: {e}"),
         }
     }
 }
@@ -221,6 +227,12 @@ pub enum InvalidTransaction {
     TooManyBlobs,
     /// Blob transaction contains a versioned hash with an incorrect version
     BlobVersionNotSupported,
+    /// EOF initcodes are not supported for blocks before the Prague EOF hardfork.
+    EofInitcodesNotSupported,
+    /// Transaction has more than [`crate::MAX_INITCODE_COUNT`] EOF initcodes.
+    EofInitcodesNumberLimit,
+    /// EOF initcode is empty or larger than [`crate::ChainConfig::max_initcode_size`].
+    EofInitcodesSizeLimit,
     /// System transactions are not supported post-regolith hardfork.
     ///
     /// Before the Regolith hardfork, there was a special field in the `Deposit` transaction
@@ -311,6 +323,13 @@ impl fmt::Display for InvalidTransaction {
             InvalidTransaction::BlobCreateTransaction => write!(f, "Blob create transaction"),
             InvalidTransaction::TooManyBlobs => write!(f, "Too many blobs"),
             InvalidTransaction::BlobVersionNotSupported => write!(f, "Blob version not supported"),
+            InvalidTransaction::EofInitcodesNotSupported => {
+                write!(f, "EOF initcodes not supported")
+            }
+            InvalidTransaction::EofInitcodesNumberLimit => write!(f, "Too many EOF initcodes"),
+            InvalidTransaction::EofInitcodesSizeLimit => {
+                write!(f, "EOF initcode is empty or exceeds the size limit")
+            }
             #[cfg(feature = "optimism")]
             InvalidTransaction::DepositSystemTxPostRegolith => {
                 write!(
@@ -335,6 +354,69 @@ impl<DBError> From<InvalidHeader> for EVMError<DBError> {
     }
 }
 
+impl<DBError> From<JournalError> for EVMError<DBError> {
+    fn from(error: JournalError) -> Self {
+        EVMError::Journal(error)
+    }
+}
+
+/// Errors of the journaled state, returned when the state is used before it is loaded or
+/// when changes that can still be reverted are taken out of it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JournalError {
+    /// Account is not loaded into the journaled state.
+    AccountNotLoaded(Address),
+    /// Storage slot of the account is not loaded into the journaled state.
+    StorageNotLoaded { address: Address, key: U256 },
+    /// Journal has checkpoints that are not committed or reverted.
+    UncommittedCheckpoint { depth: usize },
+    /// Account has changes in the journal that can still be reverted to a snapshot.
+    AccountJournaled(Address),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JournalError {}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::AccountNotLoaded(address) => {
+                write!(f, "account {address} is not loaded")
+            }
+            JournalError::StorageNotLoaded { address, key } => {
+                write!(f, "storage slot {key} of account {address} is not loaded")
+            }
+            JournalError::UncommittedCheckpoint { depth } => {
+                write!(f, "journal has uncommitted checkpoints at depth {depth}")
+            }
+            JournalError::AccountJournaled(address) => {
+                write!(
+                    f,
+                    "account {address} has changes that can be reverted to a snapshot"
+                )
+            }
+        }
+    }
+}
+
+/// Checkpoint can't be made as the call depth would exceed the limit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallDepthExceeded {
+    /// Configured call depth limit.
+    pub limit: usize,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CallDepthExceeded {}
+
+impl fmt::Display for CallDepthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "call depth limit of {} exceeded", self.limit)
+    }
+}
+
 /// Errors related to misconfiguration of a [`crate::env::BlockEnv`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -364,6 +446,8 @@ pub enum SuccessReason {
     Stop,
     Return,
     SelfDestruct,
+    /// EIP-7620: EOF initcode returned the container to deploy with `RETURNCONTRACT`.
+    EofReturnContract,
 }
 
 /// Indicates that the EVM has experienced an exceptional halt. This causes execution to
@@ -388,6 +472,16 @@ pub enum HaltReason {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitCodeSizeLimit,
+    /// EIP-4750: `CALLF` exceeds the depth of the function return stack.
+    EOFFunctionStackOverflow,
+    /// EIP-7069: `EXTCALL` target address has nonzero high bytes.
+    InvalidEXTCALLTarget,
+    /// EIP-7620: `RETURNCONTRACT` auxiliary data makes the data section larger than `u16::MAX`.
+    EofAuxDataOverflow,
+    /// EIP-7620: data section is shorter than declared after appending the auxiliary data.
+    EofAuxDataTooSmall,
+    /// EIP-3074: `AUTHCALL` without a successful `AUTH` in the current frame.
+    AuthorizedNotSet,
 
     /* Internal Halts that can be only found inside Inspector */
     OverflowPayment,
@@ -28,7 +28,12 @@ pub enum SpecId {
     MERGE = 15,           // Paris/Merge	        15537394 (TTD: 58750000000000000000000)
     SHANGHAI = 16,        // Shanghai	            17034870 (TS: 1681338455)
     CANCUN = 17,          // Cancun	                TBD
-    LATEST = u8::MAX,
+    PRAGUE = 18,          // Prague	                TBD
+    PRAGUE_EOF = 19,      // Prague with EOF        TBD
+    LATEST = u8::MAX - 1,
+    /// Experimental stateless gas schedule (EIP-4762), it is ordered after `LATEST` and is
+    /// not enabled by it.
+    VERKLE = u8::MAX,
 }
 
 /// Specification IDs and their activation block.
@@ -61,7 +66,11 @@ pub enum SpecId {
     CANYON = 19,
     CANCUN = 20,
     ECOTONE = 21,
-    LATEST = u8::MAX,
+    FJORD = 22,
+    PRAGUE = 23,
+    PRAGUE_EOF = 24,
+    LATEST = u8::MAX - 1,
+    VERKLE = u8::MAX,
 }
 
 impl SpecId {
@@ -97,6 +106,9 @@ impl From<&str> for SpecId {
             "Merge" => Self::MERGE,
             "Shanghai" => Self::SHANGHAI,
             "Cancun" => Self::CANCUN,
+            "Prague" => Self::PRAGUE,
+            "PragueEOF" => Self::PRAGUE_EOF,
+            "Verkle" => Self::VERKLE,
             #[cfg(feature = "optimism")]
             "Bedrock" => SpecId::BEDROCK,
             #[cfg(feature = "optimism")]
@@ -105,6 +117,8 @@ impl From<&str> for SpecId {
             "Canyon" => SpecId::CANYON,
             #[cfg(feature = "optimism")]
             "Ecotone" => SpecId::ECOTONE,
+            #[cfg(feature = "optimism")]
+            "Fjord" => SpecId::FJORD,
             _ => Self::LATEST,
         }
     }
@@ -150,8 +164,11 @@ spec!(LONDON, LondonSpec);
 spec!(MERGE, MergeSpec);
 spec!(SHANGHAI, ShanghaiSpec);
 spec!(CANCUN, CancunSpec);
+spec!(PRAGUE, PragueSpec);
+spec!(PRAGUE_EOF, PragueEofSpec);
 
 spec!(LATEST, LatestSpec);
+spec!(VERKLE, VerkleSpec);
 
 // Optimism Hardforks
 #[cfg(feature = "optimism")]
@@ -162,6 +179,8 @@ spec!(REGOLITH, RegolithSpec);
 spec!(CANYON, CanyonSpec);
 #[cfg(feature = "optimism")]
 spec!(ECOTONE, EcotoneSpec);
+#[cfg(feature = "optimism")]
+spec!(FJORD, FjordSpec);
 
 #[macro_export]
 macro_rules! spec_to_generic {
@@ -218,10 +237,22 @@ macro_rules! spec_to_generic {
                 use $crate::CancunSpec as SPEC;
                 $e
             }
+            $crate::SpecId::PRAGUE => {
+                use $crate::PragueSpec as SPEC;
+                $e
+            }
+            $crate::SpecId::PRAGUE_EOF => {
+                use $crate::PragueEofSpec as SPEC;
+                $e
+            }
             $crate::SpecId::LATEST => {
                 use $crate::LatestSpec as SPEC;
                 $e
             }
+            $crate::SpecId::VERKLE => {
+                use $crate::VerkleSpec as SPEC;
+                $e
+            }
             #[cfg(feature = "optimism")]
             $crate::SpecId::BEDROCK => {
                 use $crate::BedrockSpec as SPEC;
@@ -242,6 +273,11 @@ macro_rules! spec_to_generic {
                 use $crate::EcotoneSpec as SPEC;
                 $e
             }
+            #[cfg(feature = "optimism")]
+            $crate::SpecId::FJORD => {
+                use $crate::FjordSpec as SPEC;
+                $e
+            }
         }
     }};
 }
@@ -278,7 +314,21 @@ mod tests {
         #[cfg(feature = "optimism")]
         spec_to_generic!(CANYON, assert_eq!(SPEC::SPEC_ID, CANYON));
         spec_to_generic!(CANCUN, assert_eq!(SPEC::SPEC_ID, CANCUN));
+        #[cfg(feature = "optimism")]
+        spec_to_generic!(ECOTONE, assert_eq!(SPEC::SPEC_ID, ECOTONE));
+        #[cfg(feature = "optimism")]
+        spec_to_generic!(FJORD, assert_eq!(SPEC::SPEC_ID, FJORD));
+        spec_to_generic!(PRAGUE, assert_eq!(SPEC::SPEC_ID, PRAGUE));
+        spec_to_generic!(PRAGUE_EOF, assert_eq!(SPEC::SPEC_ID, PRAGUE_EOF));
         spec_to_generic!(LATEST, assert_eq!(SPEC::SPEC_ID, LATEST));
+        spec_to_generic!(VERKLE, assert_eq!(SPEC::SPEC_ID, VERKLE));
+    }
+
+    #[test]
+    fn verkle_is_after_latest() {
+        assert!(VerkleSpec::enabled(LATEST));
+        assert!(VerkleSpec::enabled(PRAGUE_EOF));
+        assert!(!LatestSpec::enabled(VERKLE));
     }
 }
 
@@ -372,4 +422,13 @@ mod optimism_tests {
         assert!(SpecId::enabled(SpecId::ECOTONE, SpecId::CANYON));
         assert!(SpecId::enabled(SpecId::ECOTONE, SpecId::ECOTONE));
     }
+
+    #[test]
+    fn test_fjord_post_merge_hardforks() {
+        assert!(FjordSpec::enabled(SpecId::CANCUN));
+        assert!(FjordSpec::enabled(SpecId::ECOTONE));
+        assert!(FjordSpec::enabled(SpecId::FJORD));
+        assert!(!FjordSpec::enabled(SpecId::LATEST));
+        assert!(!SpecId::enabled(SpecId::ECOTONE, SpecId::FJORD));
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    b256, B256, BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE, TARGET_BLOB_GAS_PER_BLOCK,
+    b256, Bloom, Log, B256, BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE,
+    TARGET_BLOB_GAS_PER_BLOCK,
 };
 pub use alloy_primitives::keccak256;
 
@@ -59,6 +60,22 @@ pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u128 {
     output / denominator
 }
 
+/// Calculates the logs bloom of the receipt from the logs of the transaction.
+#[inline]
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+    logs.into_iter().collect()
+}
+
+/// Calculates the logs bloom of the block header from the logs blooms of its receipts.
+#[inline]
+pub fn block_logs_bloom<'a>(receipt_blooms: impl IntoIterator<Item = &'a Bloom>) -> Bloom {
+    let mut bloom = Bloom::ZERO;
+    for receipt_bloom in receipt_blooms {
+        bloom.accrue_bloom(receipt_bloom);
+    }
+    bloom
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,4 +184,26 @@ mod tests {
             assert_eq!(actual, expected, "test: {t:?}");
         }
     }
+
+    #[test]
+    fn test_logs_bloom() {
+        use crate::{Address, BloomInput, Bytes};
+
+        let log = |address: u8, topic: u8| {
+            Log::new_unchecked(
+                Address::with_last_byte(address),
+                vec![B256::with_last_byte(topic)],
+                Bytes::new(),
+            )
+        };
+        let first = [log(1, 2)];
+        let second = [log(3, 4), log(5, 6)];
+        let bloom = logs_bloom(&first);
+        assert!(bloom.contains_input(BloomInput::Raw(Address::with_last_byte(1).as_slice())));
+        assert!(bloom.contains_input(BloomInput::Raw(B256::with_last_byte(2).as_slice())));
+        assert_eq!(logs_bloom(&[]), Bloom::ZERO);
+
+        let block = block_logs_bloom(&[bloom, logs_bloom(&second)]);
+        assert_eq!(block, logs_bloom(first.iter().chain(&second)));
+    }
 }
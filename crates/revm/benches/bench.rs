@@ -95,6 +95,7 @@ fn bench_transact<EXT>(g: &mut BenchmarkGroup<'_, WallTime>, evm: &mut Evm<'_, E
         BytecodeState::Raw => "raw",
         BytecodeState::Checked { .. } => "checked",
         BytecodeState::Analysed { .. } => "analysed",
+        BytecodeState::Eof(_) => "eof",
     };
     let id = format!("transact/{state}");
     g.bench_function(id, |b| b.iter(|| evm.transact().unwrap()));
@@ -312,7 +312,7 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
     pub fn append_handler_register(
         mut self,
         handle_register: register::HandleRegister<'a, EXT, DB>,
-    ) -> EvmBuilder<'_, HandlerStage, EXT, DB> {
+    ) -> EvmBuilder<'a, HandlerStage, EXT, DB> {
         self.handler
             .append_handler_register(register::HandleRegisters::Plain(handle_register));
         EvmBuilder {
@@ -330,7 +330,7 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
     pub fn append_handler_register_box(
         mut self,
         handle_register: register::HandleRegisterBox<'a, EXT, DB>,
-    ) -> EvmBuilder<'_, HandlerStage, EXT, DB> {
+    ) -> EvmBuilder<'a, HandlerStage, EXT, DB> {
         self.handler
             .append_handler_register(register::HandleRegisters::Box(handle_register));
         EvmBuilder {
@@ -444,6 +444,27 @@ mod test {
         EvmContext,
     };
 
+    #[test]
+    fn inspector_register_boxes_instructions() {
+        use crate::interpreter::opcode::InstructionTables;
+
+        // without inspector register instructions are plain function pointers.
+        let evm = Evm::builder().with_external_context(NoOpInspector).build();
+        assert!(matches!(
+            evm.handler.instruction_table,
+            Some(InstructionTables::Plain(_))
+        ));
+
+        let evm = Evm::builder()
+            .with_external_context(NoOpInspector)
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(matches!(
+            evm.handler.instruction_table,
+            Some(InstructionTables::Boxed(_))
+        ));
+    }
+
     #[test]
     fn simple_build() {
         // build without external with latest spec
@@ -518,4 +539,34 @@ mod test {
             .modify_tx_env(|tx| tx.chain_id = Some(2))
             .build();
     }
+
+    #[test]
+    fn build_with_custom_precompiles() {
+        use crate::{
+            precompile::{secp256r1, u64_to_address, Precompiles},
+            primitives::{address, TransactTo},
+        };
+        use std::sync::Arc;
+
+        let mut evm = Evm::builder()
+            .with_empty_db()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(u64_to_address(0x100));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(|handler| {
+                let precompiles = Precompiles::latest()
+                    .clone()
+                    .with_precompile(secp256r1::P256VERIFY)
+                    .without_precompile(&u64_to_address(1));
+                handler.pre_execution.load_precompiles = Arc::new(move || precompiles.clone());
+            })
+            .build();
+        evm.transact().unwrap();
+
+        let precompiles = &evm.context.evm.precompiles;
+        assert!(precompiles.contains(&u64_to_address(0x100)));
+        assert!(!precompiles.contains(&u64_to_address(1)));
+    }
 }
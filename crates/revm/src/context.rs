@@ -1,19 +1,68 @@
 use crate::{
     db::{Database, EmptyDB},
     interpreter::{
-        analysis::to_analysed, gas, return_ok, CallInputs, Contract, CreateInputs, Gas,
-        InstructionResult, Interpreter, InterpreterResult, MAX_CODE_SIZE,
+        analysis::{to_analysed, to_eof, validate_raw_eof_initcode},
+        gas::{self, WitnessKey},
+        return_ok, CallInputs, CallScheme, Contract, CreateInputs, Gas, InstructionResult,
+        Interpreter, InterpreterResult,
     },
-    journaled_state::JournaledState,
-    precompile::{Precompile, Precompiles},
+    journaled_state::{CreateError, JournaledState},
+    precompile::{address_to_u64, Precompile, PrecompileError, PrecompileResult, Precompiles},
     primitives::{
-        keccak256, Address, AnalysisKind, Bytecode, Bytes, CreateScheme, EVMError, Env, HandlerCfg,
-        HashSet, Spec, SpecId, SpecId::*, B256, U256,
+        eof::{EOF_MAGIC_BYTES, EOF_MAGIC_HASH},
+        keccak256, Address, AnalysisKind, Bytecode, Bytes, CreateScheme, EVMError, Env, Eof,
+        HandlerCfg, HashMap, HashSet, Spec, SpecId,
+        SpecId::*,
+        B256, BLOCKHASH_SERVE_WINDOW, BLOCKHASH_STORAGE_ADDRESS, BLOCK_HASH_HISTORY, U256,
     },
-    FrameOrResult, JournalCheckpoint, CALL_STACK_LIMIT,
+    FrameOrResult, JournalCheckpoint,
 };
+use core::{fmt, ops::RangeInclusive};
 use revm_interpreter::SStoreResult;
-use std::boxed::Box;
+use std::{boxed::Box, sync::Arc};
+
+/// Precompile that has mutable access to the [EvmContext].
+///
+/// It can read and write accounts and storage through the journaled state and emit logs,
+/// changes are reverted if the precompile fails or runs out of gas. Database errors should be
+/// stored in [EvmContext::error], execution stops after the precompile returns.
+pub trait ContextStatefulPrecompile<DB: Database>: Sync + Send {
+    fn call(&self, input: &Bytes, gas_limit: u64, context: &mut EvmContext<DB>)
+        -> PrecompileResult;
+}
+
+impl<DB: Database> fmt::Debug for dyn ContextStatefulPrecompile<DB> + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ContextStatefulPrecompile")
+    }
+}
+
+/// Context aware precompile, see [ContextStatefulPrecompile].
+pub type ContextPrecompile<DB> = Arc<dyn ContextStatefulPrecompile<DB>>;
+
+/// Handler of all precompiles inside an address range, e.g. ArbOS-style system precompiles.
+///
+/// Registered with [EvmContext::insert_precompile_range], it receives the called address
+/// to dispatch to the implementation of that address.
+pub trait AddressRangePrecompile<DB: Database>: Sync + Send {
+    fn call(
+        &self,
+        address: Address,
+        input: &Bytes,
+        gas_limit: u64,
+        context: &mut EvmContext<DB>,
+    ) -> PrecompileResult;
+}
+
+impl<DB: Database> fmt::Debug for dyn AddressRangePrecompile<DB> + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AddressRangePrecompile")
+    }
+}
+
+/// Range of the addresses made by [`u64_to_address`](crate::precompile::u64_to_address) and
+/// its [AddressRangePrecompile].
+pub type PrecompileRange<DB> = (RangeInclusive<u64>, Arc<dyn AddressRangePrecompile<DB>>);
 
 /// Main Context structure that contains both EvmContext and External context.
 pub struct Context<EXT, DB: Database> {
@@ -109,6 +158,13 @@ pub struct EvmContext<DB: Database> {
     pub error: Result<(), EVMError<DB::Error>>,
     /// Precompiles that are available for evm.
     pub precompiles: Precompiles,
+    /// Precompiles with access to the context, they take precedence over [Self::precompiles].
+    pub context_precompiles: HashMap<Address, ContextPrecompile<DB>>,
+    /// Precompile ranges, see [Self::insert_precompile_range].
+    pub precompile_ranges: Vec<PrecompileRange<DB>>,
+    /// Bytecode of the EOF containers by code hash, validated on the first call. Invalid
+    /// containers are cached as legacy bytecode so they are validated only once.
+    pub eof_cache: HashMap<B256, Bytecode>,
     /// Used as temporary value holder to store L1 block info.
     #[cfg(feature = "optimism")]
     pub l1_block_info: Option<crate::optimism::L1BlockInfo>,
@@ -125,6 +181,9 @@ where
             db: self.db.clone(),
             error: self.error.clone(),
             precompiles: self.precompiles.clone(),
+            context_precompiles: self.context_precompiles.clone(),
+            precompile_ranges: self.precompile_ranges.clone(),
+            eof_cache: self.eof_cache.clone(),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info.clone(),
         }
@@ -132,6 +191,7 @@ where
 }
 
 impl<DB: Database> EvmContext<DB> {
+    /// Replaces the database, context precompiles are dropped as they are bound to the database type.
     pub fn with_db<ODB: Database>(self, db: ODB) -> EvmContext<ODB> {
         EvmContext {
             env: self.env,
@@ -139,6 +199,9 @@ impl<DB: Database> EvmContext<DB> {
             db,
             error: Ok(()),
             precompiles: self.precompiles,
+            context_precompiles: HashMap::default(),
+            precompile_ranges: Vec::new(),
+            eof_cache: self.eof_cache,
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info,
         }
@@ -151,6 +214,9 @@ impl<DB: Database> EvmContext<DB> {
             db,
             error: Ok(()),
             precompiles: Precompiles::default(),
+            context_precompiles: HashMap::default(),
+            precompile_ranges: Vec::new(),
+            eof_cache: HashMap::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -164,6 +230,9 @@ impl<DB: Database> EvmContext<DB> {
             db,
             error: Ok(()),
             precompiles: Precompiles::default(),
+            context_precompiles: HashMap::default(),
+            precompile_ranges: Vec::new(),
+            eof_cache: HashMap::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -175,13 +244,70 @@ impl<DB: Database> EvmContext<DB> {
     }
 
     /// Sets precompiles
+    ///
+    /// Addresses of the precompiles become warm (EIP-2929), their accounts are not loaded
+    /// into the state until they are accessed.
     #[inline]
     pub fn set_precompiles(&mut self, precompiles: Precompiles) {
-        self.journaled_state.warm_preloaded_addresses =
-            precompiles.addresses().copied().collect::<HashSet<_>>();
+        self.journaled_state.warm_preloaded_addresses = precompiles
+            .addresses()
+            .chain(self.context_precompiles.keys())
+            .copied()
+            .collect::<HashSet<_>>();
         self.precompiles = precompiles;
     }
 
+    /// Inserts the context precompile, replaces the precompile at the same address.
+    ///
+    /// Address becomes warm same as the addresses of [Self::set_precompiles].
+    pub fn insert_context_precompile(
+        &mut self,
+        address: Address,
+        precompile: ContextPrecompile<DB>,
+    ) {
+        self.journaled_state
+            .warm_preloaded_addresses
+            .insert(address);
+        self.context_precompiles.insert(address, precompile);
+    }
+
+    /// Registers the handler for the addresses in the range, addresses are made by
+    /// [`u64_to_address`](crate::precompile::u64_to_address) so `0x64..=0xff` covers the ArbOS
+    /// system precompiles.
+    ///
+    /// Addresses of the range are warm same as the addresses of [Self::set_precompiles].
+    /// Precompiles of [Self::insert_context_precompile] take precedence over the ranges and a
+    /// range takes precedence over the ranges inserted before it.
+    pub fn insert_precompile_range(
+        &mut self,
+        range: RangeInclusive<u64>,
+        handler: Arc<dyn AddressRangePrecompile<DB>>,
+    ) {
+        self.journaled_state
+            .warm_preloaded_ranges
+            .push(range.clone());
+        self.precompile_ranges.push((range, handler));
+    }
+
+    /// Returns the handler of the precompile range that contains the `address`.
+    #[inline]
+    fn precompile_range(&self, address: &Address) -> Option<&Arc<dyn AddressRangePrecompile<DB>>> {
+        let index = address_to_u64(address)?;
+        self.precompile_ranges
+            .iter()
+            .rev()
+            .find(|(range, _)| range.contains(&index))
+            .map(|(_, handler)| handler)
+    }
+
+    /// Is the given address a precompile, a context precompile or in a precompile range.
+    #[inline]
+    pub fn is_precompile(&self, address: &Address) -> bool {
+        self.context_precompiles.contains_key(address)
+            || self.precompile_range(address).is_some()
+            || self.precompiles.contains(address)
+    }
+
     /// Load access list for berlin hard fork.
     ///
     /// Loading of accounts/storages is needed to make them warm.
@@ -201,8 +327,34 @@ impl<DB: Database> EvmContext<DB> {
     }
 
     /// Fetch block hash from database.
+    ///
+    /// After Prague the hash is read from the EIP-2935 history storage contract. The database is
+    /// used for the last [BLOCK_HASH_HISTORY] blocks if they are not in the ring buffer of the
+    /// contract, e.g. the blocks before the fork.
     #[inline]
     pub fn block_hash(&mut self, number: U256) -> Result<B256, EVMError<DB::Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(target: "revm::db", %number, "block_hash");
+        if self.journaled_state.spec.is_enabled_in(PRAGUE) {
+            let index = number.wrapping_rem(U256::from(BLOCKHASH_SERVE_WINDOW));
+            let hash = match self
+                .journaled_state
+                .get_storage(&BLOCKHASH_STORAGE_ADDRESS, &index)
+            {
+                Some(hash) => hash,
+                None => self
+                    .db
+                    .storage(BLOCKHASH_STORAGE_ADDRESS, index)
+                    .map_err(EVMError::Database)?,
+            };
+            if !hash.is_zero() {
+                return Ok(hash.to_be_bytes().into());
+            }
+            let diff = self.env.block.number.saturating_sub(number);
+            if diff > U256::from(BLOCK_HASH_HISTORY) {
+                return Ok(B256::ZERO);
+            }
+        }
         self.db.block_hash(number).map_err(EVMError::Database)
     }
 
@@ -221,21 +373,47 @@ impl<DB: Database> EvmContext<DB> {
             .map(|(acc, is_cold)| (acc.info.balance, is_cold))
     }
 
+    /// Return account nonce and is_cold flag.
+    #[inline]
+    pub fn nonce(&mut self, address: Address) -> Result<(u64, bool), EVMError<DB::Error>> {
+        self.journaled_state
+            .load_account(address, &mut self.db)
+            .map(|(acc, is_cold)| (acc.info.nonce, is_cold))
+    }
+
     /// Return account code and if address is cold loaded.
+    ///
+    /// After EOF is enabled the code of EOF accounts is the EOF magic.
     #[inline]
     pub fn code(&mut self, address: Address) -> Result<(Bytecode, bool), EVMError<DB::Error>> {
-        self.journaled_state
-            .load_code(address, &mut self.db)
-            .map(|(a, is_cold)| (a.info.code.clone().unwrap(), is_cold))
+        let is_eof_enabled = self.spec_id().is_enabled_in(PRAGUE_EOF);
+        let (account, is_cold) = self.journaled_state.load_code(address, &mut self.db)?;
+        let code = account.info.code.clone().unwrap();
+        if is_eof_enabled && code.bytes().starts_with(&EOF_MAGIC_BYTES) {
+            return Ok((Bytecode::new_raw(EOF_MAGIC_BYTES.into()), is_cold));
+        }
+        Ok((code, is_cold))
     }
 
     /// Get code hash of address.
+    ///
+    /// After EOF is enabled the code hash of EOF accounts is the hash of the EOF magic.
     #[inline]
     pub fn code_hash(&mut self, address: Address) -> Result<(B256, bool), EVMError<DB::Error>> {
+        let is_eof_enabled = self.spec_id().is_enabled_in(PRAGUE_EOF);
         let (acc, is_cold) = self.journaled_state.load_code(address, &mut self.db)?;
         if acc.is_empty() {
             return Ok((B256::ZERO, is_cold));
         }
+        if is_eof_enabled
+            && acc
+                .info
+                .code
+                .as_ref()
+                .is_some_and(|code| code.bytes().starts_with(&EOF_MAGIC_BYTES))
+        {
+            return Ok((EOF_MAGIC_HASH, is_cold));
+        }
         Ok((acc.info.code_hash, is_cold))
     }
 
@@ -281,6 +459,16 @@ impl<DB: Database> EvmContext<DB> {
         spec_id: SpecId,
         inputs: &CreateInputs,
     ) -> Result<FrameOrResult, EVMError<DB::Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            target: "revm::frame",
+            depth = self.journaled_state.depth() + 1,
+            scheme = ?inputs.scheme,
+            caller = %inputs.caller,
+            value = %inputs.value,
+            gas_limit = inputs.gas_limit,
+            "create frame"
+        );
         // Prepare crate.
         let gas = Gas::new(inputs.gas_limit);
 
@@ -296,7 +484,7 @@ impl<DB: Database> EvmContext<DB> {
         };
 
         // Check depth
-        if self.journaled_state.depth() > CALL_STACK_LIMIT {
+        if self.journaled_state.check_depth().is_err() {
             return return_error(InstructionResult::CallTooDeep);
         }
 
@@ -308,9 +496,21 @@ impl<DB: Database> EvmContext<DB> {
             return return_error(InstructionResult::OutOfFunds);
         }
 
+        // EIP-7620: EOF initcode is validated before the nonce is increased.
+        let mut eof_initcode = None;
+        if let CreateScheme::EofCreate { .. } = inputs.scheme {
+            if !spec_id.is_enabled_in(PRAGUE_EOF) {
+                return return_error(InstructionResult::NotActivated);
+            }
+            match validate_raw_eof_initcode(inputs.init_code.clone()) {
+                Ok(eof) => eof_initcode = Some(eof),
+                Err(_) => return return_error(InstructionResult::InvalidEOFInitCode),
+            }
+        }
+
         // Increase nonce of caller and check if it overflows
         let old_nonce;
-        if let Some(nonce) = self.journaled_state.inc_nonce(inputs.caller) {
+        if let Some(nonce) = self.journaled_state.inc_nonce(inputs.caller)? {
             old_nonce = nonce - 1;
         } else {
             return return_error(InstructionResult::Return);
@@ -320,7 +520,7 @@ impl<DB: Database> EvmContext<DB> {
         let mut init_code_hash = B256::ZERO;
         let created_address = match inputs.scheme {
             CreateScheme::Create => inputs.caller.create(old_nonce),
-            CreateScheme::Create2 { salt } => {
+            CreateScheme::Create2 { salt } | CreateScheme::EofCreate { salt } => {
                 init_code_hash = keccak256(&inputs.init_code);
                 inputs.caller.create2(salt.to_be_bytes(), init_code_hash)
             }
@@ -338,15 +538,19 @@ impl<DB: Database> EvmContext<DB> {
             spec_id,
         ) {
             Ok(checkpoint) => checkpoint,
-            Err(e) => {
+            Err(CreateError::Failed(e)) => {
                 return return_error(e);
             }
+            Err(CreateError::Journal(e)) => return Err(e.into()),
         };
 
-        let bytecode = Bytecode::new_raw(inputs.init_code.clone());
+        let bytecode = match eof_initcode {
+            Some(eof) => Bytecode::new_eof(eof),
+            None => Bytecode::new_raw(inputs.init_code.clone()),
+        };
 
         let contract = Box::new(Contract::new(
-            Bytes::new(),
+            inputs.input.clone(),
             bytecode,
             init_code_hash,
             created_address,
@@ -367,6 +571,17 @@ impl<DB: Database> EvmContext<DB> {
         &mut self,
         inputs: &CallInputs,
     ) -> Result<FrameOrResult, EVMError<DB::Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            target: "revm::frame",
+            depth = self.journaled_state.depth() + 1,
+            scheme = ?inputs.context.scheme,
+            caller = %inputs.context.caller,
+            contract = %inputs.contract,
+            value = %inputs.transfer.value,
+            gas_limit = inputs.gas_limit,
+            "call frame"
+        );
         let gas = Gas::new(inputs.gas_limit);
 
         let return_result = |instruction_result: InstructionResult| {
@@ -381,18 +596,34 @@ impl<DB: Database> EvmContext<DB> {
         };
 
         // Check depth
-        if self.journaled_state.depth() > CALL_STACK_LIMIT {
+        if self.journaled_state.check_depth().is_err() {
             return return_result(InstructionResult::CallTooDeep);
         }
 
+        let is_eof_enabled = self.spec_id().is_enabled_in(PRAGUE_EOF);
         let (account, _) = self
             .journaled_state
             .load_code(inputs.contract, &mut self.db)?;
         let code_hash = account.info.code_hash();
-        let bytecode = account.info.code.clone().unwrap_or_default();
+        let mut bytecode = account.info.code.clone().unwrap_or_default();
+        if is_eof_enabled && !bytecode.is_eof() && bytecode.bytes().starts_with(&EOF_MAGIC_BYTES) {
+            // account code is journaled state and is left as is, the container is cached.
+            bytecode = self
+                .eof_cache
+                .entry(code_hash)
+                .or_insert_with(|| to_eof(bytecode.clone()))
+                .clone();
+        }
+
+        // EIP-7069: EOF code can only delegate to EOF code.
+        if inputs.context.scheme == CallScheme::ExtDelegateCall && !bytecode.is_eof() {
+            return return_result(InstructionResult::InvalidExtDelegateCallTarget);
+        }
 
         // Create subroutine checkpoint
-        let checkpoint = self.journaled_state.checkpoint();
+        let Ok(checkpoint) = self.journaled_state.checkpoint() else {
+            return return_result(InstructionResult::CallTooDeep);
+        };
 
         // Touch address. For "EIP-158 State Clear", this will erase empty accounts.
         if inputs.transfer.value == U256::ZERO {
@@ -411,8 +642,28 @@ impl<DB: Database> EvmContext<DB> {
             return return_result(result);
         }
 
-        if let Some(precompile) = self.precompiles.get_mut(&inputs.contract) {
-            let result = Self::call_precompile(precompile, &inputs.input, gas, &self.env);
+        let result = if let Some(precompile) = self.context_precompiles.get(&inputs.contract) {
+            let precompile = precompile.clone();
+            let out = precompile.call(&inputs.input, gas.limit(), self);
+            Some(Self::precompile_result(out, gas))
+        } else if let Some(handler) = self.precompile_range(&inputs.contract) {
+            let handler = handler.clone();
+            let out = handler.call(inputs.contract, &inputs.input, gas.limit(), self);
+            Some(Self::precompile_result(out, gas))
+        } else {
+            self.precompiles
+                .get_mut(&inputs.contract)
+                .map(|precompile| Self::call_precompile(precompile, &inputs.input, gas, &self.env))
+        };
+
+        if let Some(result) = result {
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    self.journaled_state.checkpoint_revert(checkpoint);
+                    return Err(e);
+                }
+            };
             if matches!(result.result, return_ok!()) {
                 self.journaled_state.checkpoint_commit();
             } else {
@@ -448,9 +699,19 @@ impl<DB: Database> EvmContext<DB> {
         input_data: &Bytes,
         gas: Gas,
         env: &Env,
-    ) -> InterpreterResult {
+    ) -> Result<InterpreterResult, EVMError<DB::Error>> {
         let out = precompile.call(input_data, gas.limit(), env);
+        Self::precompile_result(out, gas)
+    }
 
+    /// Converts the output of the precompile to the interpreter result.
+    ///
+    /// Out of gas and input errors fail the call, fatal errors abort the transaction.
+    #[inline]
+    fn precompile_result(
+        out: PrecompileResult,
+        gas: Gas,
+    ) -> Result<InterpreterResult, EVMError<DB::Error>> {
         let mut result = InterpreterResult {
             result: InstructionResult::Return,
             gas,
@@ -466,15 +727,16 @@ impl<DB: Database> EvmContext<DB> {
                     result.result = InstructionResult::PrecompileOOG;
                 }
             }
+            Err(PrecompileError::Fatal(msg)) => return Err(EVMError::Precompile(msg)),
             Err(e) => {
-                result.result = if e == crate::precompile::Error::OutOfGas {
+                result.result = if e.is_oog() {
                     InstructionResult::PrecompileOOG
                 } else {
                     InstructionResult::PrecompileError
                 };
             }
         }
-        result
+        Ok(result)
     }
 
     /// Handles call return.
@@ -499,40 +761,57 @@ impl<DB: Database> EvmContext<DB> {
         interpreter_result: &mut InterpreterResult,
         address: Address,
         journal_checkpoint: JournalCheckpoint,
-    ) {
+    ) -> Result<(), EVMError<DB::Error>> {
         // if return is not ok revert and return.
         if !matches!(interpreter_result.result, return_ok!()) {
             self.journaled_state.checkpoint_revert(journal_checkpoint);
-            return;
+            return Ok(());
         }
+        // EIP-7620: EOF initcode returns the container to deploy.
+        let is_eof = interpreter_result.result == InstructionResult::ReturnContract;
+
         // Host error if present on execution
         // if ok, check contract creation limit and calculate gas deduction on output len.
         //
         // EIP-3541: Reject new contract code starting with the 0xEF byte
         if SPEC::enabled(LONDON)
+            && !is_eof
             && !interpreter_result.output.is_empty()
             && interpreter_result.output.first() == Some(&0xEF)
         {
             self.journaled_state.checkpoint_revert(journal_checkpoint);
             interpreter_result.result = InstructionResult::CreateContractStartingWithEF;
-            return;
+            return Ok(());
         }
 
         // EIP-170: Contract code size limit
         // By default limit is 0x6000 (~25kb)
         if SPEC::enabled(SPURIOUS_DRAGON)
-            && interpreter_result.output.len()
-                > self
-                    .env
-                    .cfg
-                    .limit_contract_code_size
-                    .unwrap_or(MAX_CODE_SIZE)
+            && self
+                .env
+                .cfg
+                .chain
+                .max_code_size
+                .is_some_and(|limit| interpreter_result.output.len() > limit)
         {
             self.journaled_state.checkpoint_revert(journal_checkpoint);
             interpreter_result.result = InstructionResult::CreateContractSizeLimit;
-            return;
+            return Ok(());
         }
-        let gas_for_code = interpreter_result.output.len() as u64 * gas::CODEDEPOSIT;
+        let gas_for_code = if SPEC::enabled(VERKLE) {
+            // EIP-4762: Statelessness gas cost changes, code is written to the witness.
+            let witness = &mut self.journaled_state.access_witness;
+            let chunks = interpreter_result
+                .output
+                .len()
+                .div_ceil(gas::CODE_CHUNK_SIZE);
+            witness.write(WitnessKey::code_hash(address), true)
+                + (0..chunks as u64)
+                    .map(|chunk| witness.write(WitnessKey::code_chunk(address, chunk), true))
+                    .sum::<u64>()
+        } else {
+            interpreter_result.output.len() as u64 * self.env.cfg.chain.code_deposit_gas
+        };
         if !interpreter_result.gas.record_cost(gas_for_code) {
             // record code deposit gas cost and check if we are out of gas.
             // EIP-2 point 3: If contract creation does not have enough gas to pay for the
@@ -541,7 +820,7 @@ impl<DB: Database> EvmContext<DB> {
             if SPEC::enabled(HOMESTEAD) {
                 self.journaled_state.checkpoint_revert(journal_checkpoint);
                 interpreter_result.result = InstructionResult::OutOfGas;
-                return;
+                return Ok(());
             } else {
                 interpreter_result.output = Bytes::new();
             }
@@ -550,20 +829,28 @@ impl<DB: Database> EvmContext<DB> {
         self.journaled_state.checkpoint_commit();
 
         // Do analysis of bytecode straight away.
-        let bytecode = match self.env.cfg.perf_analyse_created_bytecodes {
-            AnalysisKind::Raw => Bytecode::new_raw(interpreter_result.output.clone()),
-            AnalysisKind::Check => {
-                Bytecode::new_raw(interpreter_result.output.clone()).to_checked()
-            }
-            AnalysisKind::Analyse => {
-                to_analysed(Bytecode::new_raw(interpreter_result.output.clone()))
+        let bytecode = if is_eof {
+            Bytecode::new_eof(
+                Eof::decode(interpreter_result.output.clone())
+                    .expect("RETURNCONTRACT outputs the validated container"),
+            )
+        } else {
+            match self.env.cfg.perf_analyse_created_bytecodes {
+                AnalysisKind::Raw => Bytecode::new_raw(interpreter_result.output.clone()),
+                AnalysisKind::Check => {
+                    Bytecode::new_raw(interpreter_result.output.clone()).to_checked()
+                }
+                AnalysisKind::Analyse => {
+                    to_analysed(Bytecode::new_raw(interpreter_result.output.clone()))
+                }
             }
         };
 
         // set code
-        self.journaled_state.set_code(address, bytecode);
+        self.journaled_state.set_code(address, bytecode)?;
 
         interpreter_result.result = InstructionResult::Return;
+        Ok(())
     }
 }
 /// Test utilities for the [`EvmContext`].
@@ -632,6 +919,9 @@ pub(crate) mod test_utils {
             db,
             error: Ok(()),
             precompiles: Precompiles::default(),
+            context_precompiles: HashMap::default(),
+            precompile_ranges: Vec::new(),
+            eof_cache: HashMap::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -645,6 +935,9 @@ pub(crate) mod test_utils {
             db,
             error: Ok(()),
             precompiles: Precompiles::default(),
+            context_precompiles: HashMap::default(),
+            precompile_ranges: Vec::new(),
+            eof_cache: HashMap::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
         }
@@ -655,8 +948,9 @@ pub(crate) mod test_utils {
 mod tests {
     use super::*;
     use crate::db::{CacheDB, EmptyDB};
+    use crate::precompile::u64_to_address;
     use crate::primitives::address;
-    use crate::{Frame, JournalEntry};
+    use crate::{Frame, JournalEntry, CALL_STACK_LIMIT};
     use test_utils::*;
 
     // Tests that the `EVMContext::make_call_frame` function returns an error if the
@@ -742,4 +1036,152 @@ mod tests {
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
     }
+
+    #[test]
+    fn test_context_precompile() {
+        use crate::primitives::Log;
+
+        /// Stores one to the first slot of the precompile, fails after the write if input is set.
+        struct Store;
+
+        impl ContextStatefulPrecompile<CacheDB<EmptyDB>> for Store {
+            fn call(
+                &self,
+                input: &Bytes,
+                _gas_limit: u64,
+                context: &mut EvmContext<CacheDB<EmptyDB>>,
+            ) -> PrecompileResult {
+                let address = Address::with_last_byte(0x42);
+                context
+                    .journaled_state
+                    .set_storage(address, U256::ZERO, U256::from(1), &mut context.db)
+                    .unwrap();
+                context
+                    .journaled_state
+                    .log(Log::new_unchecked(address, vec![], Bytes::new()));
+                if input.is_empty() {
+                    Ok((0, Bytes::new()))
+                } else {
+                    Err(PrecompileError::OutOfGas)
+                }
+            }
+        }
+
+        let contract = Address::with_last_byte(0x42);
+        let cdb = CacheDB::new(EmptyDB::default());
+        let mut evm_context =
+            create_cache_db_evm_context_with_balance(Box::default(), cdb, U256::ZERO);
+        evm_context.insert_context_precompile(contract, Arc::new(Store));
+        assert!(evm_context.is_precompile(&contract));
+
+        let mut call_inputs = test_utils::create_mock_call_inputs(contract);
+        call_inputs.input = Bytes::from_static(&[1]);
+        let Ok(FrameOrResult::Result(result)) = evm_context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::PrecompileOOG
+        );
+        assert!(evm_context.journaled_state.logs.is_empty());
+        assert_eq!(
+            evm_context.journaled_state.state[&contract]
+                .storage
+                .get(&U256::ZERO),
+            None
+        );
+
+        call_inputs.input = Bytes::new();
+        let Ok(FrameOrResult::Result(result)) = evm_context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::Return
+        );
+        assert_eq!(evm_context.journaled_state.logs.len(), 1);
+        assert_eq!(
+            evm_context.journaled_state.state[&contract].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn test_fatal_precompile_error() {
+        use std::string::String;
+
+        /// Fails as if the backend is not available.
+        struct Unavailable;
+
+        impl ContextStatefulPrecompile<CacheDB<EmptyDB>> for Unavailable {
+            fn call(
+                &self,
+                _input: &Bytes,
+                _gas_limit: u64,
+                _context: &mut EvmContext<CacheDB<EmptyDB>>,
+            ) -> PrecompileResult {
+                Err(PrecompileError::Fatal(String::from("no backend")))
+            }
+        }
+
+        let contract = Address::with_last_byte(0x42);
+        let cdb = CacheDB::new(EmptyDB::default());
+        let mut evm_context =
+            create_cache_db_evm_context_with_balance(Box::default(), cdb, U256::ZERO);
+        evm_context.insert_context_precompile(contract, Arc::new(Unavailable));
+        let call_inputs = test_utils::create_mock_call_inputs(contract);
+        assert_eq!(
+            evm_context.make_call_frame(&call_inputs).err(),
+            Some(EVMError::Precompile(String::from("no backend")))
+        );
+        assert_eq!(evm_context.journaled_state.depth, 0);
+    }
+
+    #[test]
+    fn test_precompile_range() {
+        /// Returns the last byte of the called address.
+        struct System;
+
+        impl AddressRangePrecompile<CacheDB<EmptyDB>> for System {
+            fn call(
+                &self,
+                address: Address,
+                _input: &Bytes,
+                _gas_limit: u64,
+                _context: &mut EvmContext<CacheDB<EmptyDB>>,
+            ) -> PrecompileResult {
+                Ok((0, Bytes::copy_from_slice(&address[19..])))
+            }
+        }
+
+        let cdb = CacheDB::new(EmptyDB::default());
+        let mut evm_context =
+            create_cache_db_evm_context_with_balance(Box::default(), cdb, U256::ZERO);
+        evm_context.insert_precompile_range(0x64..=0xff, Arc::new(System));
+        assert!(!evm_context.is_precompile(&Address::with_last_byte(0x63)));
+        assert!(!evm_context.is_precompile(&u64_to_address(0x100)));
+
+        for last_byte in [0x64, 0xa0, 0xff] {
+            let address = Address::with_last_byte(last_byte);
+            assert!(evm_context.is_precompile(&address));
+            assert!(evm_context.journaled_state.is_warm_preloaded(&address));
+            let call_inputs = test_utils::create_mock_call_inputs(address);
+            let Ok(FrameOrResult::Result(result)) = evm_context.make_call_frame(&call_inputs)
+            else {
+                panic!("Expected FrameOrResult::Result");
+            };
+            assert_eq!(result.interpreter_result().output[..], [last_byte]);
+        }
+
+        // wide range is not expanded into addresses, only addresses made from `u64` are in it.
+        evm_context.insert_precompile_range(0x1000..=u64::MAX, Arc::new(System));
+        assert!(evm_context.is_precompile(&u64_to_address(u64::MAX - 0x10)));
+        assert!(evm_context
+            .journaled_state
+            .is_warm_preloaded(&u64_to_address(0x1000)));
+        assert!(!evm_context.is_precompile(&Address::repeat_byte(0x10)));
+        assert!(!evm_context
+            .journaled_state
+            .is_warm_preloaded(&Address::repeat_byte(0x10)));
+    }
 }
@@ -1,10 +1,10 @@
 use super::{DatabaseCommit, DatabaseRef, EmptyDB};
 use crate::primitives::{
-    hash_map::Entry, Account, AccountInfo, Address, Bytecode, HashMap, Log, B256, KECCAK_EMPTY,
-    U256,
+    hash_map::{DefaultHashBuilder, Entry},
+    Account, AccountInfo, Address, Bytecode, HashMap, Log, B256, KECCAK_EMPTY, U256,
 };
 use crate::Database;
-use core::convert::Infallible;
+use core::{convert::Infallible, hash::BuildHasher};
 use std::vec::Vec;
 
 /// A [Database] implementation that stores all state changes in memory.
@@ -17,40 +17,59 @@ pub type InMemoryDB = CacheDB<EmptyDB>;
 /// Accounts and code are stored in two separate maps, the `accounts` map maps addresses to [DbAccount],
 /// whereas contracts are identified by their code hash, and are stored in the `contracts` map.
 /// The [DbAccount] holds the code hash of the contract, which is used to look up the contract in the `contracts` map.
+///
+/// Maps are hashed with `S`, hashbrown's default ahash unless it is set. SipHash of
+/// `std::collections::hash_map::RandomState` is resistant to HashDoS with attacker controlled
+/// keys, while faster hashers like FxHash are not.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct CacheDB<ExtDB> {
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "ExtDB: serde::Serialize, S: BuildHasher",
+        deserialize = "ExtDB: serde::Deserialize<'de>, S: BuildHasher + Default"
+    ))
+)]
+pub struct CacheDB<ExtDB, S = DefaultHashBuilder> {
     /// Account info where None means it is not existing. Not existing state is needed for Pre TANGERINE forks.
     /// `code` is always `None`, and bytecode can be found in `contracts`.
-    pub accounts: HashMap<Address, DbAccount>,
+    pub accounts: HashMap<Address, DbAccount<S>, S>,
     /// Tracks all contracts by their code hash.
-    pub contracts: HashMap<B256, Bytecode>,
+    pub contracts: HashMap<B256, Bytecode, S>,
     /// All logs that were committed via [DatabaseCommit::commit].
     pub logs: Vec<Log>,
     /// All cached block hashes from the [DatabaseRef].
-    pub block_hashes: HashMap<U256, B256>,
+    pub block_hashes: HashMap<U256, B256, S>,
     /// The underlying database ([DatabaseRef]) that is used to load data.
     ///
     /// Note: this is read-only, data is never written to this database.
     pub db: ExtDB,
 }
 
-impl<ExtDB: Default> Default for CacheDB<ExtDB> {
+impl<ExtDB: Default, S: BuildHasher + Default> Default for CacheDB<ExtDB, S> {
     fn default() -> Self {
-        Self::new(ExtDB::default())
+        Self::with_hasher(ExtDB::default())
     }
 }
 
 impl<ExtDB> CacheDB<ExtDB> {
     pub fn new(db: ExtDB) -> Self {
-        let mut contracts = HashMap::new();
+        Self::with_hasher(db)
+    }
+}
+
+impl<ExtDB, S: BuildHasher + Default> CacheDB<ExtDB, S> {
+    /// Creates the cache with the maps hashed by `S`, e.g.
+    /// `CacheDB::<_, RandomState>::with_hasher(db)` for SipHash.
+    pub fn with_hasher(db: ExtDB) -> Self {
+        let mut contracts = HashMap::default();
         contracts.insert(KECCAK_EMPTY, Bytecode::new());
         contracts.insert(B256::ZERO, Bytecode::new());
         Self {
-            accounts: HashMap::new(),
+            accounts: HashMap::default(),
             contracts,
             logs: Vec::default(),
-            block_hashes: HashMap::new(),
+            block_hashes: HashMap::default(),
             db,
         }
     }
@@ -83,11 +102,11 @@ impl<ExtDB> CacheDB<ExtDB> {
     }
 }
 
-impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
+impl<ExtDB: DatabaseRef, S: BuildHasher + Default> CacheDB<ExtDB, S> {
     /// Returns the account for the given address.
     ///
     /// If the account was not found in the cache, it will be loaded from the underlying database.
-    pub fn load_account(&mut self, address: Address) -> Result<&mut DbAccount, ExtDB::Error> {
+    pub fn load_account(&mut self, address: Address) -> Result<&mut DbAccount<S>, ExtDB::Error> {
         let db = &self.db;
         match self.accounts.entry(address) {
             Entry::Occupied(entry) => Ok(entry.into_mut()),
@@ -127,7 +146,7 @@ impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
     }
 }
 
-impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
+impl<ExtDB, S: BuildHasher + Default> DatabaseCommit for CacheDB<ExtDB, S> {
     fn commit(&mut self, changes: HashMap<Address, Account>) {
         for (address, mut account) in changes {
             if !account.is_touched() {
@@ -165,7 +184,7 @@ impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
     }
 }
 
-impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
+impl<ExtDB: DatabaseRef, S: BuildHasher + Default> Database for CacheDB<ExtDB, S> {
     type Error = ExtDB::Error;
 
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
@@ -222,7 +241,7 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
                 let info = self.db.basic_ref(address)?;
                 let (account, value) = if info.is_some() {
                     let value = self.db.storage_ref(address, index)?;
-                    let mut account: DbAccount = info.into();
+                    let mut account: DbAccount<S> = info.into();
                     account.storage.insert(index, value);
                     (account, value)
                 } else {
@@ -246,7 +265,7 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
     }
 }
 
-impl<ExtDB: DatabaseRef> DatabaseRef for CacheDB<ExtDB> {
+impl<ExtDB: DatabaseRef, S: BuildHasher + Default> DatabaseRef for CacheDB<ExtDB, S> {
     type Error = ExtDB::Error;
 
     fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
@@ -292,15 +311,19 @@ impl<ExtDB: DatabaseRef> DatabaseRef for CacheDB<ExtDB> {
 
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct DbAccount {
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "S: BuildHasher", deserialize = "S: BuildHasher + Default"))
+)]
+pub struct DbAccount<S = DefaultHashBuilder> {
     pub info: AccountInfo,
     /// If account is selfdestructed or newly created, storage will be cleared.
     pub account_state: AccountState,
     /// storage slots
-    pub storage: HashMap<U256, U256>,
+    pub storage: HashMap<U256, U256, S>,
 }
 
-impl DbAccount {
+impl<S: Default> DbAccount<S> {
     pub fn new_not_existing() -> Self {
         Self {
             account_state: AccountState::NotExisting,
@@ -317,13 +340,13 @@ impl DbAccount {
     }
 }
 
-impl From<Option<AccountInfo>> for DbAccount {
+impl<S: Default> From<Option<AccountInfo>> for DbAccount<S> {
     fn from(from: Option<AccountInfo>) -> Self {
         from.map(Self::from).unwrap_or_else(Self::new_not_existing)
     }
 }
 
-impl From<AccountInfo> for DbAccount {
+impl<S: Default> From<AccountInfo> for DbAccount<S> {
     fn from(info: AccountInfo) -> Self {
         Self {
             info,
@@ -459,6 +482,24 @@ mod tests {
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let account = Address::with_last_byte(42);
+        let mut state = CacheDB::<_, RandomState>::with_hasher(EmptyDB::default());
+        state.insert_account_info(account, AccountInfo::from_balance(U256::from(1)));
+        let (key, value) = (U256::from(123), U256::from(456));
+        let _ = state.insert_account_storage(account, key, value);
+
+        assert_eq!(
+            state.basic(account).unwrap().unwrap().balance,
+            U256::from(1)
+        );
+        assert_eq!(state.storage(account, key), Ok(value));
+    }
+
     #[cfg(feature = "serde-json")]
     #[test]
     fn test_serialize_deserialize_cachedb() {
@@ -1,6 +1,7 @@
 use super::RevertToSlot;
-use revm_interpreter::primitives::{AccountInfo, Address, Bytecode, B256, U256};
-use std::vec::Vec;
+use crate::AccountTransition;
+use revm_interpreter::primitives::{AccountInfo, Address, Bytecode, HashMap, B256, U256};
+use std::{collections::BTreeMap, vec::Vec};
 
 /// accounts/storages/contracts for inclusion into database.
 /// Structure is made so it is easier to apply directly to database
@@ -65,7 +66,140 @@ impl PlainStateReverts {
             storage: Vec::with_capacity(capacity),
         }
     }
+
+    /// Constructs reverts of a single block from the account transitions of its
+    /// transactions, see [crate::JournaledState::finalize_with_transitions].
+    ///
+    /// Transitions are expected in execution order. Value before the block is the value
+    /// before the first transaction that changed it. Slots first changed after the account
+    /// was destroyed inside the block are reverted to [RevertToSlot::Destroyed].
+    pub fn from_block_transitions<'a>(
+        transitions: impl IntoIterator<Item = &'a HashMap<Address, AccountTransition>>,
+    ) -> Self {
+        let mut accounts: BTreeMap<Address, Option<AccountInfo>> = BTreeMap::new();
+        let mut storage: BTreeMap<Address, PlainStorageRevert> = BTreeMap::new();
+        for transaction in transitions {
+            for (address, transition) in transaction {
+                let changed = transition.destroyed
+                    || transition.previous_info != transition.info
+                    || !transition.storage.is_empty();
+                if !changed {
+                    continue;
+                }
+                accounts
+                    .entry(*address)
+                    .or_insert_with(|| transition.previous_info.clone().map(|i| i.without_code()));
+
+                let revert = storage
+                    .entry(*address)
+                    .or_insert_with(|| PlainStorageRevert {
+                        address: *address,
+                        ..Default::default()
+                    });
+                let changed = transition
+                    .storage
+                    .iter()
+                    .map(|(key, slot)| (key, &slot.previous_or_original_value));
+                for (key, original) in changed.chain(&transition.wiped_storage) {
+                    if revert.storage_revert.iter().any(|(k, _)| k == key) {
+                        continue;
+                    }
+                    let value = if revert.wiped {
+                        RevertToSlot::Destroyed
+                    } else {
+                        RevertToSlot::Some(*original)
+                    };
+                    revert.storage_revert.push((*key, value));
+                }
+                revert.wiped |= transition.destroyed;
+            }
+        }
+        storage.retain(|_, revert| revert.wiped || !revert.storage_revert.is_empty());
+        Self {
+            accounts: vec![accounts.into_iter().collect()],
+            storage: vec![storage.into_values().collect()],
+        }
+    }
 }
 
 /// Storage reverts
 pub type StorageRevert = Vec<Vec<(Address, bool, Vec<(U256, RevertToSlot)>)>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(
+        previous: Option<u64>,
+        balance: Option<u64>,
+        storage: &[(u64, u64, u64)],
+        destroyed: bool,
+    ) -> AccountTransition {
+        AccountTransition {
+            previous_info: previous.map(|b| AccountInfo::from_balance(U256::from(b))),
+            info: balance.map(|b| AccountInfo::from_balance(U256::from(b))),
+            storage: storage
+                .iter()
+                .map(|(key, original, present)| {
+                    (
+                        U256::from(*key),
+                        crate::primitives::StorageSlot::new_changed(
+                            U256::from(*original),
+                            U256::from(*present),
+                        ),
+                    )
+                })
+                .collect(),
+            created: false,
+            destroyed,
+            wiped_storage: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_reverts_from_block_transitions() {
+        let changed = Address::with_last_byte(1);
+        let destroyed = Address::with_last_byte(2);
+        let touched = Address::with_last_byte(3);
+        let first = HashMap::from([
+            (changed, transition(Some(10), Some(5), &[(1, 7, 8)], false)),
+            (destroyed, transition(Some(3), Some(3), &[(1, 4, 5)], false)),
+            (touched, transition(Some(1), Some(1), &[], false)),
+        ]);
+        let mut destroy = transition(Some(3), None, &[], true);
+        destroy.wiped_storage = HashMap::from([(U256::from(3), U256::from(9))]);
+        let second = HashMap::from([
+            (changed, transition(Some(5), Some(2), &[(1, 8, 9)], false)),
+            (destroyed, destroy),
+        ]);
+        let third = HashMap::from([(destroyed, transition(None, Some(1), &[(2, 0, 6)], false))]);
+
+        let reverts = PlainStateReverts::from_block_transitions([&first, &second, &third]);
+        assert_eq!(
+            reverts.accounts,
+            vec![vec![
+                (changed, Some(AccountInfo::from_balance(U256::from(10)))),
+                (destroyed, Some(AccountInfo::from_balance(U256::from(3)))),
+            ]]
+        );
+        assert_eq!(
+            reverts.storage,
+            vec![vec![
+                PlainStorageRevert {
+                    address: changed,
+                    wiped: false,
+                    storage_revert: vec![(U256::from(1), RevertToSlot::Some(U256::from(7)))],
+                },
+                PlainStorageRevert {
+                    address: destroyed,
+                    wiped: true,
+                    storage_revert: vec![
+                        (U256::from(1), RevertToSlot::Some(U256::from(4))),
+                        (U256::from(3), RevertToSlot::Some(U256::from(9))),
+                        (U256::from(2), RevertToSlot::Destroyed),
+                    ],
+                },
+            ]]
+        );
+    }
+}
@@ -3,22 +3,23 @@ use crate::{
     db::{Database, DatabaseCommit, EmptyDB},
     handler::Handler,
     interpreter::{
-        opcode::InstructionTables, Host, Interpreter, InterpreterAction, SStoreResult,
-        SelfDestructResult, SharedMemory,
+        gas::WitnessKey, opcode::InstructionTables, Host, Interpreter, InterpreterAction,
+        SStoreResult, SelfDestructResult, SharedMemory,
     },
     primitives::{
-        specification::SpecId, Address, BlockEnv, Bytecode, CfgEnv, EVMError, EVMResult, Env,
-        EnvWithHandlerCfg, ExecutionResult, HandlerCfg, Log, ResultAndState, TransactTo, TxEnv,
-        B256, U256,
+        specification::SpecId, Address, BlockEnv, Bytecode, Bytes, CfgEnv, EVMError, EVMResult,
+        Env, EnvWithHandlerCfg, ExecutionResult, HandlerCfg, Log, Requests, ResultAndState,
+        TransactTo, TxEnv, B256, BEACON_ROOTS_ADDRESS, BLOCKHASH_STORAGE_ADDRESS,
+        CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, SYSTEM_ADDRESS, SYSTEM_CALL_GAS_LIMIT, U256,
+        WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
     },
     Context, ContextWithHandlerCfg, Frame, FrameOrResult, FrameResult,
 };
 use core::fmt;
 use revm_interpreter::{CallInputs, CreateInputs};
-use std::vec::Vec;
+use std::{format, vec::Vec};
 
-/// EVM call stack limit.
-pub const CALL_STACK_LIMIT: u64 = 1024;
+pub use crate::primitives::CALL_STACK_LIMIT;
 
 /// EVM instance containing both internal EVM context and external context
 /// and the handler that dictates the logic of EVM (or hardfork specification).
@@ -50,6 +51,81 @@ impl<EXT, DB: Database + DatabaseCommit> Evm<'_, EXT, DB> {
         self.context.evm.db.commit(state);
         Ok(result)
     }
+
+    /// Stores the parent beacon block root in the EIP-4788 beacon roots contract and commits the
+    /// change to the database.
+    ///
+    /// Called before the first transaction of the block, the call is free and is not part of the
+    /// block receipts. Does nothing before Cancun and in the genesis block.
+    pub fn transact_beacon_root_commit(
+        &mut self,
+        parent_beacon_block_root: B256,
+    ) -> Result<(), EVMError<DB::Error>> {
+        if !self.spec_id().is_enabled_in(SpecId::CANCUN) || self.block().number.is_zero() {
+            return Ok(());
+        }
+        self.block_system_call_commit(BEACON_ROOTS_ADDRESS, parent_beacon_block_root.into())?;
+        Ok(())
+    }
+
+    /// Stores the parent hash in the EIP-2935 history storage contract and commits the change
+    /// to the database.
+    ///
+    /// Called before the first transaction of the block, does nothing before Prague and in the
+    /// genesis block.
+    pub fn transact_parent_hash_commit(
+        &mut self,
+        parent_hash: B256,
+    ) -> Result<(), EVMError<DB::Error>> {
+        if !self.spec_id().is_enabled_in(SpecId::PRAGUE) || self.block().number.is_zero() {
+            return Ok(());
+        }
+        self.block_system_call_commit(BLOCKHASH_STORAGE_ADDRESS, parent_hash.into())?;
+        Ok(())
+    }
+
+    /// Dequeues the EIP-7002 withdrawal and EIP-7251 consolidation requests of the block and
+    /// commits the changes of the request contracts to the database.
+    ///
+    /// Called after the last transaction of the block, returns no requests before Prague.
+    pub fn transact_requests_commit(&mut self) -> Result<Requests, EVMError<DB::Error>> {
+        if !self.spec_id().is_enabled_in(SpecId::PRAGUE) {
+            return Ok(Requests::default());
+        }
+        let withdrawals =
+            self.block_system_call_commit(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, Bytes::new())?;
+        let consolidations =
+            self.block_system_call_commit(CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, Bytes::new())?;
+        Requests::decode(&withdrawals, &consolidations)
+            .ok_or_else(|| EVMError::Custom("invalid requests output".into()))
+    }
+
+    /// Calls the contract with the system call semantics and commits the changes to the
+    /// database, see [Evm::system_call].
+    pub fn system_call_commit(
+        &mut self,
+        contract: Address,
+        data: Bytes,
+    ) -> Result<ExecutionResult, EVMError<DB::Error>> {
+        let ResultAndState { result, state } = self.system_call(contract, data)?;
+        self.context.evm.db.commit(state);
+        Ok(result)
+    }
+
+    /// Calls the system contract of the block, commits the changes and returns its output, the
+    /// block is invalid if the call fails.
+    fn block_system_call_commit(
+        &mut self,
+        contract: Address,
+        data: Bytes,
+    ) -> Result<Bytes, EVMError<DB::Error>> {
+        match self.system_call_commit(contract, data)? {
+            ExecutionResult::Success { output, .. } => Ok(output.into_data()),
+            result => Err(EVMError::Custom(format!(
+                "system call to {contract} failed: {result:?}"
+            ))),
+        }
+    }
 }
 
 impl<'a> Evm<'a, (), EmptyDB> {
@@ -103,12 +179,17 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
     /// This function will not validate the transaction.
     #[inline]
     pub fn transact_preverified(&mut self) -> EVMResult<DB::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = transact_span(&self.context.evm.env);
         let initial_gas_spend = self
             .handler
             .validation()
             .initial_tx_gas(&self.context.evm.env)?;
         let output = self.transact_preverified_inner(initial_gas_spend);
-        self.handler.post_execution().end(&mut self.context, output)
+        let output = self.handler.post_execution().end(&mut self.context, output);
+        #[cfg(feature = "tracing")]
+        trace_transact_end(&output);
+        output
     }
 
     /// Returns the reference of handler configuration
@@ -170,6 +251,8 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
     /// This function will validate the transaction.
     #[inline]
     pub fn transact(&mut self) -> EVMResult<DB::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = transact_span(&self.context.evm.env);
         self.handler.validation().env(&self.context.evm.env)?;
         let initial_gas_spend = self
             .handler
@@ -180,7 +263,73 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
             .tx_against_state(&mut self.context)?;
 
         let output = self.transact_preverified_inner(initial_gas_spend);
-        self.handler.post_execution().end(&mut self.context, output)
+        let output = self.handler.post_execution().end(&mut self.context, output);
+        #[cfg(feature = "tracing")]
+        trace_transact_end(&output);
+        output
+    }
+
+    /// Calls the contract with the system call semantics, used for the system contracts of
+    /// EIP-4788, EIP-2935 and EIP-7002 and for the custom block hooks of the chains.
+    ///
+    /// The caller is [SYSTEM_ADDRESS] and the gas limit is [SYSTEM_CALL_GAS_LIMIT]. The call is
+    /// not validated, there are no nonce, balance or base fee checks and no gas fees. The
+    /// transaction environment is restored after the call.
+    ///
+    /// The system address is not part of the returned state, it is only loaded by the call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use revm::{primitives::{address, Bytes}, Evm};
+    ///
+    /// let mut evm = Evm::builder().build();
+    /// let result = evm
+    ///     .system_call(address!("000F3df6D732807Ef1319fB7B8bB8522d0Beac02"), Bytes::new())
+    ///     .unwrap();
+    /// assert!(result.result.is_success());
+    /// ```
+    pub fn system_call(&mut self, contract: Address, data: Bytes) -> EVMResult<DB::Error> {
+        let system_tx = TxEnv {
+            caller: SYSTEM_ADDRESS,
+            transact_to: TransactTo::Call(contract),
+            data,
+            gas_limit: SYSTEM_CALL_GAS_LIMIT,
+            ..Default::default()
+        };
+        let tx = core::mem::replace(&mut self.context.evm.env.tx, system_tx);
+        let output = self.system_call_inner();
+        let mut output = self.handler.post_execution().end(&mut self.context, output);
+        self.context.evm.env.tx = tx;
+        if let Ok(ResultAndState { state, .. }) = &mut output {
+            state.remove(&SYSTEM_ADDRESS);
+        }
+        output
+    }
+
+    fn system_call_inner(&mut self) -> EVMResult<DB::Error> {
+        let ctx = &mut self.context;
+        // journal is configured as in `load_accounts`, but no account is warmed.
+        ctx.evm
+            .journaled_state
+            .set_spec_id(self.handler.cfg.spec_id);
+        ctx.evm
+            .journaled_state
+            .set_chain_config(&ctx.evm.env.cfg.chain);
+        let precompiles = self.handler.pre_execution().load_precompiles();
+        ctx.evm.set_precompiles(precompiles);
+
+        let inputs = CallInputs::new_boxed(&ctx.evm.env.tx, SYSTEM_CALL_GAS_LIMIT).unwrap();
+        let mut result = match self.handler.execution().call(ctx, inputs)? {
+            FrameOrResult::Frame(first_frame) => self.start_the_loop(first_frame)?,
+            FrameOrResult::Result(result) => result,
+        };
+
+        let ctx = &mut self.context;
+        self.handler
+            .execution()
+            .last_frame_return(ctx, &mut result)?;
+        self.handler.post_execution().output(ctx, result)
     }
 
     /// Modify spec id, this will create new EVM that matches this spec id.
@@ -306,6 +455,8 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
                     stack_frame = call_stack.last_mut().unwrap();
                 }
                 FrameOrResult::Result(result) => {
+                    #[cfg(feature = "tracing")]
+                    trace_frame_end(&self.context, &result);
                     let Some(top_frame) = call_stack.last_mut() else {
                         // Break the look if there are no more frames.
                         return Ok(result);
@@ -361,7 +512,11 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         // Starts the main running loop.
         let mut result = match first_frame_or_result {
             FrameOrResult::Frame(first_frame) => self.start_the_loop(first_frame)?,
-            FrameOrResult::Result(result) => result,
+            FrameOrResult::Result(result) => {
+                #[cfg(feature = "tracing")]
+                trace_frame_end(&self.context, &result);
+                result
+            }
         };
 
         let ctx = &mut self.context;
@@ -381,6 +536,47 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
     }
 }
 
+/// Enters the `transact` span that covers the whole transaction.
+#[cfg(feature = "tracing")]
+fn transact_span(env: &Env) -> tracing::span::EnteredSpan {
+    tracing::debug_span!(
+        target: "revm",
+        "transact",
+        caller = %env.tx.caller,
+        transact_to = ?env.tx.transact_to,
+        value = %env.tx.value,
+        gas_limit = env.tx.gas_limit,
+    )
+    .entered()
+}
+
+/// Emits the outcome of the transaction.
+#[cfg(feature = "tracing")]
+fn trace_transact_end<DBError>(output: &EVMResult<DBError>) {
+    match output {
+        Ok(ResultAndState { result, .. }) => tracing::debug!(
+            target: "revm",
+            success = result.is_success(),
+            gas_used = result.gas_used(),
+            "transaction end"
+        ),
+        Err(_) => tracing::debug!(target: "revm", "transaction error"),
+    }
+}
+
+/// Emits the outcome of the call or create frame.
+#[cfg(feature = "tracing")]
+fn trace_frame_end<EXT, DB: Database>(context: &Context<EXT, DB>, result: &FrameResult) {
+    let interpreter_result = result.interpreter_result();
+    tracing::trace!(
+        target: "revm::frame",
+        depth = context.evm.journaled_state.depth() + 1,
+        result = ?interpreter_result.result,
+        gas_used = interpreter_result.gas.spend(),
+        "frame end"
+    );
+}
+
 impl<EXT, DB: Database> Host for Evm<'_, EXT, DB> {
     fn env_mut(&mut self) -> &mut Env {
         &mut self.context.evm.env
@@ -465,4 +661,786 @@ impl<EXT, DB: Database> Host for Evm<'_, EXT, DB> {
             .map_err(|e| self.context.evm.error = Err(e))
             .ok()
     }
+
+    fn witness_read(&mut self, key: WitnessKey) -> u64 {
+        self.context.evm.journaled_state.access_witness.read(key)
+    }
+
+    fn witness_write(&mut self, key: WitnessKey, fill: bool) -> u64 {
+        self.context
+            .evm
+            .journaled_state
+            .access_witness
+            .write(key, fill)
+    }
+
+    #[cfg(feature = "experimental-eips")]
+    fn nonce(&mut self, address: Address) -> Option<(u64, bool)> {
+        self.context
+            .evm
+            .nonce(address)
+            .map_err(|e| self.context.evm.error = Err(e))
+            .ok()
+    }
+
+    #[cfg(feature = "experimental-eips")]
+    fn ecrecover(
+        &mut self,
+        sig: &crate::primitives::alloy_primitives::B512,
+        recid: u8,
+        msg: &B256,
+    ) -> Option<Address> {
+        crate::precompile::recover_address(sig, recid, msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{in_memory_db::AccountState, CacheDB, DatabaseRef, EmptyDB},
+        primitives::{bytes, hex, keccak256, AccountInfo, HaltReason, WithdrawalRequest},
+    };
+
+    fn contract(code: Bytes) -> AccountInfo {
+        let code = Bytecode::new_raw(code);
+        AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code)
+    }
+
+    #[test]
+    fn transact_requests() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        // returns one withdrawal request that is appended to the code.
+        let request = [[0x11; 20].as_slice(), &[0x22; 48], &7u64.to_be_bytes()].concat();
+        let code = [bytes!("604c600c600039604c6000f3").as_ref(), &request].concat();
+        db.insert_account_info(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, contract(code.into()));
+        // stores the caller to the slot zero.
+        db.insert_account_info(
+            CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+            contract(bytes!("3360005500")),
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .build();
+        assert!(evm.transact_requests_commit().unwrap().is_empty());
+
+        let mut evm = evm.modify().with_spec_id(SpecId::PRAGUE).build();
+        let requests = evm.transact_requests_commit().unwrap();
+        assert_eq!(
+            requests.withdrawals,
+            [WithdrawalRequest::decode(&request).unwrap()]
+        );
+        assert!(requests.consolidations.is_empty());
+        assert_eq!(
+            evm.db()
+                .storage_ref(CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, U256::ZERO)
+                .unwrap(),
+            U256::from_be_slice(SYSTEM_ADDRESS.as_slice())
+        );
+        // system address is only loaded.
+        assert_eq!(
+            evm.db().accounts[&SYSTEM_ADDRESS].account_state,
+            AccountState::NotExisting
+        );
+        // transaction environment is restored.
+        assert_eq!(evm.tx(), &TxEnv::default());
+    }
+
+    #[test]
+    fn transact_parent_hash() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        // EIP-2935 history storage contract.
+        db.insert_account_info(
+            BLOCKHASH_STORAGE_ADDRESS,
+            contract(bytes!(
+                "3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111"
+                "604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff6001430306"
+                "5500"
+            )),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_block_env(|block| block.number = U256::from(10_000))
+            .build();
+
+        let parent_hash = B256::repeat_byte(0x11);
+        evm.transact_parent_hash_commit(parent_hash).unwrap();
+        assert_eq!(
+            evm.db()
+                .storage_ref(BLOCKHASH_STORAGE_ADDRESS, U256::from(9_999 % 8191))
+                .unwrap(),
+            U256::from_be_bytes(parent_hash.0)
+        );
+
+        let block_hash = |evm: &mut Evm<'_, (), _>, number: u64| {
+            evm.context.evm.block_hash(U256::from(number)).unwrap()
+        };
+        assert_eq!(block_hash(&mut evm, 9_999), parent_hash);
+        // blocks that are not in the ring buffer fall back to the database.
+        assert_eq!(
+            block_hash(&mut evm, 9_998),
+            EmptyDB::default()
+                .block_hash_ref(U256::from(9_998))
+                .unwrap()
+        );
+        assert_eq!(block_hash(&mut evm, 9_000), B256::ZERO);
+    }
+
+    #[test]
+    fn transact_beacon_root() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        // EIP-4788 beacon roots contract.
+        db.insert_account_info(
+            BEACON_ROOTS_ADDRESS,
+            contract(bytes!(
+                "3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f3580"
+                "1560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b6200"
+                "1fff42064281555f359062001fff015500"
+            )),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_block_env(|block| {
+                block.number = U256::from(1);
+                block.timestamp = U256::from(12);
+            })
+            .build();
+
+        let root = B256::repeat_byte(0x22);
+        evm.transact_beacon_root_commit(root).unwrap();
+        let storage = |slot: u64| {
+            evm.db()
+                .storage_ref(BEACON_ROOTS_ADDRESS, U256::from(slot))
+                .unwrap()
+        };
+        // ring buffers of the timestamps and the roots.
+        assert_eq!(storage(12), U256::from(12));
+        assert_eq!(storage(12 + 8191), U256::from_be_bytes(root.0));
+    }
+
+    #[test]
+    fn system_call() {
+        let target = Address::repeat_byte(0x33);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // returns the caller.
+        db.insert_account_info(target, contract(bytes!("335f5260205ff3")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| tx.caller = Address::repeat_byte(0x44))
+            .build();
+
+        let ResultAndState { result, state } = evm.system_call(target, Bytes::new()).unwrap();
+        assert_eq!(
+            result.output().unwrap()[..],
+            B256::left_padding_from(SYSTEM_ADDRESS.as_slice())[..]
+        );
+        assert!(result.gas_used() > 0);
+        assert!(!state.contains_key(&SYSTEM_ADDRESS));
+        assert_eq!(evm.tx().caller, Address::repeat_byte(0x44));
+    }
+
+    #[test]
+    fn system_call_chain_config() {
+        let target = Address::repeat_byte(0x33);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // creates the contract from the initcode `PUSH1 1 PUSH0 RETURN` and stores its address
+        // to the slot zero.
+        db.insert_account_info(target, contract(bytes!("6360015ff35f526004601c5ff05f5500")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| cfg.chain.call_depth_limit = 0)
+            .build();
+
+        // creation is a nested frame, it fails if only the top frame is allowed.
+        let ResultAndState { result, state } = evm.system_call(target, Bytes::new()).unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&target].storage[&U256::ZERO].present_value,
+            U256::ZERO
+        );
+        assert_eq!(evm.context.evm.journaled_state.call_depth_limit, 0);
+        assert_eq!(evm.context.evm.journaled_state.spec, SpecId::CANCUN);
+    }
+
+    #[test]
+    fn eof_call() {
+        let target = Address::repeat_byte(0x33);
+        let legacy = Address::repeat_byte(0x34);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // stores `1 + 2` to the slot zero with the function that adds two to non zero inputs.
+        db.insert_account_info(
+            target,
+            contract(bytes!(
+                "ef000101000802000200090009040000000080000201010002"
+                "6001e3000160005500"
+                "80e10001e4600201e4"
+            )),
+        );
+        // stores the code size of the EOF contract to the slot zero.
+        let code = [&[0x73][..], target.as_slice(), &hex!("3b5f5500")[..]].concat();
+        db.insert_account_info(legacy, contract(code.into()));
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(target))
+            .build();
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&target].storage[&U256::ZERO].present_value,
+            U256::from(3)
+        );
+        // validated container is cached, the account code is not changed.
+        let code = state[&target].info.code.as_ref().unwrap();
+        assert!(!code.is_eof());
+        assert!(evm.context.evm.eof_cache[&code.hash_slow()].is_eof());
+
+        evm.tx_mut().transact_to = TransactTo::Call(legacy);
+        let ResultAndState { state, .. } = evm.transact().unwrap();
+        assert_eq!(
+            state[&legacy].storage[&U256::ZERO].present_value,
+            U256::from(2)
+        );
+
+        // EOF is not enabled, container halts on the `0xEF` opcode.
+        let mut evm = evm.modify().with_spec_id(SpecId::PRAGUE).build();
+        evm.tx_mut().transact_to = TransactTo::Call(target);
+        let ResultAndState { result, .. } = evm.transact().unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeNotFound,
+                gas_used: evm.tx().gas_limit
+            }
+        );
+    }
+
+    #[test]
+    fn eof_invalid_container_cached() {
+        let target = Address::repeat_byte(0x33);
+        let code = bytes!("ef000100");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(target, contract(code.clone()));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(target))
+            .build();
+
+        // invalid container runs as legacy code and halts on the `0xEF` opcode.
+        for _ in 0..2 {
+            let ResultAndState { result, .. } = evm.transact().unwrap();
+            assert!(matches!(
+                result,
+                ExecutionResult::Halt {
+                    reason: HaltReason::OpcodeNotFound,
+                    ..
+                }
+            ));
+        }
+        assert_eq!(evm.context.evm.eof_cache.len(), 1);
+        assert!(!evm.context.evm.eof_cache[&keccak256(&code)].is_eof());
+    }
+
+    #[test]
+    fn eof_extcall() {
+        let [caller, callee, reverter, invalid] =
+            [0x33, 0x34, 0x35, 0x36].map(Address::repeat_byte);
+        // stores the status codes of the calls to the slots zero to three.
+        let push_target = |target: Address| [&[0x73][..], target.as_slice()].concat();
+        let code = [
+            &hex!("5f5f5f")[..],
+            &push_target(callee),
+            &hex!("f8 5f55"),
+            &hex!("5f5f"),
+            &push_target(callee),
+            &hex!("f9 600155"),
+            &hex!("5f5f5f"),
+            &push_target(reverter),
+            &hex!("f8 600255"),
+            &hex!("5f5f5f"),
+            &push_target(invalid),
+            &hex!("f8 600355 00"),
+        ]
+        .concat();
+        let container = [
+            &hex!("ef0001 010004 020001")[..],
+            &(code.len() as u16).to_be_bytes(),
+            &hex!("040000 00 00800004"),
+            &code,
+        ]
+        .concat();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(caller, contract(container.into()));
+        db.insert_account_info(callee, contract(bytes!("00")));
+        db.insert_account_info(reverter, contract(bytes!("5f5ffd")));
+        db.insert_account_info(invalid, contract(bytes!("fe")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(caller))
+            .build();
+
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        let storage = &state[&caller].storage;
+        // success, delegate call to the legacy code, revert and failure.
+        for (slot, status) in [0, 1, 1, 2].into_iter().enumerate() {
+            assert_eq!(
+                storage
+                    .get(&U256::from(slot))
+                    .map_or(U256::ZERO, |slot| slot.present_value),
+                U256::from(status)
+            );
+        }
+    }
+
+    #[test]
+    fn eof_data_and_exchange() {
+        let target = Address::repeat_byte(0x37);
+        // stores the first data word, the data size and the exchanged values to the slots zero
+        // to three.
+        let code = hex!("d10000 5f55 d2 600155 6001 6002 6003 e800 50 600255 600355 00");
+        let data = [0x11; 32];
+        let container = [
+            &hex!("ef0001 010004 020001")[..],
+            &(code.len() as u16).to_be_bytes(),
+            &hex!("040020 00 00800003"),
+            &code,
+            &data,
+        ]
+        .concat();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(target, contract(container.into()));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(target))
+            .build();
+
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        let storage = &state[&target].storage;
+        let expected = [
+            U256::from_be_bytes(data),
+            U256::from(32),
+            U256::from(1),
+            U256::from(2),
+        ];
+        for (slot, value) in expected.into_iter().enumerate() {
+            assert_eq!(storage[&U256::from(slot)].present_value, value);
+        }
+    }
+
+    #[test]
+    fn eof_create() {
+        let [deployer, tx_deployer] = [0x38, 0x39].map(Address::repeat_byte);
+        // runtime container with `STOP` that declares two bytes of data.
+        let runtime = hex!("ef0001 010004 020001 0001 040002 00 00800000 00");
+        // writes 0xaabb to memory and returns the runtime container with it as the data.
+        let initcode = [
+            &hex!("ef0001 010004 020001 000b 030001 0014 040000 00 00800002")[..],
+            &hex!("61aabb 5f 52 6002 601e ee00"),
+            &runtime,
+        ]
+        .concat();
+        // EOFCREATE of the container section and TXCREATE of the transaction initcode store
+        // the created address to the slot zero.
+        let eofcreate = [
+            &hex!("ef0001 010004 020001 0009 030001")[..],
+            &(initcode.len() as u16).to_be_bytes(),
+            &hex!("040000 00 00800004 5f5f5f5f ec00 5f55 00"),
+            &initcode,
+        ]
+        .concat();
+        let txcreate = [
+            &hex!("ef0001 010004 020001 0029 040000 00 00800005 5f5f5f5f 7f")[..],
+            keccak256(&initcode).as_slice(),
+            &hex!("ed 5f55 00"),
+        ]
+        .concat();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(deployer, contract(eofcreate.into()));
+        db.insert_account_info(tx_deployer, contract(txcreate.into()));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(deployer);
+                tx.eof_initcodes = vec![initcode.clone().into()];
+            })
+            .build();
+
+        for deployer in [deployer, tx_deployer] {
+            evm.tx_mut().transact_to = TransactTo::Call(deployer);
+            let ResultAndState { result, state } = evm.transact().unwrap();
+            assert!(result.is_success(), "{result:?}");
+            let created = deployer.create2_from_code([0; 32], &initcode);
+            assert_eq!(
+                state[&deployer].storage[&U256::ZERO].present_value,
+                U256::from_be_slice(created.as_slice())
+            );
+            assert_eq!(state[&deployer].info.nonce, 2);
+            let code = state[&created].info.code.as_ref().unwrap();
+            assert!(code.is_eof());
+            assert_eq!(
+                code.original_bytes()[..],
+                hex!("ef0001 010004 020001 0001 040002 00 00800000 00 aabb")
+            );
+        }
+    }
+
+    #[test]
+    fn verkle_storage_witness() {
+        use crate::interpreter::gas::{WITNESS_BRANCH_COST, WITNESS_CHUNK_COST};
+
+        let address = Address::repeat_byte(0x50);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // loads the slot zero twice.
+        db.insert_account_info(address, contract(bytes!("5f545f5400")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::LATEST)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(address))
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), 21_000 + 2 + 2_100 + 2 + 100);
+
+        // the first load is charged for the slot leaf instead of the cold slot, the header
+        // branch is already charged for the first code chunk.
+        let mut evm = evm.modify().with_spec_id(SpecId::VERKLE).build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.gas_used(),
+            21_000
+                + WITNESS_BRANCH_COST
+                + WITNESS_CHUNK_COST
+                + 2
+                + WITNESS_CHUNK_COST
+                + 100
+                + 2
+                + 100
+        );
+    }
+
+    #[test]
+    fn verkle_code_chunks_witness() {
+        use crate::interpreter::gas::{WITNESS_BRANCH_COST, WITNESS_CHUNK_COST};
+
+        let address = Address::repeat_byte(0x50);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // `PUSH32` data spans into the second code chunk.
+        db.insert_account_info(
+            address,
+            contract(bytes!(
+                "7f00000000000000000000000000000000000000000000000000000000000000005000"
+            )),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::LATEST)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(address))
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), 21_000 + 3 + 2);
+
+        // both chunks are charged once, `POP` and `STOP` are in the already read chunk.
+        let mut evm = evm.modify().with_spec_id(SpecId::VERKLE).build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.gas_used(),
+            21_000 + WITNESS_BRANCH_COST + 2 * WITNESS_CHUNK_COST + 3 + 2
+        );
+    }
+
+    #[test]
+    fn chain_config_create_costs() {
+        let address = Address::repeat_byte(0x50);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // creates the contract with one byte of code from the initcode `PUSH1 1 PUSH0 RETURN`
+        // and stores its address to the slot zero.
+        db.insert_account_info(
+            address,
+            contract(bytes!("6360015ff35f526004601c5ff05f5500")),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(address))
+            .build();
+        let gas_used = evm.transact().unwrap().result.gas_used();
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| {
+                cfg.chain.create_gas = 50_000;
+                cfg.chain.code_deposit_gas = 300;
+            })
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(result.gas_used(), gas_used + 18_000 + 100);
+
+        // creation is a nested frame, it fails if only the top frame is allowed.
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.call_depth_limit = 0)
+            .build();
+        let state = evm.transact().unwrap().state;
+        assert_eq!(
+            state[&address].storage[&U256::ZERO].present_value,
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn chain_config_create_tx_cost() {
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::create())
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), 53_000);
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.create_gas = 50_000)
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), 71_000);
+    }
+
+    #[test]
+    fn chain_config_selfdestruct_to_self() {
+        use crate::primitives::{SelfDestructToSelf, KECCAK_EMPTY};
+
+        let address = Address::repeat_byte(0x50);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // `SELFDESTRUCT` with the contract itself as the target.
+        let mut info = contract(bytes!("30ff"));
+        info.balance = U256::from(5);
+        db.insert_account_info(address, info);
+
+        for (policy, balance) in [
+            (SelfDestructToSelf::Keep, U256::from(5)),
+            (SelfDestructToSelf::Burn, U256::ZERO),
+        ] {
+            let mut evm = Evm::builder()
+                .with_db(db.clone())
+                .with_spec_id(SpecId::SHANGHAI)
+                .modify_cfg_env(|cfg| cfg.chain.selfdestruct_to_self = policy)
+                .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(address))
+                .build();
+            assert!(evm.transact_commit().unwrap().is_success());
+            let account = &evm.db().accounts[&address];
+            assert_eq!(account.info.balance, balance);
+            assert_eq!(
+                account.info.code_hash == KECCAK_EMPTY,
+                policy == SelfDestructToSelf::Burn
+            );
+        }
+    }
+
+    #[test]
+    fn chain_config_code_size_limits() {
+        use crate::primitives::{InvalidTransaction, MAX_INITCODE_SIZE};
+
+        // `JUMPDEST`s over the initcode limit followed by the return of one byte over the code
+        // size limit.
+        let mut initcode = vec![0x5b; MAX_INITCODE_SIZE - 4];
+        initcode.extend(hex!("616001 5f f3"));
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::create();
+                tx.data = initcode.into();
+            })
+            .build();
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CreateInitCodeSizeLimit
+            ))
+        ));
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.max_initcode_size = None)
+            .build();
+        assert_eq!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                gas_used: evm.tx().gas_limit,
+            }
+        );
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.max_code_size = None)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+    }
+
+    #[test]
+    fn chain_config_refund_policy() {
+        use crate::primitives::RefundPolicy;
+
+        let address = Address::repeat_byte(0x50);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // clears the slot zero.
+        db.insert_account_info(address, contract(bytes!("5f5f5500")));
+        db.insert_account_storage(address, U256::ZERO, U256::from(1))
+            .unwrap();
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(address))
+            .build();
+        let spent = 21_000 + 2 + 2 + 2_100 + 2_900;
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), spent - 4_800);
+
+        // refund of 15000 is limited to the half of the gas spent.
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.refund_policy = Some(RefundPolicy::PreLondon))
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), spent - spent / 2);
+    }
+
+    #[test]
+    fn chain_config_retained_gas() {
+        let [caller, callee] = [0x50, 0x51].map(Address::repeat_byte);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // calls the callee with all gas, the callee stores its gas to the slot zero.
+        let code = [
+            &hex!("5f5f5f5f5f 73")[..],
+            callee.as_slice(),
+            &hex!("5a f1 00"),
+        ]
+        .concat();
+        db.insert_account_info(caller, contract(code.into()));
+        db.insert_account_info(callee, contract(bytes!("5a5f5500")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(caller);
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        let state = evm.transact().unwrap().state;
+        let forwarded = state[&callee].storage[&U256::ZERO].present_value;
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.chain.retained_gas_divisor = None)
+            .build();
+        let state = evm.transact().unwrap().state;
+        let remaining = state[&callee].storage[&U256::ZERO].present_value;
+        // callee gets all of the remaining gas, `GAS` itself costs 2.
+        assert_eq!(
+            forwarded,
+            remaining - (remaining + U256::from(2)) / U256::from(64)
+        );
+    }
+
+    #[cfg(feature = "experimental-eips")]
+    #[test]
+    fn auth_and_authcall() {
+        use k256::ecdsa::SigningKey;
+
+        let [invoker, target] = [0x40, 0x41].map(Address::repeat_byte);
+        let key = SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let public = key.verifying_key().to_encoded_point(false);
+        let authority = Address::from_slice(&keccak256(&public.as_bytes()[1..])[12..]);
+
+        // magic, chain id, authority nonce, invoker and zero commit.
+        let msg = keccak256(
+            [
+                &[0x04][..],
+                &U256::from(1).to_be_bytes::<32>(),
+                &[0; 32],
+                invoker.into_word().as_slice(),
+                &[0; 32],
+            ]
+            .concat(),
+        );
+        let (sig, recid) = key.sign_prehash_recoverable(msg.as_slice()).unwrap();
+        let sig = sig.to_bytes();
+
+        // writes the signature to memory and stores the results of `AUTH` and `AUTHCALL` to the
+        // slots zero and one.
+        let code = [
+            &[0x60, recid.to_byte(), 0x5f, 0x53, 0x7f][..],
+            &sig[..32],
+            &hex!("6001 52 7f"),
+            &sig[32..],
+            &hex!("6021 52 6061 5f 73"),
+            authority.as_slice(),
+            &hex!("f6 5f55 5f5f5f5f5f 73"),
+            target.as_slice(),
+            &hex!("5f f7 600155 00"),
+        ]
+        .concat();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(invoker, contract(code.into()));
+        // stores the caller to the slot zero.
+        db.insert_account_info(target, contract(bytes!("335f5500")));
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_tx_env(|tx| tx.transact_to = TransactTo::Call(invoker))
+            .build();
+
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(
+            state[&invoker].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+        assert_eq!(
+            state[&invoker].storage[&U256::from(1)].present_value,
+            U256::from(1)
+        );
+        assert_eq!(
+            state[&target].storage[&U256::ZERO].present_value,
+            U256::from_be_slice(authority.as_slice())
+        );
+
+        // `AUTHCALL` halts without `AUTH`.
+        let code = [
+            &hex!("5f5f5f5f5f 73")[..],
+            target.as_slice(),
+            &hex!("5f f7 00"),
+        ]
+        .concat();
+        evm.db_mut()
+            .insert_account_info(invoker, contract(code.into()));
+        let result = evm.transact().unwrap().result;
+        assert!(
+            matches!(
+                result,
+                ExecutionResult::Halt {
+                    reason: HaltReason::AuthorizedNotSet,
+                    ..
+                }
+            ),
+            "{result:?}"
+        );
+    }
 }
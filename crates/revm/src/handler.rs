@@ -8,7 +8,11 @@ pub use handle_types::*;
 
 // Includes.
 use crate::{
-    interpreter::{opcode::InstructionTables, Host},
+    interpreter::{
+        instructions::host::code_chunks_instruction,
+        opcode::{make_boxed_instruction_table, make_instruction_table, InstructionTables},
+        Host,
+    },
     primitives::{db::Database, spec_to_generic, HandlerCfg, Spec, SpecId},
     Evm,
 };
@@ -58,9 +62,18 @@ impl<'a, EXT, DB: Database> EvmHandler<'a, EXT, DB> {
 
     /// Default handler for Ethereum mainnet.
     pub fn mainnet<SPEC: Spec>() -> Self {
+        let instruction_table = if SPEC::enabled(SpecId::VERKLE) {
+            // EIP-4762: code chunks of the executed instructions are charged.
+            InstructionTables::Boxed(make_boxed_instruction_table::<_, SPEC, _>(
+                make_instruction_table::<_, SPEC>(),
+                code_chunks_instruction,
+            ))
+        } else {
+            InstructionTables::new_plain::<SPEC>()
+        };
         Self {
             cfg: HandlerCfg::new(SPEC::SPEC_ID),
-            instruction_table: Some(InstructionTables::new_plain::<SPEC>()),
+            instruction_table: Some(instruction_table),
             registers: Vec::new(),
             validation: ValidationHandler::new::<SPEC>(),
             pre_execution: PreExecutionHandler::new::<SPEC>(),
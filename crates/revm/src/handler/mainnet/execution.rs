@@ -41,10 +41,11 @@ pub fn frame_return_with_refund_flag<SPEC: Spec>(
     // If config is set to disable gas refund, it will return 0.
     // If spec is set to london, it will decrease the maximum refund amount to 5th part of
     // gas spend. (Before london it was 2th part of gas spend)
+    // Chain config can override the refund policy and the quotient regardless of the spec.
     if refund_enabled {
         // EIP-3529: Reduction in refunds
-        gas.set_final_refund::<SPEC>()
-    };
+        gas.set_final_refund_with_quotient(env.cfg.chain.refund_quotient_for(SPEC::SPEC_ID));
+    }
 }
 
 /// Handle output of the transaction
@@ -115,7 +116,7 @@ pub fn create_return<SPEC: Spec, EXT, DB: Database>(
         &mut interpreter_result,
         frame.created_address,
         frame.frame_data.checkpoint,
-    );
+    )?;
     Ok(CreateOutcome::new(
         interpreter_result,
         Some(frame.created_address),
@@ -26,6 +26,10 @@ pub fn load_accounts<SPEC: Spec, EXT, DB: Database>(
 ) -> Result<(), EVMError<DB::Error>> {
     // set journaling state flag.
     context.evm.journaled_state.set_spec_id(SPEC::SPEC_ID);
+    context
+        .evm
+        .journaled_state
+        .set_chain_config(&context.evm.env.cfg.chain);
 
     // load coinbase
     // EIP-3651: Warm COINBASE. Starts the `COINBASE` address warm
@@ -37,7 +41,11 @@ pub fn load_accounts<SPEC: Spec, EXT, DB: Database>(
         )?;
     }
 
-    context.evm.load_access_list()?;
+    // EIP-2929: caller, callee and access list are warm.
+    context
+        .evm
+        .journaled_state
+        .initial_account_and_storage_load(&context.evm.env.tx, &mut context.evm.db)?;
     Ok(())
 }
 
@@ -1,34 +1,103 @@
 use crate::{
     interpreter::{CallInputs, CreateInputs, Interpreter},
-    primitives::{db::Database, Address, Log, U256},
+    primitives::{db::Database, Address, EVMError, Log, ResultAndState, U256},
     EvmContext,
 };
 use auto_impl::auto_impl;
 
-#[cfg(feature = "std")]
+mod access_list;
+mod call_tracer;
+mod cheatcodes;
+mod chrome_trace;
+mod coverage;
+mod create_recorder;
 mod customprinter;
+mod debugger;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
+mod flamegraph;
+mod four_byte;
+mod frame_gas;
 mod gas;
 mod handler_register;
+mod invariant;
+mod labels;
+mod mux_tracer;
 mod noop;
+mod opcode_stats;
+mod parity_tracer;
+mod prestate_tracer;
+mod recorder;
+#[cfg(feature = "serde")]
+mod serde_hex;
+mod stack;
+mod storage_access;
+mod stream;
+mod tracer_config;
+mod value_transfer;
 
 // Exports.
 
 pub use handler_register::{inspector_handle_register, inspector_instruction, GetInspector};
-use revm_interpreter::{CallOutcome, CreateOutcome};
+use revm_interpreter::{CallOutcome, CreateOutcome, InterpreterResult};
 
 /// [Inspector] implementations.
 pub mod inspectors {
-    #[cfg(feature = "std")]
-    pub use super::customprinter::CustomPrintTracer;
+    pub use super::access_list::AccessListInspector;
+    pub use super::call_tracer::{
+        CallKind, CallTraceFrame, CallTraceLog, CallTracer, CallTracerConfig,
+    };
+    pub use super::cheatcodes::CheatcodeInspector;
+    pub use super::chrome_trace::{
+        ChromeTrace, ChromeTraceInspector, TraceEvent, TraceEventArgs, TraceEventPhase,
+    };
+    pub use super::coverage::{CodeCoverage, CoverageInspector};
+    pub use super::create_recorder::{CreateRecord, CreateRecorder};
+    pub use super::customprinter::{CustomPrintTracer, StdoutWriter};
+    pub use super::debugger::{Breakpoint, DebuggerAction, DebuggerInspector, DebuggerPause};
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
+    pub use super::flamegraph::FlamegraphInspector;
+    pub use super::four_byte::FourByteInspector;
+    pub use super::frame_gas::{FrameGas, FrameGasInspector, OpcodeGas};
     pub use super::gas::GasInspector;
+    pub use super::invariant::InvariantInspector;
+    pub use super::labels::{AddressLabels, LabeledAddress};
+    pub use super::mux_tracer::{
+        FourByteTracerConfig, MuxTraceOutput, MuxTracer, MuxTracerConfig, PrestateOutput,
+        PrestateTracerConfig,
+    };
     pub use super::noop::NoOpInspector;
+    pub use super::opcode_stats::{OpcodeStat, OpcodeStatsInspector};
+    pub use super::parity_tracer::{
+        AccountDiff, ChangedValue, Delta, MemoryDelta, ParityAction, ParityCallAction,
+        ParityCallResult, ParityCallType, ParityCreateAction, ParityCreateResult,
+        ParitySelfdestructAction, ParityTrace, ParityTraceResult, ParityTraceResults, ParityTracer,
+        ParityTracerConfig, StateDiff, StorageDelta, VmExecutedOperation, VmInstruction, VmTrace,
+    };
+    pub use super::prestate_tracer::{
+        PrestateAccount, PrestateAccounts, PrestateDiff, PrestateTracer,
+    };
+    pub use super::recorder::{
+        Checkpoint, ExecutionRecorder, InterpreterSnapshot, RecordedState, StateDelta,
+    };
+    pub use super::stack::InspectorStack;
+    pub use super::storage_access::{StorageAccess, StorageAccessInspector, StorageAccessKind};
+    pub use super::stream::{LineSink, StreamEvent, StreamingTracer, TraceSink};
+    pub use super::tracer_config::TracerConfig;
+    pub use super::value_transfer::{ValueTransfer, ValueTransferInspector, ValueTransferKind};
 }
 
 /// EVM [Interpreter] callbacks.
+///
+/// Every hook that gets the [EvmContext] can read the current journaled state through
+/// `context.journaled_state`, e.g. balances, nonces and storage of the loaded accounts with
+/// [crate::JournaledState::get_account] and [crate::JournaledState::get_storage], call depth
+/// and logs emitted so far.
+///
+/// Any hook that gets the [EvmContext] can abort the transaction by setting `context.error`,
+/// e.g. to [EVMError::Custom] with the reason. Execution stops right after the hook returns
+/// and the transaction returns the error.
 #[auto_impl(&mut, Box)]
 pub trait Inspector<DB: Database> {
     /// Called before the interpreter is initialized.
@@ -65,7 +134,11 @@ pub trait Inspector<DB: Database> {
         let _ = context;
     }
 
-    /// Called when a log is emitted.
+    /// Called when a log is emitted by the `LOG0`-`LOG4` instructions.
+    ///
+    /// It is called as soon as the instruction is executed, so logs of the frames that
+    /// later revert are observed too. Logs that survive the transaction are returned
+    /// by [crate::JournaledState::finalize].
     #[inline]
     fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
         let _ = context;
@@ -103,6 +176,22 @@ pub trait Inspector<DB: Database> {
         outcome
     }
 
+    /// Called when a precompile has been executed, before [Inspector::call_end].
+    ///
+    /// `inputs.contract` is the address of the precompile. It is not called if the call failed
+    /// before the precompile was executed, e.g. on call depth limit or insufficient balance.
+    #[inline]
+    fn precompile(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        result: &InterpreterResult,
+    ) {
+        let _ = context;
+        let _ = inputs;
+        let _ = result;
+    }
+
     /// Called when a contract is about to be created.
     ///
     /// If this returns `Some` then the [CreateOutcome] is used to override the result of the creation.
@@ -136,10 +225,48 @@ pub trait Inspector<DB: Database> {
     }
 
     /// Called when a contract has been self-destructed with funds transferred to target.
+    ///
+    /// `value` is the balance of the contract before `SELFDESTRUCT`. After EIP-6780 it is
+    /// called even if the contract is not destroyed and only its balance is transferred.
     #[inline]
     fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
         let _ = contract;
         let _ = target;
         let _ = value;
     }
+
+    /// Called when an instruction changed the refund counter of the current frame.
+    ///
+    /// `opcode` is the instruction that made the change, `SSTORE` or `SELFDESTRUCT` before
+    /// London, and `delta` is the change of the counter that can be negative. Refund counter
+    /// after the change is `interp.gas.refunded()`. Refunds of the child frame are reported
+    /// in the child frame and are discarded if it reverts.
+    #[inline]
+    fn refund(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<DB>,
+        opcode: u8,
+        delta: i64,
+    ) {
+        let _ = interp;
+        let _ = context;
+        let _ = opcode;
+        let _ = delta;
+    }
+
+    /// Called once when the transaction has been executed, with the final result and the
+    /// finalized state.
+    ///
+    /// It is the place to flush the output of the tracer. It is not called if the
+    /// transaction failed validation.
+    #[inline]
+    fn transaction_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        result: &Result<ResultAndState, EVMError<DB::Error>>,
+    ) {
+        let _ = context;
+        let _ = result;
+    }
 }
@@ -0,0 +1,232 @@
+//! Access list generation inspector, can be used to implement `eth_createAccessList`.
+
+use crate::{
+    interpreter::{gas, opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, Address, HashSet, U256},
+    EvmContext, Inspector,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+/// [Inspector] that collects all accounts and storage slots accessed by the transaction
+/// into an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+///
+/// Caller, transaction target and precompiles are warm regardless of the access list
+/// and are not included, unless their storage slots were accessed.
+///
+/// Every account and slot that was first accessed cold is recorded, so
+/// [AccessListInspector::gas_saving] can estimate effect of the access list on the gas used.
+#[derive(Clone, Debug, Default)]
+pub struct AccessListInspector {
+    /// Accounts that are not added to the access list.
+    excluded: HashSet<Address>,
+    access_list: BTreeMap<Address, BTreeSet<U256>>,
+    /// Accounts from the access list that were first accessed cold.
+    cold_accounts: HashSet<Address>,
+    /// Slots from the access list that were first accessed cold.
+    cold_slots: HashSet<(Address, U256)>,
+}
+
+impl AccessListInspector {
+    /// Creates new inspector starting from the given access list.
+    ///
+    /// Passing the access list of the transaction keeps its entries in the output.
+    pub fn new(access_list: &[(Address, Vec<U256>)]) -> Self {
+        Self {
+            access_list: access_list
+                .iter()
+                .map(|(address, slots)| (*address, slots.iter().copied().collect()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the generated access list in the transaction format.
+    pub fn access_list(&self) -> Vec<(Address, Vec<U256>)> {
+        self.entries()
+            .map(|(address, slots)| (*address, slots.iter().copied().collect()))
+            .collect()
+    }
+
+    /// Returns the estimated gas saved by adding the generated access list to the transaction.
+    ///
+    /// Every account and slot of the access list adds the intrinsic cost, accesses
+    /// that were cold during the execution become warm. Result is negative if the
+    /// access list increases the gas used.
+    pub fn gas_saving(&self) -> i64 {
+        let mut saving = 0i64;
+        for (address, slots) in self.entries() {
+            saving -= gas::ACCESS_LIST_ADDRESS as i64;
+            if self.cold_accounts.contains(address) {
+                saving += (gas::COLD_ACCOUNT_ACCESS_COST - gas::WARM_STORAGE_READ_COST) as i64;
+            }
+            for slot in slots {
+                saving -= gas::ACCESS_LIST_STORAGE_KEY as i64;
+                if self.cold_slots.contains(&(*address, *slot)) {
+                    saving += (gas::COLD_SLOAD_COST - gas::WARM_STORAGE_READ_COST) as i64;
+                }
+            }
+        }
+        saving
+    }
+
+    /// Returns the entries of the access list, skipping excluded accounts without slots.
+    fn entries(&self) -> impl Iterator<Item = (&Address, &BTreeSet<U256>)> {
+        self.access_list
+            .iter()
+            .filter(|(address, slots)| !slots.is_empty() || !self.excluded.contains(*address))
+    }
+
+    fn record_account<DB: Database>(&mut self, address: Address, context: &EvmContext<DB>) {
+        if self.excluded.contains(&address) || context.is_precompile(&address) {
+            return;
+        }
+        let journaled_state = &context.journaled_state;
+        if !journaled_state.state.contains_key(&address)
+            && !journaled_state.is_warm_preloaded(&address)
+        {
+            self.cold_accounts.insert(address);
+        }
+        self.access_list.entry(address).or_default();
+    }
+
+    fn record_slot<DB: Database>(&mut self, address: Address, key: U256, context: &EvmContext<DB>) {
+        let is_cold = !matches!(
+            context.journaled_state.state.get(&address),
+            Some(account) if account.storage.contains_key(&key)
+        );
+        if is_cold {
+            self.cold_slots.insert((address, key));
+        }
+        self.access_list.entry(address).or_default().insert(key);
+    }
+}
+
+impl<DB: Database> Inspector<DB> for AccessListInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let stack = interp.stack();
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Ok(key) = stack.peek(0) {
+                    self.record_slot(interp.contract.address, key, context);
+                }
+            }
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => {
+                if let Ok(address) = stack.peek(0) {
+                    self.record_account(Address::from_word(address.into()), context);
+                }
+            }
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                if let Ok(address) = stack.peek(1) {
+                    self.record_account(Address::from_word(address.into()), context);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if context.journaled_state.depth() == 0 {
+            self.excluded.insert(context.env.tx.caller);
+            self.excluded.insert(inputs.contract);
+        }
+        None
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        if context.journaled_state.depth() == 0 {
+            let nonce = context
+                .journaled_state
+                .state
+                .get(&inputs.caller)
+                .map(|account| account.info.nonce)
+                .unwrap_or_default();
+            self.excluded.insert(context.env.tx.caller);
+            self.excluded.insert(inputs.created_address(nonce));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        primitives::{address, AccountInfo, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_access_list() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        let other = address!("3000000000000000000000000000000000000000");
+        // SLOAD slot 1, BALANCE of `other` and of the identity precompile.
+        let mut code = vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::POP,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(other.as_slice());
+        code.extend_from_slice(&[
+            opcode::BALANCE,
+            opcode::POP,
+            opcode::PUSH1,
+            0x4,
+            opcode::BALANCE,
+            opcode::POP,
+            opcode::STOP,
+        ]);
+        let code = Bytecode::new_raw(Bytes::from(code));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: code.hash_slow(),
+                code: Some(code),
+                ..Default::default()
+            },
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(AccessListInspector::new(&[]))
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .modify_block_env(|block| block.basefee = U256::ZERO)
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let inspector = evm.into_context().external;
+
+        // precompile is not included.
+        assert_eq!(
+            inspector.access_list(),
+            vec![(contract, vec![U256::from(1)]), (other, vec![])]
+        );
+        // contract address costs more than its warm slot saves.
+        assert_eq!(inspector.gas_saving(), -2300 + 100);
+    }
+}
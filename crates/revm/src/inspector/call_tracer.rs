@@ -0,0 +1,625 @@
+//! Geth `callTracer` compatible inspector.
+//!
+//! Output format is described in
+//! <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#call-tracer>.
+
+use super::labels::AddressLabels;
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+        InstructionResult,
+    },
+    primitives::{db::Database, Address, Bytes, EVMError, Log, ResultAndState, B256, U256},
+    EvmContext, Inspector,
+};
+use std::{string::String, vec::Vec};
+
+/// Type of the traced frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub enum CallKind {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+impl CallKind {
+    /// Returns the name of the opcode that started the frame.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Call => "CALL",
+            Self::CallCode => "CALLCODE",
+            Self::DelegateCall => "DELEGATECALL",
+            Self::StaticCall => "STATICCALL",
+            Self::Create => "CREATE",
+            Self::Create2 => "CREATE2",
+            Self::SelfDestruct => "SELFDESTRUCT",
+        }
+    }
+}
+
+impl From<CallScheme> for CallKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call | CallScheme::ExtCall => Self::Call,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall | CallScheme::ExtDelegateCall => Self::DelegateCall,
+            CallScheme::StaticCall | CallScheme::ExtStaticCall => Self::StaticCall,
+        }
+    }
+}
+
+impl From<CreateScheme> for CallKind {
+    fn from(scheme: CreateScheme) -> Self {
+        match scheme {
+            CreateScheme::Create => Self::Create,
+            CreateScheme::Create2 { .. } | CreateScheme::EofCreate { .. } => Self::Create2,
+        }
+    }
+}
+
+/// Log emitted inside of the frame, only collected if [CallTracerConfig::with_log] is set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallTraceLog {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    /// Number of child calls made by the frame before this log was emitted.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub position: u64,
+}
+
+/// Single call frame of the geth `callTracer` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallTraceFrame {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: CallKind,
+    pub from: Address,
+    /// Label of `from`, see [CallTracer::with_labels].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub from_label: Option<String>,
+    /// Target of the call or created address. `None` if creation failed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub to: Option<Address>,
+    /// Label of `to`, see [CallTracer::with_labels].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub to_label: Option<String>,
+    /// Transferred value, not present for `DELEGATECALL` and `STATICCALL`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub value: Option<U256>,
+    /// Gas available to the frame. For the root frame it is the gas limit of the transaction.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas: u64,
+    /// Gas used by the frame. For the root frame it is the gas used of the transaction
+    /// receipt, intrinsic gas included and refund subtracted.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas_used: u64,
+    pub input: Bytes,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub output: Option<Bytes>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub revert_reason: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub calls: Vec<CallTraceFrame>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub logs: Vec<CallTraceLog>,
+}
+
+impl CallTraceFrame {
+    /// Removes logs from this frame and all of its children.
+    ///
+    /// Geth does not report logs of frames that were reverted.
+    fn clear_logs(&mut self) {
+        self.logs.clear();
+        self.calls.iter_mut().for_each(Self::clear_logs);
+    }
+
+    /// Sets `output`, `error` and `revert_reason` from the result of the frame.
+    fn set_result(&mut self, result: InstructionResult, output: &Bytes) {
+        if result.is_ok() {
+            self.output = Some(output.clone());
+            return;
+        }
+        if result.is_revert() {
+            self.output = Some(output.clone());
+            self.revert_reason = decode_revert_reason(output);
+        }
+        self.error = Some(geth_error_message(result));
+        self.clear_logs();
+    }
+}
+
+/// Configuration of the [CallTracer].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallTracerConfig {
+    /// Trace only the top level call.
+    pub only_top_call: bool,
+    /// Collect logs emitted by the frames.
+    pub with_log: bool,
+}
+
+/// [Inspector] that records nested call frames in the geth `callTracer` format.
+#[derive(Clone, Debug, Default)]
+pub struct CallTracer {
+    config: CallTracerConfig,
+    /// Frames that are currently executing.
+    stack: Vec<CallTraceFrame>,
+    /// Root frame, set when the outermost frame ends.
+    result: Option<CallTraceFrame>,
+    labels: AddressLabels,
+}
+
+impl CallTracer {
+    /// Creates new call tracer with the given config.
+    pub fn new(config: CallTracerConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Sets labels that are reported as `fromLabel` and `toLabel` of the frames.
+    pub fn with_labels(mut self, labels: AddressLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Returns mutable reference to the address labels.
+    pub fn labels_mut(&mut self) -> &mut AddressLabels {
+        &mut self.labels
+    }
+
+    /// Returns the config of the tracer.
+    pub fn config(&self) -> &CallTracerConfig {
+        &self.config
+    }
+
+    /// Returns the root frame if execution is finished.
+    pub fn frame(&self) -> Option<&CallTraceFrame> {
+        self.result.as_ref()
+    }
+
+    /// Consumes the tracer and returns the root frame if execution is finished.
+    pub fn into_frame(self) -> Option<CallTraceFrame> {
+        self.result
+    }
+
+    fn start_frame(&mut self, frame: CallTraceFrame) {
+        self.stack.push(frame);
+    }
+
+    fn end_frame(&mut self, result: InstructionResult, gas_used: u64, output: &Bytes) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.gas_used = gas_used;
+        frame.set_result(result, output);
+        self.attach(frame);
+    }
+
+    /// Attaches finished frame to its parent, or sets it as a result if it is the root.
+    fn attach(&mut self, mut frame: CallTraceFrame) {
+        frame.from_label = self.labels.get(&frame.from).map(String::from);
+        frame.to_label = frame
+            .to
+            .and_then(|to| self.labels.get(&to))
+            .map(String::from);
+        match self.stack.last_mut() {
+            Some(parent) => {
+                if !self.config.only_top_call {
+                    parent.calls.push(frame)
+                }
+            }
+            None => self.result = Some(frame),
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn log(&mut self, _context: &mut EvmContext<DB>, log: &Log) {
+        if !self.config.with_log {
+            return;
+        }
+        if self.config.only_top_call && self.stack.len() > 1 {
+            return;
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.logs.push(CallTraceLog {
+                address: log.address,
+                topics: log.topics().to_vec(),
+                data: log.data.data.clone(),
+                position: frame.calls.len() as u64,
+            });
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let kind = CallKind::from(inputs.context.scheme);
+        let value = match kind {
+            CallKind::DelegateCall | CallKind::StaticCall => None,
+            _ => Some(inputs.transfer.value),
+        };
+        self.start_frame(CallTraceFrame {
+            kind,
+            from: inputs.transfer.source,
+            from_label: None,
+            to: Some(inputs.contract),
+            to_label: None,
+            value,
+            gas: inputs.gas_limit,
+            gas_used: 0,
+            input: inputs.input.clone(),
+            output: None,
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+            logs: Vec::new(),
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(
+            outcome.result.result,
+            outcome.result.gas.spend(),
+            &outcome.result.output,
+        );
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame(CallTraceFrame {
+            kind: inputs.scheme.into(),
+            from: inputs.caller,
+            from_label: None,
+            to: None,
+            to_label: None,
+            value: Some(inputs.value),
+            gas: inputs.gas_limit,
+            gas_used: 0,
+            input: inputs.init_code.clone(),
+            output: None,
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+            logs: Vec::new(),
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.to = outcome.address;
+        }
+        self.end_frame(
+            outcome.result.result,
+            outcome.result.gas.spend(),
+            &outcome.result.output,
+        );
+        outcome
+    }
+
+    fn transaction_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        result: &Result<ResultAndState, EVMError<DB::Error>>,
+    ) {
+        // frames only see the gas of the execution, geth reports the transaction gas
+        // in the root frame.
+        let (Ok(result), Some(frame)) = (result, self.result.as_mut()) else {
+            return;
+        };
+        frame.gas = context.env.tx.gas_limit;
+        frame.gas_used = result.result.gas_used();
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if self.stack.is_empty() {
+            return;
+        }
+        self.attach(CallTraceFrame {
+            kind: CallKind::SelfDestruct,
+            from: contract,
+            from_label: None,
+            to: Some(target),
+            to_label: None,
+            value: Some(value),
+            gas: 0,
+            gas_used: 0,
+            input: Bytes::new(),
+            output: None,
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+            logs: Vec::new(),
+        });
+    }
+}
+
+/// Returns the error message geth uses for the given failed result.
+pub(crate) fn geth_error_message(result: InstructionResult) -> String {
+    match result {
+        InstructionResult::Revert => "execution reverted",
+        InstructionResult::InvalidExtDelegateCallTarget => "invalid delegate call target",
+        InstructionResult::InvalidEOFInitCode => "invalid initcode",
+        InstructionResult::CallTooDeep => "max call depth exceeded",
+        InstructionResult::OutOfFunds => "insufficient balance for transfer",
+        InstructionResult::OutOfGas
+        | InstructionResult::MemoryOOG
+        | InstructionResult::MemoryLimitOOG
+        | InstructionResult::PrecompileOOG
+        | InstructionResult::InvalidOperandOOG => "out of gas",
+        InstructionResult::OpcodeNotFound
+        | InstructionResult::InvalidFEOpcode
+        | InstructionResult::NotActivated
+        | InstructionResult::EOFOpcodeDisabledInLegacy => "invalid opcode",
+        InstructionResult::CallNotAllowedInsideStatic
+        | InstructionResult::StateChangeDuringStaticCall => "write protection",
+        InstructionResult::InvalidJump => "invalid jump destination",
+        InstructionResult::InvalidEXTCALLTarget => "invalid call target",
+        InstructionResult::StackUnderflow => "stack underflow",
+        InstructionResult::StackOverflow => "stack limit reached 1024",
+        InstructionResult::EOFFunctionStackOverflow => "return stack limit reached 1024",
+        InstructionResult::OutOfOffset => "return data out of bounds",
+        InstructionResult::CreateCollision => "contract address collision",
+        InstructionResult::OverflowPayment => "gas uint64 overflow",
+        InstructionResult::NonceOverflow => "nonce uint64 overflow",
+        InstructionResult::CreateContractSizeLimit => "max code size exceeded",
+        InstructionResult::CreateContractStartingWithEF => "invalid code: must not begin with 0xef",
+        InstructionResult::CreateInitCodeSizeLimit => "max initcode size exceeded",
+        InstructionResult::EofAuxDataOverflow => "data section size exceeded",
+        InstructionResult::EofAuxDataTooSmall => "data section is shorter than declared",
+        InstructionResult::AuthorizedNotSet => "authorized account not set",
+        InstructionResult::PrecompileError => "precompile failed",
+        InstructionResult::FatalExternalError => "fatal external error",
+        InstructionResult::Continue
+        | InstructionResult::Stop
+        | InstructionResult::Return
+        | InstructionResult::SelfDestruct
+        | InstructionResult::ReturnContract
+        | InstructionResult::CallOrCreate => "",
+    }
+    .into()
+}
+
+/// Decodes the `Error(string)` revert reason from the revert output.
+pub(crate) fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    /// Selector of the `Error(string)` function.
+    const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+    let data = output.strip_prefix(&ERROR_SELECTOR)?;
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        // offset and length can't be larger than the output itself.
+        if word[..24].iter().any(|b| *b != 0) {
+            return None;
+        }
+        Some(u64::from_be_bytes(word[24..].try_into().ok()?) as usize)
+    };
+    let string_offset = word(0)?;
+    let len = word(string_offset)?;
+    let start = string_offset.checked_add(32)?;
+    let bytes = data.get(start..start.checked_add(len)?)?;
+    core::str::from_utf8(bytes).ok().map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_decode_revert_reason() {
+        let output = crate::primitives::hex::decode(
+            "08c379a0\
+             0000000000000000000000000000000000000000000000000000000000000020\
+             0000000000000000000000000000000000000000000000000000000000000004\
+             6e6f706500000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert_eq!(decode_revert_reason(&output).as_deref(), Some("nope"));
+        assert_eq!(decode_revert_reason(&output[..40]), None);
+    }
+
+    #[test]
+    fn test_call_tracer() {
+        // CREATE a contract with empty init code and stop.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::CREATE,
+            opcode::STOP,
+        ]);
+        let bytecode = Bytecode::new_raw(contract_data);
+        let caller = address!("1000000000000000000000000000000000000000");
+        let callee = address!("0000000000000000000000000000000000000000");
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(
+                CallTracer::new(CallTracerConfig::default())
+                    .with_labels(AddressLabels::from_iter([(callee, "Factory")])),
+            )
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(callee);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+        let frame = evm.into_context().external.into_frame().unwrap();
+
+        assert_eq!(frame.kind, CallKind::Call);
+        assert_eq!(frame.from, caller);
+        assert_eq!(frame.to, Some(callee));
+        assert_eq!(frame.from_label, None);
+        assert_eq!(frame.to_label.as_deref(), Some("Factory"));
+        assert_eq!(frame.value, Some(U256::ZERO));
+        assert_eq!(frame.error, None);
+        assert_eq!(frame.calls.len(), 1);
+
+        let create = &frame.calls[0];
+        assert_eq!(create.kind, CallKind::Create);
+        assert_eq!(create.from, callee);
+        assert_eq!(create.from_label.as_deref(), Some("Factory"));
+        assert_eq!(create.to, Some(callee.create(1)));
+        assert_eq!(create.output, Some(Bytes::new()));
+        assert!(frame.gas_used > create.gas_used);
+        assert_eq!(frame.gas, 100_000);
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_root_frame_gas_geth_fixture() {
+        // stores one and then zero to the slot, so the transaction gets a refund.
+        let bytecode = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(CallTracer::new(CallTracerConfig::default()))
+            .with_spec_id(crate::primitives::SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.data = Bytes::from_static(&[1, 2]);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        let frame = evm.into_context().external.into_frame().unwrap();
+
+        // geth `callTracer` output of the transaction. Intrinsic gas is 21032, execution is
+        // 22212 and the refund of 19900 is capped to a fifth of 43244.
+        let fixture: serde_json::Value = serde_json::from_str(
+            r#"{
+                "from": "0x1000000000000000000000000000000000000000",
+                "gas": "0x186a0",
+                "gasUsed": "0x8724",
+                "to": "0x0000000000000000000000000000000000000000",
+                "input": "0x0102",
+                "value": "0x0",
+                "type": "CALL"
+            }"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&frame).unwrap();
+        for (key, value) in fixture.as_object().unwrap() {
+            assert_eq!(&json[key], value, "{key}");
+        }
+        assert_eq!(frame.gas_used, result.gas_used());
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_serialize_frame() {
+        let frame = CallTraceFrame {
+            kind: CallKind::DelegateCall,
+            from: Address::ZERO,
+            from_label: None,
+            to: Some(Address::with_last_byte(1)),
+            to_label: None,
+            value: None,
+            gas: 0x100,
+            gas_used: 0x10,
+            input: Bytes::new(),
+            output: Some(Bytes::new()),
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+            logs: Vec::new(),
+        };
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"DELEGATECALL","from":"0x0000000000000000000000000000000000000000","to":"0x0000000000000000000000000000000000000001","gas":"0x100","gasUsed":"0x10","input":"0x","output":"0x"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<CallTraceFrame>(&json).unwrap(),
+            frame
+        );
+    }
+}
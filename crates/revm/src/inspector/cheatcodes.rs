@@ -0,0 +1,141 @@
+//! CheatcodeInspector. Routes calls to the cheatcode address to the handler.
+
+use crate::{
+    interpreter::{CallInputs, CallOutcome, Gas, InstructionResult, InterpreterResult},
+    primitives::{db::Database, Address, Bytes},
+    EvmContext, Inspector,
+};
+
+/// [Inspector] that intercepts calls to the cheatcode address and routes them to the handler
+/// instead of executing the code of the address.
+///
+/// Handler gets mutable access to the [EvmContext], so it can change the environment
+/// (e.g. `context.env.block.timestamp`) and the journaled state. Ok output is returned
+/// as a successful call and error output as a revert. Cheatcode calls use no gas.
+///
+/// Storage changes made with [EvmContext::sstore] are journaled and reverted together with
+/// the calling frame, while fields of the accounts changed directly are kept.
+pub struct CheatcodeInspector<F> {
+    address: Address,
+    handler: F,
+}
+
+impl<F> core::fmt::Debug for CheatcodeInspector<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CheatcodeInspector")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> CheatcodeInspector<F> {
+    /// Creates new inspector that routes calls to `address` to the handler.
+    pub fn new(address: Address, handler: F) -> Self {
+        Self { address, handler }
+    }
+
+    /// Returns the cheatcode address.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+impl<DB, F> Inspector<DB> for CheatcodeInspector<F>
+where
+    DB: Database,
+    F: FnMut(&mut EvmContext<DB>, &CallInputs) -> Result<Bytes, Bytes>,
+{
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.contract != self.address {
+            return None;
+        }
+        let (result, output) = match (self.handler)(context, inputs) {
+            Ok(output) => (InstructionResult::Return, output),
+            Err(output) => (InstructionResult::Revert, output),
+        };
+        Some(CallOutcome::new(
+            InterpreterResult {
+                result,
+                output,
+                gas: Gas::new(inputs.gas_limit),
+            },
+            inputs.return_memory_offset.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, TransactTo, U256},
+        Evm,
+    };
+
+    #[test]
+    fn test_cheatcode() {
+        let cheatcodes = address!("000000000000000000000000000000000000cafe");
+        let funded = Address::with_last_byte(0x42);
+        // CALL the cheatcode address, then SSTORE the TIMESTAMP into slot 0.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH2,
+            0xca,
+            0xfe,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::TIMESTAMP,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let inspector = CheatcodeInspector::new(
+            cheatcodes,
+            |context: &mut EvmContext<BenchmarkDB>, _inputs: &CallInputs| {
+                // warp
+                context.env.block.timestamp = U256::from(1234);
+                // set balance
+                let (account, _) = context
+                    .journaled_state
+                    .load_account(funded, &mut context.db)
+                    .map_err(|_| Bytes::new())?;
+                account.info.balance = U256::from(7);
+                account.mark_touch();
+                Ok(Bytes::new())
+            },
+        );
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(inspector)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().unwrap();
+
+        assert!(result.result.is_success());
+        assert_eq!(
+            result.state[&Address::ZERO].storage[&U256::ZERO].present_value,
+            U256::from(1234)
+        );
+        assert_eq!(result.state[&funded].info.balance, U256::from(7));
+    }
+}
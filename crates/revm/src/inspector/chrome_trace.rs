@@ -0,0 +1,292 @@
+//! ChromeTraceInspector. Call frames as Chrome `trace_event` JSON.
+
+use super::call_tracer::CallKind;
+use crate::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, Address},
+    EvmContext, Inspector,
+};
+use std::{format, string::String, vec::Vec};
+
+/// Phase of the trace event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TraceEventPhase {
+    /// Frame started.
+    #[cfg_attr(feature = "serde", serde(rename = "B"))]
+    Begin,
+    /// Frame ended.
+    #[cfg_attr(feature = "serde", serde(rename = "E"))]
+    End,
+}
+
+/// Arguments attached to the trace event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TraceEventArgs {
+    /// Caller of the frame, set on begin events.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub from: Option<Address>,
+    /// Gas limit of the frame, set on begin events.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub gas_limit: Option<u64>,
+    /// Gas used by the frame, set on end events.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub gas_used: Option<u64>,
+    /// Whether the frame succeeded, set on end events.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub success: Option<bool>,
+}
+
+/// Single event of the Chrome trace event format.
+///
+/// Timestamp is the gas used by the transaction before the event, so durations
+/// of the frames are their gas usage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceEvent {
+    /// Kind and target of the frame, e.g. `CALL 0x..`.
+    pub name: String,
+    pub cat: String,
+    pub ph: TraceEventPhase,
+    pub ts: u64,
+    pub pid: u64,
+    pub tid: u64,
+    pub args: TraceEventArgs,
+}
+
+/// Chrome trace that can be opened in `chrome://tracing` or Perfetto.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChromeTrace {
+    pub trace_events: Vec<TraceEvent>,
+}
+
+/// Start of the currently executing frame.
+#[derive(Clone, Debug)]
+struct FrameClock {
+    name: String,
+    /// Timestamp of the begin event.
+    start: u64,
+    /// Gas spent by the frame before the last executed instruction.
+    spent: u64,
+}
+
+/// [Inspector] that emits begin and end events of every call frame in the
+/// Chrome `trace_event` format, with gas used as duration.
+#[derive(Clone, Debug, Default)]
+pub struct ChromeTraceInspector {
+    events: Vec<TraceEvent>,
+    frames: Vec<FrameClock>,
+}
+
+impl ChromeTraceInspector {
+    /// Creates new Chrome trace inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the events in emission order.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Consumes the inspector and returns the trace.
+    pub fn into_trace(self) -> ChromeTrace {
+        ChromeTrace {
+            trace_events: self.events,
+        }
+    }
+
+    fn start_frame(&mut self, kind: CallKind, from: Address, to: Address, gas_limit: u64) {
+        let start = self
+            .frames
+            .last()
+            .map(|parent| parent.start + parent.spent)
+            .unwrap_or_default();
+        let name = format!("{} {}", kind.as_str(), to);
+        self.events.push(TraceEvent {
+            name: name.clone(),
+            cat: String::from("evm"),
+            ph: TraceEventPhase::Begin,
+            ts: start,
+            pid: 1,
+            tid: 1,
+            args: TraceEventArgs {
+                from: Some(from),
+                gas_limit: Some(gas_limit),
+                ..Default::default()
+            },
+        });
+        self.frames.push(FrameClock {
+            name,
+            start,
+            spent: 0,
+        });
+    }
+
+    fn end_frame(&mut self, gas_used: u64, success: bool) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        self.events.push(TraceEvent {
+            name: frame.name,
+            cat: String::from("evm"),
+            ph: TraceEventPhase::End,
+            ts: frame.start + gas_used,
+            pid: 1,
+            tid: 1,
+            args: TraceEventArgs {
+                gas_used: Some(gas_used),
+                success: Some(success),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ChromeTraceInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.spent = interp.gas.spend();
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame(
+            inputs.context.scheme.into(),
+            inputs.context.caller,
+            inputs.contract,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(outcome.result.gas.spend(), outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let nonce = context
+            .journaled_state
+            .get_account(&inputs.caller)
+            .map_or(0, |account| account.info.nonce);
+        self.start_frame(
+            inputs.scheme.into(),
+            inputs.caller,
+            inputs.created_address(nonce),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(outcome.result.gas.spend(), outcome.result.is_ok());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_chrome_trace() {
+        // STATICCALL the identity precompile with 32 bytes.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x20,
+            opcode::DUP2,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(ChromeTraceInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let trace = evm.into_context().external.into_trace();
+
+        let events: Vec<_> = trace
+            .trace_events
+            .iter()
+            .map(|event| (event.ph, event.name.as_str(), event.ts))
+            .collect();
+        let call = "CALL 0x0000000000000000000000000000000000000000";
+        let staticcall = "STATICCALL 0x0000000000000000000000000000000000000004";
+        // PUSH1, DUP1, PUSH1, DUP2, PUSH1 and GAS before the STATICCALL.
+        let before_call = 5 * 3 + 2;
+        assert_eq!(
+            events,
+            vec![
+                (TraceEventPhase::Begin, call, 0),
+                (TraceEventPhase::Begin, staticcall, before_call),
+                (TraceEventPhase::End, staticcall, before_call + 18),
+                // warm precompile access and memory expansion of the STATICCALL.
+                (TraceEventPhase::End, call, before_call + 18 + 103),
+            ]
+        );
+        assert_eq!(trace.trace_events[2].args.gas_used, Some(18));
+        assert_eq!(trace.trace_events[3].args.success, Some(true));
+
+        #[cfg(feature = "serde-json")]
+        {
+            let json = serde_json::to_value(&trace).unwrap();
+            assert_eq!(json["traceEvents"][0]["ph"], "B");
+            assert_eq!(json["traceEvents"][3]["args"]["gasUsed"], 121 + before_call);
+        }
+    }
+}
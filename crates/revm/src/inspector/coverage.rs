@@ -0,0 +1,179 @@
+//! CoverageInspector. Program counter and branch coverage for fuzzers.
+
+use crate::{
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, keccak256, HashMap, B256},
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// Hit counts of the single bytecode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CodeCoverage {
+    /// Number of times instruction at the program counter was executed.
+    pub pcs: HashMap<usize, u64>,
+    /// Number of times the `(from, to)` program counter edge of `JUMP` or `JUMPI` was taken.
+    ///
+    /// `JUMPI` records a fallthrough edge when condition is zero.
+    pub edges: HashMap<(usize, usize), u64>,
+}
+
+/// [Inspector] that records hit counts per code hash and program counter.
+///
+/// Init code is keyed by the hash of the init code.
+#[derive(Clone, Debug, Default)]
+pub struct CoverageInspector {
+    coverage: HashMap<B256, CodeCoverage>,
+    /// Code hashes of the frames that are currently executing,
+    /// `None` if frame does not execute bytecode.
+    frames: Vec<Option<B256>>,
+    /// Program counter of the executing jump.
+    pending_jump: Option<usize>,
+}
+
+impl CoverageInspector {
+    /// Creates new coverage inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the coverage map keyed by code hash.
+    pub fn coverage(&self) -> &HashMap<B256, CodeCoverage> {
+        &self.coverage
+    }
+
+    /// Consumes the inspector and returns the coverage map keyed by code hash.
+    pub fn into_coverage(self) -> HashMap<B256, CodeCoverage> {
+        self.coverage
+    }
+
+    /// Clears collected coverage, so inspector can be reused for the next input.
+    pub fn clear(&mut self) {
+        self.coverage.clear();
+    }
+
+    fn current(&mut self) -> Option<&mut CodeCoverage> {
+        let hash = (*self.frames.last()?)?;
+        Some(self.coverage.entry(hash).or_default())
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CoverageInspector {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let mut hash = interp.contract.hash;
+        if hash == B256::ZERO {
+            hash = keccak256(interp.contract.bytecode.original_bytecode_slice());
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            *frame = Some(hash);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let pc = interp.program_counter();
+        self.pending_jump =
+            matches!(interp.current_opcode(), opcode::JUMP | opcode::JUMPI).then_some(pc);
+        if let Some(coverage) = self.current() {
+            *coverage.pcs.entry(pc).or_default() += 1;
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(from) = self.pending_jump.take() else {
+            return;
+        };
+        if !interp.instruction_result.is_ok() {
+            return;
+        }
+        let to = interp.program_counter();
+        if let Some(coverage) = self.current() {
+            *coverage.edges.entry((from, to)).or_default() += 1;
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.frames.push(None);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.frames.pop();
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frames.push(None);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frames.pop();
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_coverage() {
+        // PUSH1 1, PUSH1 6, JUMPI, INVALID, JUMPDEST, STOP
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x6,
+            opcode::JUMPI,
+            opcode::INVALID,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]);
+        let bytecode = Bytecode::new_raw(contract_data);
+        let hash = bytecode.hash_slow();
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(CoverageInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let coverage = evm.into_context().external.into_coverage();
+
+        let code = &coverage[&hash];
+        let mut pcs: Vec<_> = code.pcs.iter().map(|(pc, hits)| (*pc, *hits)).collect();
+        pcs.sort();
+        assert_eq!(pcs, vec![(0, 1), (2, 1), (4, 1), (6, 1), (7, 1)]);
+        assert_eq!(code.edges, HashMap::from_iter([((4, 6), 1)]));
+    }
+}
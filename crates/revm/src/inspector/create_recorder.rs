@@ -0,0 +1,149 @@
+//! CreateRecorder. Records address derivation of every contract creation.
+
+use crate::{
+    interpreter::{CreateInputs, CreateOutcome},
+    primitives::{db::Database, keccak256, Address, CreateScheme, B256, U256},
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// Single `CREATE` or `CREATE2` with the inputs of the address derivation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateRecord {
+    pub deployer: Address,
+    /// Nonce of the deployer, used to derive the `CREATE` address.
+    pub nonce: u64,
+    /// Salt of `CREATE2`, `None` for `CREATE`.
+    pub salt: Option<U256>,
+    pub init_code_hash: B256,
+    /// Computed address of the contract.
+    pub address: Address,
+    pub value: U256,
+    /// Depth of the create frame, top level frame has depth 1.
+    pub depth: u64,
+    /// True if the contract was deployed. `None` while the creation is executing.
+    pub success: Option<bool>,
+}
+
+/// [Inspector] that records every `CREATE` and `CREATE2`, with the init code hash,
+/// salt and computed address of the contract.
+#[derive(Clone, Debug, Default)]
+pub struct CreateRecorder {
+    records: Vec<CreateRecord>,
+    /// Indices of records of the currently executing creates.
+    pending: Vec<usize>,
+}
+
+impl CreateRecorder {
+    /// Creates new create recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all creations in the order they started.
+    pub fn records(&self) -> &[CreateRecord] {
+        &self.records
+    }
+
+    /// Consumes the recorder and returns all creations in the order they started.
+    pub fn into_records(self) -> Vec<CreateRecord> {
+        self.records
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CreateRecorder {
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        // nonce of the deployer is bumped after this hook.
+        let nonce = context
+            .journaled_state
+            .get_account(&inputs.caller)
+            .map(|account| account.info.nonce)
+            .unwrap_or_default();
+        let salt = match inputs.scheme {
+            CreateScheme::Create => None,
+            CreateScheme::Create2 { salt } | CreateScheme::EofCreate { salt } => Some(salt),
+        };
+        self.pending.push(self.records.len());
+        self.records.push(CreateRecord {
+            deployer: inputs.caller,
+            nonce,
+            salt,
+            init_code_hash: keccak256(&inputs.init_code),
+            address: inputs.created_address(nonce),
+            value: inputs.value,
+            depth: context.journaled_state.depth() + 1,
+            success: None,
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some(index) = self.pending.pop() {
+            self.records[index].success = Some(outcome.result.is_ok());
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo, KECCAK_EMPTY},
+        Evm,
+    };
+
+    #[test]
+    fn test_create2_record() {
+        // CREATE2 with salt 7 and empty init code.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x7,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::CREATE2,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(CreateRecorder::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let salt = U256::from(7);
+        assert_eq!(
+            evm.into_context().external.into_records(),
+            vec![CreateRecord {
+                deployer: Address::ZERO,
+                nonce: 1,
+                salt: Some(salt),
+                init_code_hash: KECCAK_EMPTY,
+                address: Address::ZERO.create2(salt.to_be_bytes(), KECCAK_EMPTY),
+                value: U256::ZERO,
+                depth: 2,
+                success: Some(true),
+            }]
+        );
+    }
+}
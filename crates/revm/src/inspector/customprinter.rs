@@ -1,25 +1,90 @@
 //! Custom print inspector, it has step level information of execution.
 //! It is a great tool if some debugging is needed.
 
+use core::fmt::Write;
 use revm_interpreter::CallOutcome;
 use revm_interpreter::CreateOutcome;
 
 use crate::{
-    inspectors::GasInspector,
+    inspectors::{AddressLabels, GasInspector},
     interpreter::{opcode, CallInputs, CreateInputs, Interpreter},
     primitives::{Address, U256},
     Database, EvmContext, Inspector,
 };
 
+/// [core::fmt::Write] adapter that prints to the standard output.
+///
+/// It implements [core::fmt::Write] only with the `std` feature.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdoutWriter;
+
+#[cfg(feature = "std")]
+impl Write for StdoutWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        print!("{s}");
+        Ok(())
+    }
+}
+
 /// Custom print [Inspector], it has step level information of execution.
 ///
 /// It is a great tool if some debugging is needed.
-#[derive(Clone, Debug, Default)]
-pub struct CustomPrintTracer {
+///
+/// Output is written to the [core::fmt::Write] writer, so it can be redirected to
+/// a buffer or used without `std`. Default tracer prints to the standard output.
+/// Write errors are ignored.
+#[derive(Clone, Debug)]
+pub struct CustomPrintTracer<W = StdoutWriter> {
     gas_inspector: GasInspector,
+    writer: W,
+    labels: AddressLabels,
+}
+
+#[cfg(feature = "std")]
+impl Default for CustomPrintTracer {
+    fn default() -> Self {
+        Self::new(StdoutWriter)
+    }
 }
 
-impl<DB: Database> Inspector<DB> for CustomPrintTracer {
+impl<W: Write> CustomPrintTracer<W> {
+    /// Creates new tracer that writes to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            gas_inspector: GasInspector::default(),
+            writer,
+            labels: AddressLabels::default(),
+        }
+    }
+
+    /// Sets labels that are printed next to the addresses.
+    pub fn with_labels(mut self, labels: AddressLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Returns mutable reference to the address labels.
+    pub fn labels_mut(&mut self) -> &mut AddressLabels {
+        &mut self.labels
+    }
+
+    /// Returns the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the tracer and returns the writer.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<DB: Database, W: Write> Inspector<DB> for CustomPrintTracer<W> {
     fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
         self.gas_inspector.initialize_interp(interp, context);
     }
@@ -28,13 +93,14 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
     // all other information can be obtained from interp.
     fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
         let opcode = interp.current_opcode();
-        let opcode_str = opcode::OPCODE_JUMPMAP[opcode as usize];
+        let opcode_str = opcode::spec_opcode_name(opcode, context.journaled_state.spec);
 
         let gas_remaining = self.gas_inspector.gas_remaining();
 
         let memory_size = interp.shared_memory.len();
 
-        println!(
+        let _ = writeln!(
+            self.writer,
             "depth:{}, PC:{}, gas:{:#x}({}), OPCODE: {:?}({:?})  refund:{:#x}({}) Stack:{:?}, Data size:{}",
             context.journaled_state.depth(),
             interp.program_counter(),
@@ -78,9 +144,10 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
         _context: &mut EvmContext<DB>,
         inputs: &mut CallInputs,
     ) -> Option<CallOutcome> {
-        println!(
-            "SM CALL:   {:?}, context:{:?}, is_static:{:?}, transfer:{:?}, input_size:{:?}",
-            inputs.contract,
+        let _ = writeln!(
+            self.writer,
+            "SM CALL:   {}, context:{:?}, is_static:{:?}, transfer:{:?}, input_size:{:?}",
+            self.labels.display(inputs.contract),
             inputs.context,
             inputs.is_static,
             inputs.transfer,
@@ -94,17 +161,25 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
         _context: &mut EvmContext<DB>,
         inputs: &mut CreateInputs,
     ) -> Option<CreateOutcome> {
-        println!(
-            "CREATE CALL: caller:{:?}, scheme:{:?}, value:{:?}, init_code:{:?}, gas:{:?}",
-            inputs.caller, inputs.scheme, inputs.value, inputs.init_code, inputs.gas_limit
+        let _ = writeln!(
+            self.writer,
+            "CREATE CALL: caller:{}, scheme:{:?}, value:{:?}, init_code:{:?}, gas:{:?}",
+            self.labels.display(inputs.caller),
+            inputs.scheme,
+            inputs.value,
+            inputs.init_code,
+            inputs.gas_limit
         );
         None
     }
 
     fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
-        println!(
-            "SELFDESTRUCT: contract: {:?}, refund target: {:?}, value {:?}",
-            contract, target, value
+        let _ = writeln!(
+            self.writer,
+            "SELFDESTRUCT: contract: {}, refund target: {}, value {:?}",
+            self.labels.display(contract),
+            self.labels.display(target),
+            value
         );
     }
 }
@@ -112,11 +187,13 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
 #[cfg(test)]
 mod test {
     use crate::{
+        db::BenchmarkDB,
         inspector_handle_register,
-        inspectors::CustomPrintTracer,
-        primitives::{address, bytes, SpecId},
+        inspectors::{AddressLabels, CustomPrintTracer},
+        primitives::{address, bytes, Address, Bytecode, SpecId, TransactTo},
         Evm, InMemoryDB,
     };
+    use std::{string::String, vec::Vec};
 
     #[test]
     fn gas_calculation_underflow() {
@@ -149,4 +226,32 @@ mod test {
 
         evm.transact().expect("Transaction to work");
     }
+
+    #[test]
+    fn write_to_buffer() {
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(bytes!(
+                "600100"
+            ))))
+            .with_external_context(
+                CustomPrintTracer::new(String::new())
+                    .with_labels(AddressLabels::from_iter([(Address::ZERO, "Target")])),
+            )
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().expect("Transaction to work");
+
+        let output = evm.into_context().external.into_writer();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(
+            lines[0].starts_with("SM CALL:   Target (0x0000000000000000000000000000000000000000)")
+        );
+        assert!(lines[1].contains("OPCODE: \"PUSH1\"(96)"));
+        assert!(lines[2].contains("OPCODE: \"STOP\"(0)"));
+    }
 }
@@ -0,0 +1,232 @@
+//! DebuggerInspector. Breakpoint based step debugger.
+
+use crate::{
+    interpreter::{opcode, InstructionResult, Interpreter},
+    primitives::{db::Database, Address, U256},
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// Condition on which the [DebuggerInspector] pauses execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Breakpoint {
+    /// Instruction at the program counter of the contract.
+    Pc { address: Address, pc: usize },
+    /// Any instruction with the opcode.
+    Opcode(u8),
+    /// `SLOAD` or `SSTORE` of the storage slot of the contract.
+    Storage { address: Address, key: U256 },
+}
+
+impl Breakpoint {
+    /// Returns true if breakpoint matches the instruction that is about to be executed.
+    fn matches(&self, interp: &Interpreter) -> bool {
+        match *self {
+            Self::Pc { address, pc } => {
+                interp.contract.address == address && interp.program_counter() == pc
+            }
+            Self::Opcode(opcode) => interp.current_opcode() == opcode,
+            Self::Storage { address, key } => {
+                matches!(interp.current_opcode(), opcode::SLOAD | opcode::SSTORE)
+                    && interp.contract.address == address
+                    && interp.stack().peek(0) == Ok(key)
+            }
+        }
+    }
+}
+
+/// What the debugger does after the handler returns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DebuggerAction {
+    /// Continue until the next breakpoint.
+    Continue,
+    /// Pause again before the next instruction.
+    Step,
+    /// Halt the current frame with the given result, parent frame continues.
+    Halt(InstructionResult),
+}
+
+/// Reason why execution was paused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DebuggerPause {
+    /// Breakpoint that was hit, `None` when stepping.
+    pub breakpoint: Option<Breakpoint>,
+    /// Depth of the paused frame.
+    pub depth: u64,
+}
+
+/// [Inspector] that pauses execution before the instruction that hits a breakpoint
+/// and calls the handler.
+///
+/// Execution is paused while the handler runs, handler has read access to the
+/// interpreter and can inspect the state through the [EvmContext], then returns
+/// [DebuggerAction] to decide how execution continues.
+pub struct DebuggerInspector<F> {
+    breakpoints: Vec<Breakpoint>,
+    stepping: bool,
+    handler: F,
+}
+
+impl<F> core::fmt::Debug for DebuggerInspector<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DebuggerInspector")
+            .field("breakpoints", &self.breakpoints)
+            .field("stepping", &self.stepping)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> DebuggerInspector<F> {
+    /// Creates new debugger with the handler that is called when execution pauses.
+    pub fn new(handler: F) -> Self {
+        Self {
+            breakpoints: Vec::new(),
+            stepping: false,
+            handler,
+        }
+    }
+
+    /// Adds the breakpoint.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    /// Removes the breakpoint, returns true if it was present.
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) -> bool {
+        let len = self.breakpoints.len();
+        self.breakpoints.retain(|b| b != breakpoint);
+        self.breakpoints.len() != len
+    }
+
+    /// Returns all breakpoints.
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Pauses before the first executed instruction.
+    pub fn step_into(&mut self) {
+        self.stepping = true;
+    }
+}
+
+impl<DB, F> Inspector<DB> for DebuggerInspector<F>
+where
+    DB: Database,
+    F: FnMut(&DebuggerPause, &Interpreter, &mut EvmContext<DB>) -> DebuggerAction,
+{
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let breakpoint = self
+            .breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.matches(interp))
+            .copied();
+        if breakpoint.is_none() && !self.stepping {
+            return;
+        }
+        let pause = DebuggerPause {
+            breakpoint,
+            depth: context.journaled_state.depth(),
+        };
+        match (self.handler)(&pause, interp, context) {
+            DebuggerAction::Continue => self.stepping = false,
+            DebuggerAction::Step => self.stepping = true,
+            DebuggerAction::Halt(result) => {
+                self.stepping = false;
+                interp.instruction_result = result;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        primitives::{address, Bytecode, Bytes, ExecutionResult, TransactTo},
+        Evm,
+    };
+
+    fn run<F>(debugger: DebuggerInspector<F>) -> (ExecutionResult, DebuggerInspector<F>)
+    where
+        F: FnMut(&DebuggerPause, &Interpreter, &mut EvmContext<BenchmarkDB>) -> DebuggerAction,
+    {
+        // SSTORE 1 into slot 2 and STOP.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x2,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(debugger)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().unwrap().result;
+        (result, evm.into_context().external)
+    }
+
+    #[test]
+    fn test_breakpoints_and_stepping() {
+        let mut pauses = Vec::new();
+        let mut debugger = DebuggerInspector::new(
+            |pause: &DebuggerPause, interp: &Interpreter, _: &mut EvmContext<BenchmarkDB>| {
+                pauses.push((pause.breakpoint, interp.program_counter()));
+                // step once after the storage breakpoint.
+                if pause.breakpoint.is_some() {
+                    DebuggerAction::Step
+                } else {
+                    DebuggerAction::Continue
+                }
+            },
+        );
+        debugger.add_breakpoint(Breakpoint::Storage {
+            address: Address::ZERO,
+            key: U256::from(2),
+        });
+        let (result, _) = run(debugger);
+        assert!(result.is_success());
+
+        let storage = Breakpoint::Storage {
+            address: Address::ZERO,
+            key: U256::from(2),
+        };
+        assert_eq!(pauses, vec![(Some(storage), 4), (None, 5)]);
+    }
+
+    #[test]
+    fn test_halt() {
+        let mut debugger = DebuggerInspector::new(
+            |_: &DebuggerPause, _: &Interpreter, _: &mut EvmContext<BenchmarkDB>| {
+                DebuggerAction::Halt(InstructionResult::Revert)
+            },
+        );
+        debugger.add_breakpoint(Breakpoint::Pc {
+            address: Address::ZERO,
+            pc: 2,
+        });
+        assert!(debugger.remove_breakpoint(&Breakpoint::Pc {
+            address: Address::ZERO,
+            pc: 2,
+        }));
+        debugger.add_breakpoint(Breakpoint::Opcode(opcode::SSTORE));
+        let (result, debugger) = run(debugger);
+        assert!(matches!(result, ExecutionResult::Revert { .. }));
+        assert_eq!(
+            debugger.breakpoints(),
+            &[Breakpoint::Opcode(opcode::SSTORE)]
+        );
+    }
+}
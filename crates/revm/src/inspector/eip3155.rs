@@ -1,7 +1,7 @@
 use crate::{
-    inspectors::GasInspector,
-    interpreter::{opcode, CallInputs, CallOutcome, Interpreter},
-    primitives::{db::Database, hex, HashMap, B256, U256},
+    inspectors::{GasInspector, TracerConfig},
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, hex, Bytes, HashMap, B256, U256},
     EvmContext, Inspector,
 };
 use serde::Serialize;
@@ -12,16 +12,20 @@ pub struct TracerEip3155 {
     output: Box<dyn Write>,
     gas_inspector: GasInspector,
 
-    #[allow(dead_code)]
-    trace_mem: bool,
-    #[allow(dead_code)]
-    trace_return_data: bool,
+    config: TracerConfig,
 
     stack: Vec<U256>,
     pc: usize,
     opcode: u8,
     gas: u64,
     mem_size: usize,
+    memory: Option<String>,
+    storage: Option<HashMap<String, String>>,
+    return_data: Bytes,
+    /// Refund of the currently executing frame.
+    refund: i64,
+    /// Refunds of the parent frames, refund is merged into the parent when frame ends.
+    parent_refunds: Vec<i64>,
     skip: bool,
 }
 
@@ -57,9 +61,9 @@ struct Output {
     /// Description of an error (should contain revert reason if supported)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     error: Option<String>,
-    /// Array of all allocated values
+    /// Hex encoded memory
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    memory: Option<Vec<String>>,
+    memory: Option<String>,
     /// Array of all stored values
     #[serde(default, skip_serializing_if = "Option::is_none")]
     storage: Option<HashMap<String, String>>,
@@ -99,17 +103,34 @@ impl TracerEip3155 {
 }
 
 impl TracerEip3155 {
+    /// Creates new tracer that always captures the stack.
     pub fn new(output: Box<dyn Write>, trace_mem: bool, trace_return_data: bool) -> Self {
+        Self::with_config(
+            output,
+            TracerConfig {
+                memory: trace_mem,
+                return_data: trace_return_data,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Creates new tracer that captures the data enabled in the config.
+    pub fn with_config(output: Box<dyn Write>, config: TracerConfig) -> Self {
         Self {
             output,
             gas_inspector: GasInspector::default(),
-            trace_mem,
-            trace_return_data,
+            config,
             stack: Default::default(),
             pc: 0,
             opcode: 0,
             gas: 0,
             mem_size: 0,
+            memory: None,
+            storage: None,
+            return_data: Bytes::new(),
+            refund: 0,
+            parent_refunds: Vec::new(),
             skip: false,
         }
     }
@@ -119,6 +140,35 @@ impl TracerEip3155 {
         self.output.write_all(b"\n")?;
         self.output.flush()
     }
+
+    /// Returns the refund of all executing frames.
+    fn global_refund(&self) -> i64 {
+        self.parent_refunds.iter().sum::<i64>() + self.refund
+    }
+
+    /// Saves the refund of the parent frame when new frame starts.
+    fn start_frame(&mut self) {
+        self.parent_refunds.push(self.refund);
+        self.refund = 0;
+    }
+
+    /// Restores the refund of the parent frame when the frame ends,
+    /// writes the summary if it is the last frame.
+    fn end_frame(&mut self, depth: u64, output: &Bytes, gas_used: u64, pass: bool) {
+        self.refund = self.parent_refunds.pop().unwrap_or_default();
+        if depth == 0 {
+            let value = Summary {
+                state_root: B256::ZERO.to_string(),
+                output: output.to_string(),
+                gas_used: hex_number(gas_used),
+                pass,
+
+                time: None,
+                fork: None,
+            };
+            let _ = self.write_value(&value);
+        }
+    }
 }
 
 impl<DB: Database> Inspector<DB> for TracerEip3155 {
@@ -128,11 +178,37 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
 
     fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
         self.gas_inspector.step(interp, context);
-        self.stack = interp.stack.data().clone();
+        if self.config.stack {
+            self.stack.clone_from(interp.stack.data());
+        }
         self.pc = interp.program_counter();
         self.opcode = interp.current_opcode();
         self.mem_size = interp.shared_memory.len();
         self.gas = interp.gas.remaining();
+        self.refund = interp.gas.refunded();
+        self.memory = self
+            .config
+            .memory
+            .then(|| hex::encode_prefixed(interp.shared_memory.context_memory()));
+        self.storage = self.config.storage.then(|| {
+            context
+                .journaled_state
+                .state
+                .get(&interp.contract.address)
+                .map(|account| {
+                    account
+                        .storage
+                        .iter()
+                        .map(|(key, slot)| {
+                            (hex_number_u256(key), hex_number_u256(&slot.present_value))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        if self.config.return_data {
+            self.return_data = interp.return_data_buffer.clone();
+        }
     }
 
     fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
@@ -149,23 +225,32 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
             gas_cost: hex_number(self.gas_inspector.last_gas_cost()),
             stack: self.stack.iter().map(hex_number_u256).collect(),
             depth: context.journaled_state.depth(),
-            return_data: "0x".to_string(),
-            refund: "0x0".to_string(),
+            return_data: self.return_data.to_string(),
+            refund: hex_number(self.global_refund().max(0) as u64),
             mem_size: self.mem_size.to_string(),
 
-            op_name: opcode::OPCODE_JUMPMAP[self.opcode as usize],
+            op_name: opcode::spec_opcode_name(self.opcode, context.journaled_state.spec),
             error: if !interp.instruction_result.is_ok() {
                 Some(format!("{:?}", interp.instruction_result))
             } else {
                 None
             },
-            memory: None,
-            storage: None,
+            memory: self.memory.take(),
+            storage: self.storage.take(),
             return_stack: None,
         };
         let _ = self.write_value(&value);
     }
 
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame();
+        None
+    }
+
     fn call_end(
         &mut self,
         context: &mut EvmContext<DB>,
@@ -173,18 +258,37 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
         outcome: CallOutcome,
     ) -> CallOutcome {
         let outcome = self.gas_inspector.call_end(context, inputs, outcome);
-        if context.journaled_state.depth() == 0 {
-            let value = Summary {
-                state_root: B256::ZERO.to_string(),
-                output: outcome.result.output.to_string(),
-                gas_used: hex_number(self.gas_inspector.gas_remaining()),
-                pass: outcome.result.is_ok(),
+        self.end_frame(
+            context.journaled_state.depth(),
+            &outcome.result.output,
+            outcome.result.gas.spend(),
+            outcome.result.is_ok(),
+        );
+        outcome
+    }
 
-                time: None,
-                fork: None,
-            };
-            let _ = self.write_value(&value);
-        }
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let outcome = self.gas_inspector.create_end(context, inputs, outcome);
+        self.end_frame(
+            context.journaled_state.depth(),
+            &outcome.result.output,
+            outcome.result.gas.spend(),
+            outcome.result.is_ok(),
+        );
         outcome
     }
 }
@@ -202,3 +306,154 @@ fn hex_number_u256(b: &U256) -> String {
         format!("0x{s}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        primitives::{address, Bytecode, SpecId, TransactTo},
+        Evm,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Writer that can be read after the tracer is moved into the evm.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_trace_lines() {
+        // MSTORE 1 at offset 0 and STOP.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::MSTORE,
+            opcode::STOP,
+        ]);
+        let buffer = SharedBuffer::default();
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(TracerEip3155::new(Box::new(buffer.clone()), true, true))
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        drop(evm);
+
+        let output = String::from_utf8(buffer.0.take()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // 4 steps and the summary.
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["pc"], 0);
+        assert_eq!(lines[0]["depth"], 1);
+        assert_eq!(lines[0]["returnData"], "0x");
+        assert_eq!(lines[0]["memory"], "0x");
+        assert_eq!(lines[2]["opName"], "MSTORE");
+        assert_eq!(lines[2]["stack"], serde_json::json!(["0x1", "0x0"]));
+        assert_eq!(lines[3]["memSize"], "32");
+        assert_eq!(
+            lines[3]["memory"],
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(lines[4]["pass"], true);
+        assert_eq!(lines[4]["gasUsed"], "0xc");
+    }
+
+    #[test]
+    fn test_eof_opcode_name_depends_on_spec() {
+        // `RJUMP` is only an opcode once EOF is enabled.
+        let contract_data = Bytes::from(vec![opcode::RJUMP, 0x0, 0x0]);
+        let op_name = |spec_id| {
+            let buffer = SharedBuffer::default();
+            let mut evm = Evm::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+                    contract_data.clone(),
+                )))
+                .with_external_context(TracerEip3155::new(Box::new(buffer.clone()), true, true))
+                .with_spec_id(spec_id)
+                .modify_tx_env(|tx| {
+                    tx.clear();
+                    tx.caller = address!("1000000000000000000000000000000000000000");
+                    tx.transact_to =
+                        TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                    tx.gas_limit = 100_000;
+                })
+                .append_handler_register(inspector_handle_register)
+                .build();
+            evm.transact().unwrap();
+            drop(evm);
+
+            let output = String::from_utf8(buffer.0.take()).unwrap();
+            let line: serde_json::Value =
+                serde_json::from_str(output.lines().next().unwrap()).unwrap();
+            line["opName"].clone()
+        };
+        assert_eq!(op_name(SpecId::CANCUN), serde_json::Value::Null);
+        assert_eq!(op_name(SpecId::PRAGUE_EOF), "RJUMP");
+    }
+
+    #[test]
+    fn test_config() {
+        // SSTORE 1 into slot 0 and STOP.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let buffer = SharedBuffer::default();
+        let config = TracerConfig {
+            stack: false,
+            storage: true,
+            ..Default::default()
+        };
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(TracerEip3155::with_config(Box::new(buffer.clone()), config))
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        drop(evm);
+
+        let output = String::from_utf8(buffer.0.take()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(lines[2]["stack"].as_array().unwrap().is_empty());
+        assert!(lines[2].get("memory").is_none());
+        assert_eq!(lines[2]["storage"], serde_json::json!({}));
+        assert_eq!(lines[3]["storage"], serde_json::json!({"0x0": "0x1"}));
+    }
+}
@@ -0,0 +1,177 @@
+//! FlamegraphInspector. Gas usage as folded stacks for flamegraph tools.
+
+use super::call_tracer::CallKind;
+use crate::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::{db::Database, Address},
+    EvmContext, Inspector,
+};
+use core::fmt;
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+/// Currently executing frame.
+#[derive(Clone, Debug)]
+struct StackFrame {
+    /// Folded call stack path of the frame.
+    path: String,
+    /// Gas used by the child frames.
+    children_gas: u64,
+}
+
+/// [Inspector] that aggregates the gas used by every call stack path.
+///
+/// Output is in the folded stacks format, one `CALL 0x..;STATICCALL 0x.. <gas>` line
+/// per path with the gas used by the frame itself, excluding child frames.
+/// It can be consumed by `inferno-flamegraph` or `flamegraph.pl`.
+#[derive(Clone, Debug, Default)]
+pub struct FlamegraphInspector {
+    stacks: BTreeMap<String, u64>,
+    frames: Vec<StackFrame>,
+}
+
+impl FlamegraphInspector {
+    /// Creates new flamegraph inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns self gas aggregated by folded call stack path.
+    pub fn stacks(&self) -> &BTreeMap<String, u64> {
+        &self.stacks
+    }
+
+    /// Writes folded stack lines.
+    pub fn write_folded<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        for (path, gas) in &self.stacks {
+            writeln!(writer, "{path} {gas}")?;
+        }
+        Ok(())
+    }
+
+    /// Returns folded stack lines.
+    pub fn folded(&self) -> String {
+        let mut folded = String::new();
+        self.write_folded(&mut folded)
+            .expect("writing to string does not fail");
+        folded
+    }
+
+    fn start_frame(&mut self, kind: CallKind, address: Address) {
+        let label = format!("{} {}", kind.as_str(), address);
+        let path = match self.frames.last() {
+            Some(parent) => format!("{};{}", parent.path, label),
+            None => label,
+        };
+        self.frames.push(StackFrame {
+            path,
+            children_gas: 0,
+        });
+    }
+
+    fn end_frame(&mut self, gas_used: u64) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        let self_gas = gas_used.saturating_sub(frame.children_gas);
+        if self_gas != 0 {
+            *self.stacks.entry(frame.path).or_default() += self_gas;
+        }
+        if let Some(parent) = self.frames.last_mut() {
+            parent.children_gas += gas_used;
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for FlamegraphInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame(inputs.context.scheme.into(), inputs.contract);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(outcome.result.gas.spend());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let nonce = context
+            .journaled_state
+            .get_account(&inputs.caller)
+            .map_or(0, |account| account.info.nonce);
+        self.start_frame(inputs.scheme.into(), inputs.created_address(nonce));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(outcome.result.gas.spend());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_folded_stacks() {
+        // STATICCALL the identity precompile with 32 bytes.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x20,
+            opcode::DUP2,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(FlamegraphInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let flamegraph = evm.into_context().external;
+
+        assert_eq!(
+            flamegraph.folded(),
+            "CALL 0x0000000000000000000000000000000000000000 120\n\
+             CALL 0x0000000000000000000000000000000000000000;\
+             STATICCALL 0x0000000000000000000000000000000000000004 18\n"
+        );
+    }
+}
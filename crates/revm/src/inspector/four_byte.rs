@@ -0,0 +1,113 @@
+//! Geth `4byteTracer` compatible inspector.
+//!
+//! Output format is described in
+//! <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#4byte-tracer>.
+
+use crate::{
+    interpreter::{CallInputs, CallOutcome},
+    primitives::{db::Database, hex, FixedBytes},
+    EvmContext, Inspector,
+};
+use std::{collections::BTreeMap, format, string::String};
+
+/// [Inspector] that counts function selectors and calldata sizes of all calls.
+///
+/// Calls to precompiles and calls with less than 4 bytes of input are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct FourByteInspector {
+    /// Number of calls keyed by the selector and size of input after the selector.
+    counts: BTreeMap<(FixedBytes<4>, usize), u64>,
+}
+
+impl FourByteInspector {
+    /// Creates new 4byte inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of calls keyed by the selector and size of input after the selector.
+    pub fn counts(&self) -> &BTreeMap<(FixedBytes<4>, usize), u64> {
+        &self.counts
+    }
+
+    /// Returns the geth output, where keys are in `0x<selector>-<calldata size>` format.
+    pub fn output(&self) -> BTreeMap<String, u64> {
+        self.counts
+            .iter()
+            .map(|((selector, size), count)| {
+                (format!("0x{}-{}", hex::encode(selector), size), *count)
+            })
+            .collect()
+    }
+}
+
+impl<DB: Database> Inspector<DB> for FourByteInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.input.len() < 4 || context.is_precompile(&inputs.contract) {
+            return None;
+        }
+        let selector = FixedBytes::<4>::from_slice(&inputs.input[..4]);
+        *self
+            .counts
+            .entry((selector, inputs.input.len() - 4))
+            .or_default() += 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_four_byte() {
+        // STATICCALL identity precompile, output should not contain it.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(FourByteInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.data = Bytes::from(vec![0xaa, 0xbb, 0xcc, 0xdd, 0x01, 0x02]);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+        let inspector = evm.into_context().external;
+
+        assert_eq!(
+            inspector.output(),
+            BTreeMap::from([("0xaabbccdd-2".into(), 1)])
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! FrameGasInspector. Gas used and refunded by every call frame.
+
+use super::call_tracer::CallKind;
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, InstructionResult, Interpreter,
+    },
+    primitives::{db::Database, Address},
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// Gas cost of the single executed instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpcodeGas {
+    pub pc: usize,
+    pub opcode: u8,
+    /// Gas charged by the instruction.
+    ///
+    /// For `CALL` and `CREATE` instructions it includes the gas used by the child frame.
+    pub gas_cost: u64,
+}
+
+/// Gas accounting of the single call frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameGas {
+    pub kind: CallKind,
+    /// Target of the call or created address.
+    pub address: Option<Address>,
+    /// Depth of the frame, top level frame has depth 0.
+    pub depth: usize,
+    /// Index of the parent frame in [FrameGasInspector::frames].
+    pub parent: Option<usize>,
+    /// Indices of the child frames in [FrameGasInspector::frames].
+    pub children: Vec<usize>,
+    pub gas_limit: u64,
+    /// Gas used by the frame and its children.
+    ///
+    /// Frame that halted with an error consumes all of its gas.
+    pub gas_used: u64,
+    /// Gas used by the frame itself, without the gas used by its children.
+    pub self_gas_used: u64,
+    /// Refund of the frame and its children.
+    ///
+    /// Zero if the frame did not succeed, as refunds of reverted frames are discarded.
+    pub refunded: i64,
+    /// Refund recorded by the frame itself, without the refunds of its children.
+    pub self_refunded: i64,
+    /// Result of the frame.
+    pub result: InstructionResult,
+    /// Gas cost of every executed instruction, only recorded if enabled.
+    pub opcodes: Vec<OpcodeGas>,
+}
+
+/// [Inspector] that records gas used and refunded by every call frame.
+///
+/// Gas of the child frames is attributed to their parents the same way as the
+/// interpreter does it: unused gas of the succeeding and reverting frames is
+/// returned to the parent, while refunds are only merged if frame succeeds.
+#[derive(Clone, Debug, Default)]
+pub struct FrameGasInspector {
+    with_opcodes: bool,
+    frames: Vec<FrameGas>,
+    /// Indices of the frames that are currently executing.
+    stack: Vec<usize>,
+    /// Gas remaining before the currently executing instruction.
+    gas_remaining: u64,
+}
+
+impl FrameGasInspector {
+    /// Creates new inspector, if `with_opcodes` is set gas costs of every instruction are recorded.
+    pub fn new(with_opcodes: bool) -> Self {
+        Self {
+            with_opcodes,
+            ..Default::default()
+        }
+    }
+
+    /// Returns all frames in the order they were started.
+    pub fn frames(&self) -> &[FrameGas] {
+        &self.frames
+    }
+
+    /// Consumes the inspector and returns all frames in the order they were started.
+    pub fn into_frames(self) -> Vec<FrameGas> {
+        self.frames
+    }
+
+    fn start_frame(&mut self, kind: CallKind, address: Option<Address>, gas_limit: u64) {
+        let index = self.frames.len();
+        let parent = self.stack.last().copied();
+        if let Some(parent) = parent {
+            self.frames[parent].children.push(index);
+        }
+        self.frames.push(FrameGas {
+            kind,
+            address,
+            depth: self.stack.len(),
+            parent,
+            children: Vec::new(),
+            gas_limit,
+            gas_used: 0,
+            self_gas_used: 0,
+            refunded: 0,
+            self_refunded: 0,
+            result: InstructionResult::Continue,
+            opcodes: Vec::new(),
+        });
+        self.stack.push(index);
+    }
+
+    fn end_frame(&mut self, result: InstructionResult, remaining: u64, refunded: i64) {
+        let Some(index) = self.stack.pop() else {
+            return;
+        };
+        let (children_gas, children_refund) = self.frames[index]
+            .children
+            .iter()
+            .map(|child| &self.frames[*child])
+            .fold((0, 0), |(gas, refund), child| {
+                (gas + child.gas_used, refund + child.refunded)
+            });
+
+        let frame = &mut self.frames[index];
+        frame.result = result;
+        frame.gas_used = if result.is_ok() || result.is_revert() {
+            frame.gas_limit.saturating_sub(remaining)
+        } else {
+            frame.gas_limit
+        };
+        frame.self_gas_used = frame.gas_used.saturating_sub(children_gas);
+        if result.is_ok() {
+            frame.refunded = refunded;
+            frame.self_refunded = refunded - children_refund;
+        }
+        let (gas_limit, gas_used) = (frame.gas_limit, frame.gas_used);
+
+        // unused gas of the child is returned to the calling instruction.
+        if let Some(parent) = self.stack.last() {
+            if let Some(op) = self.frames[*parent].opcodes.last_mut() {
+                op.gas_cost = op.gas_cost.saturating_sub(gas_limit - gas_used);
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for FrameGasInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.with_opcodes {
+            return;
+        }
+        self.gas_remaining = interp.gas.remaining();
+        if let Some(index) = self.stack.last() {
+            self.frames[*index].opcodes.push(OpcodeGas {
+                pc: interp.program_counter(),
+                opcode: interp.current_opcode(),
+                gas_cost: 0,
+            });
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.with_opcodes {
+            return;
+        }
+        let Some(index) = self.stack.last() else {
+            return;
+        };
+        if let Some(op) = self.frames[*index].opcodes.last_mut() {
+            op.gas_cost = self.gas_remaining.saturating_sub(interp.gas.remaining());
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame(
+            inputs.context.scheme.into(),
+            Some(inputs.contract),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        let result = &outcome.result;
+        self.end_frame(result.result, result.gas.remaining(), result.gas.refunded());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame(inputs.scheme.into(), None, inputs.gas_limit);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some(index) = self.stack.last() {
+            self.frames[*index].address = outcome.address;
+        }
+        let result = &outcome.result;
+        self.end_frame(result.result, result.gas.remaining(), result.gas.refunded());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, AccountInfo, Bytecode, Bytes, TransactTo, U256},
+        Evm,
+    };
+
+    #[test]
+    fn test_frame_gas() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        let child = address!("3000000000000000000000000000000000000000");
+
+        // CALL(50000, child, 0, 0, 0, 0, 0) and STOP.
+        let mut code = vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(child.as_slice());
+        code.extend_from_slice(&[opcode::PUSH2, 0xc3, 0x50, opcode::CALL, opcode::STOP]);
+        let code = Bytecode::new_raw(Bytes::from(code));
+        // clear slot 0 and revert, refund of the child is discarded.
+        let child_code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::REVERT,
+        ]));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(contract, code), (child, child_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo {
+                    code_hash: code.hash_slow(),
+                    code: Some(code),
+                    ..Default::default()
+                },
+            );
+        }
+        db.insert_account_storage(child, U256::ZERO, U256::from(1))
+            .unwrap();
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(FrameGasInspector::new(true))
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .modify_block_env(|block| block.basefee = U256::ZERO)
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let frames = evm.into_context().external.into_frames();
+
+        assert_eq!(frames.len(), 2);
+        let (top, child) = (&frames[0], &frames[1]);
+        assert_eq!(top.children, vec![1]);
+        assert_eq!(child.parent, Some(0));
+        assert_eq!(child.depth, 1);
+        assert_eq!(child.result, InstructionResult::Revert);
+        assert_eq!(child.refunded, 0);
+        // PUSH1, DUP1, cold SSTORE reset, PUSH1, DUP1, REVERT.
+        assert_eq!(child.gas_used, 3 + 3 + 5000 + 3 + 3);
+        assert_eq!(child.self_gas_used, child.gas_used);
+
+        assert_eq!(top.gas_used, top.self_gas_used + child.gas_used);
+        let call = top
+            .opcodes
+            .iter()
+            .find(|op| op.opcode == opcode::CALL)
+            .unwrap();
+        // cold account access and the gas used by the child.
+        assert_eq!(call.gas_cost, 2600 + child.gas_used);
+        let opcodes_gas: u64 = top.opcodes.iter().map(|op| op.gas_cost).sum();
+        assert_eq!(opcodes_gas, top.gas_used);
+    }
+}
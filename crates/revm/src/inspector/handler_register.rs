@@ -2,8 +2,8 @@ use crate::{
     db::Database,
     handler::register::EvmHandler,
     interpreter::{opcode, opcode::BoxedInstruction, InstructionResult, Interpreter},
-    primitives::EVMError,
-    Evm, FrameOrResult, FrameResult, Inspector, JournalEntry,
+    primitives::{Address, EVMError},
+    Evm, EvmContext, FrameOrResult, FrameResult, Inspector,
 };
 use core::cell::RefCell;
 use revm_interpreter::opcode::InstructionTables;
@@ -31,7 +31,12 @@ impl<DB: Database, INSP: Inspector<DB>> GetInspector<DB> for INSP {
 ///
 /// A few instructions handlers are wrapped twice once for `step` and `step_end`
 /// and in case of Logs and Selfdestruct wrapper is wrapped again for the
-/// `log` and `selfdestruct` calls.
+/// `log` and `selfdestruct` calls. `SSTORE` and `SELFDESTRUCT` are wrapped for the
+/// `refund` call.
+///
+/// Every instruction is boxed and hooks are dispatched dynamically, so this register
+/// should only be appended when execution is inspected. Without it the plain
+/// instruction table is used and no hook is called.
 pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
     handler: &mut EvmHandler<'a, EXT, DB>,
 ) {
@@ -62,9 +67,8 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
                     // check if log was added. It is possible that revert happened
                     // cause of gas or stack underflow.
                     if host.context.evm.journaled_state.logs.len() == old_log_len + 1 {
-                        // clone log.
-                        // TODO decide if we should remove this and leave the comment
-                        // that log can be found as journaled_state.
+                        // clone log, logs of the reverted frames are removed from the
+                        // journaled state later so inspector sees every emitted log.
                         let last_log = host
                             .context
                             .evm
@@ -78,6 +82,7 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
                             .external
                             .get_inspector()
                             .log(&mut host.context.evm, &last_log);
+                        halt_on_abort(interpreter, &host.context.evm);
                     }
                 },
             )
@@ -90,38 +95,67 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
     inspect_log(opcode::LOG3);
     inspect_log(opcode::LOG4);
 
-    // // register selfdestruct function.
+    // register selfdestruct function.
     if let Some(i) = table.get_mut(opcode::SELFDESTRUCT as usize) {
         let old = core::mem::replace(i, Box::new(|_, _| ()));
         *i = Box::new(
             move |interpreter: &mut Interpreter, host: &mut Evm<'a, EXT, DB>| {
-                // execute selfdestruct
-                old(interpreter, host);
-                // check if selfdestruct was successful and if journal entry is made.
-                if let Some(JournalEntry::AccountDestroyed {
-                    address,
-                    target,
-                    had_balance,
-                    ..
-                }) = host
+                // balance and target are read before execution as after EIP-6780 account
+                // is not always destroyed and journal entry is not always made.
+                let contract = interpreter.contract.address;
+                let target = interpreter
+                    .stack
+                    .peek(0)
+                    .map(|target| Address::from_word(target.into()));
+                let balance = host
                     .context
                     .evm
                     .journaled_state
-                    .journal
-                    .last()
-                    .unwrap()
-                    .last()
+                    .state
+                    .get(&contract)
+                    .map(|account| account.info.balance)
+                    .unwrap_or_default();
+                // execute selfdestruct
+                old(interpreter, host);
+                // check if selfdestruct was successful.
+                if let (InstructionResult::SelfDestruct, Ok(target)) =
+                    (interpreter.instruction_result, target)
                 {
-                    host.context.external.get_inspector().selfdestruct(
-                        *address,
-                        *target,
-                        *had_balance,
-                    );
+                    host.context
+                        .external
+                        .get_inspector()
+                        .selfdestruct(contract, target, balance);
                 }
             },
         )
     }
 
+    // register refund hook of the instructions that change the refund counter.
+    let mut inspect_refund = |index: u8| {
+        if let Some(i) = table.get_mut(index as usize) {
+            let old = core::mem::replace(i, Box::new(|_, _| ()));
+            *i = Box::new(
+                move |interpreter: &mut Interpreter, host: &mut Evm<'a, EXT, DB>| {
+                    let old_refund = interpreter.gas.refunded();
+                    old(interpreter, host);
+                    let delta = interpreter.gas.refunded() - old_refund;
+                    if delta != 0 {
+                        host.context.external.get_inspector().refund(
+                            interpreter,
+                            &mut host.context.evm,
+                            index,
+                            delta,
+                        );
+                        halt_on_abort(interpreter, &host.context.evm);
+                    }
+                },
+            )
+        }
+    };
+
+    inspect_refund(opcode::SSTORE);
+    inspect_refund(opcode::SELFDESTRUCT);
+
     // cast vector to array.
     handler.instruction_table = Some(InstructionTables::Boxed(
         table.try_into().unwrap_or_else(|_| unreachable!()),
@@ -139,7 +173,9 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
         move |ctx, mut inputs| -> Result<FrameOrResult, EVMError<DB::Error>> {
             let inspector = ctx.external.get_inspector();
             // call inspector create to change input or return outcome.
-            if let Some(outcome) = inspector.create(&mut ctx.evm, &mut inputs) {
+            let outcome = inspector.create(&mut ctx.evm, &mut inputs);
+            core::mem::replace(&mut ctx.evm.error, Ok(()))?;
+            if let Some(outcome) = outcome {
                 create_input_stack_inner.borrow_mut().push(inputs.clone());
                 return Ok(FrameOrResult::Result(FrameResult::Create(outcome)));
             }
@@ -149,7 +185,8 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
 
             let inspector = ctx.external.get_inspector();
             if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
-                inspector.initialize_interp(&mut frame.frame_data_mut().interpreter, &mut ctx.evm)
+                inspector.initialize_interp(&mut frame.frame_data_mut().interpreter, &mut ctx.evm);
+                core::mem::replace(&mut ctx.evm.error, Ok(()))?;
             }
             frame_or_result
         },
@@ -163,7 +200,9 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
             let inspector = ctx.external.get_inspector();
             let _mems = inputs.return_memory_offset.clone();
             // call inspector callto change input or return outcome.
-            if let Some(outcome) = inspector.call(&mut ctx.evm, &mut inputs) {
+            let outcome = inspector.call(&mut ctx.evm, &mut inputs);
+            core::mem::replace(&mut ctx.evm.error, Ok(()))?;
+            if let Some(outcome) = outcome {
                 call_input_stack_inner.borrow_mut().push(inputs.clone());
                 return Ok(FrameOrResult::Result(FrameResult::Call(outcome)));
             }
@@ -172,9 +211,26 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
             let mut frame_or_result = old_handle(ctx, inputs);
 
             let inspector = ctx.external.get_inspector();
-            if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
-                inspector.initialize_interp(&mut frame.frame_data_mut().interpreter, &mut ctx.evm)
+            match &mut frame_or_result {
+                Ok(FrameOrResult::Frame(frame)) => inspector
+                    .initialize_interp(&mut frame.frame_data_mut().interpreter, &mut ctx.evm),
+                Ok(FrameOrResult::Result(FrameResult::Call(outcome))) => {
+                    let call_input_stack = call_input_stack_inner.borrow();
+                    let inputs = call_input_stack.last().unwrap();
+                    // other results are returned by the checks done before the precompile is called.
+                    let precompile_executed = matches!(
+                        outcome.result.result,
+                        InstructionResult::Return
+                            | InstructionResult::PrecompileOOG
+                            | InstructionResult::PrecompileError
+                    );
+                    if precompile_executed && ctx.evm.is_precompile(&inputs.contract) {
+                        inspector.precompile(&mut ctx.evm, inputs, &outcome.result);
+                    }
+                }
+                _ => {}
             }
+            core::mem::replace(&mut ctx.evm.error, Ok(()))?;
             frame_or_result
         },
     );
@@ -187,6 +243,7 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
             let inspector = ctx.external.get_inspector();
             let call_inputs = call_input_stack_inner.borrow_mut().pop().unwrap();
             outcome = inspector.call_end(&mut ctx.evm, &call_inputs, outcome);
+            core::mem::replace(&mut ctx.evm.error, Ok(()))?;
             old_handle(ctx, frame, shared_memory, outcome)
         });
 
@@ -197,6 +254,7 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
         let inspector = ctx.external.get_inspector();
         let create_inputs = create_input_stack_inner.borrow_mut().pop().unwrap();
         outcome = inspector.create_end(&mut ctx.evm, &create_inputs, outcome);
+        core::mem::replace(&mut ctx.evm.error, Ok(()))?;
         old_handle(ctx, frame, outcome)
     });
 
@@ -214,9 +272,19 @@ pub fn inspector_handle_register<'a, DB: Database, EXT: GetInspector<DB>>(
                 *outcome = inspector.create_end(&mut ctx.evm, &create_inputs, outcome.clone());
             }
         }
-        //inspector.last_frame_return(ctx, frame_result);
+        core::mem::replace(&mut ctx.evm.error, Ok(()))?;
         old_handle(ctx, frame_result)
     });
+
+    // transaction end
+    let old_handle = handler.post_execution.end.clone();
+    handler.post_execution.end = Arc::new(move |ctx, result| {
+        let result = old_handle(ctx, result);
+        ctx.external
+            .get_inspector()
+            .transaction_end(&mut ctx.evm, &result);
+        result
+    });
 }
 
 /// Outer closure that calls Inspector for every instruction.
@@ -238,6 +306,7 @@ pub fn inspector_instruction<
                 .external
                 .get_inspector()
                 .step(interpreter, &mut host.context.evm);
+            halt_on_abort(interpreter, &host.context.evm);
             if interpreter.instruction_result != InstructionResult::Continue {
                 return;
             }
@@ -252,10 +321,21 @@ pub fn inspector_instruction<
                 .external
                 .get_inspector()
                 .step_end(interpreter, &mut host.context.evm);
+            halt_on_abort(interpreter, &host.context.evm);
         },
     )
 }
 
+/// Halts the interpreter if the inspector aborted the execution by setting `context.error`.
+///
+/// Error is taken and returned by the main loop when the interpreter returns.
+#[inline]
+fn halt_on_abort<DB: Database>(interpreter: &mut Interpreter, context: &EvmContext<DB>) {
+    if context.error.is_err() {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -405,6 +485,365 @@ mod tests {
         assert!(inspector.call_end);
     }
 
+    #[test]
+    fn test_log_of_reverted_frame() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::opcode,
+            primitives::{address, Bytecode, Bytes, Log, TransactTo},
+            Evm,
+        };
+
+        #[derive(Default)]
+        struct LogInspector {
+            logs: Vec<Log>,
+        }
+
+        impl<DB: Database> Inspector<DB> for LogInspector {
+            fn log(&mut self, _context: &mut EvmContext<DB>, log: &Log) {
+                self.logs.push(log.clone());
+            }
+        }
+
+        // LOG1 with topic 0x1 and empty data then REVERT.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::LOG1,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::REVERT,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(LogInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap();
+        assert!(result.result.logs().is_empty());
+
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.logs.len(), 1);
+        assert_eq!(
+            inspector.logs[0].topics(),
+            &[crate::primitives::B256::with_last_byte(1)]
+        );
+    }
+
+    #[test]
+    fn test_selfdestruct_after_cancun() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::opcode,
+            primitives::{address, Address, Bytecode, Bytes, TransactTo, U256},
+            Evm,
+        };
+
+        #[derive(Default)]
+        struct SelfdestructInspector {
+            destroyed: Vec<(Address, Address, U256)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for SelfdestructInspector {
+            fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+                self.destroyed.push((contract, target, value));
+            }
+        }
+
+        // SELFDESTRUCT to 0x01, contract is not created in the transaction
+        // so after EIP-6780 only the balance is transferred.
+        let contract_data: Bytes = Bytes::from(vec![opcode::PUSH1, 0x1, opcode::SELFDESTRUCT]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(SelfdestructInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.destroyed,
+            vec![(
+                Address::ZERO,
+                Address::with_last_byte(1),
+                U256::from(10_000_000)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_journaled_state_in_hooks() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::opcode,
+            primitives::{address, Address, Bytecode, Bytes, TransactTo, U256},
+            Evm,
+        };
+
+        /// State seen before the `STOP`.
+        #[derive(Default)]
+        struct StateInspector {
+            seen: Option<(u64, Option<U256>, U256, usize)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for StateInspector {
+            fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+                if interp.current_opcode() != opcode::STOP {
+                    return;
+                }
+                let state = &context.journaled_state;
+                let address = interp.contract.address;
+                self.seen = Some((
+                    state.depth(),
+                    state.get_storage(&address, &U256::from(1)),
+                    state.get_account(&address).unwrap().info.balance,
+                    state.logs().len(),
+                ));
+            }
+        }
+
+        // SSTORE 5 into slot 1, LOG0 with empty data and STOP.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x5,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::LOG0,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(StateInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.seen,
+            Some((1, Some(U256::from(5)), U256::from(10_000_000), 1))
+        );
+    }
+
+    #[test]
+    fn test_precompile_hook() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::{opcode, InterpreterResult},
+            primitives::{address, Address, Bytecode, Bytes, TransactTo},
+            Evm,
+        };
+
+        #[derive(Default)]
+        struct PrecompileInspector {
+            calls: Vec<(Address, Bytes, InstructionResult, u64)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for PrecompileInspector {
+            fn precompile(
+                &mut self,
+                _context: &mut EvmContext<DB>,
+                inputs: &CallInputs,
+                result: &InterpreterResult,
+            ) {
+                self.calls.push((
+                    inputs.contract,
+                    inputs.input.clone(),
+                    result.result,
+                    result.gas.spend(),
+                ));
+            }
+        }
+
+        // STATICCALL the identity precompile with 32 zero bytes.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x20,
+            opcode::DUP2,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(PrecompileInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.calls,
+            vec![(
+                Address::with_last_byte(4),
+                Bytes::from(vec![0; 32]),
+                InstructionResult::Return,
+                18
+            )]
+        );
+    }
+
+    #[test]
+    fn test_refund_hook() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::opcode,
+            primitives::{address, Address, Bytecode, Bytes, TransactTo},
+            Evm,
+        };
+
+        #[derive(Default)]
+        struct RefundInspector {
+            refunds: Vec<(u8, i64, i64)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for RefundInspector {
+            fn refund(
+                &mut self,
+                interp: &mut Interpreter,
+                _context: &mut EvmContext<DB>,
+                opcode: u8,
+                delta: i64,
+            ) {
+                self.refunds.push((opcode, delta, interp.gas.refunded()));
+            }
+        }
+
+        // SSTORE 1 and then 0 into empty slot 0.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(RefundInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        // slot is restored to its original value, SSTORE_SET - WARM_STORAGE_READ_COST is refunded.
+        assert_eq!(
+            evm.into_context().external.refunds,
+            vec![(opcode::SSTORE, 19_900, 19_900)]
+        );
+    }
+
+    #[test]
+    fn test_transaction_end() {
+        use crate::{
+            db::BenchmarkDB,
+            inspector::inspector_handle_register,
+            interpreter::opcode,
+            primitives::{address, Address, Bytecode, Bytes, EVMError, ResultAndState, TransactTo},
+            Evm,
+        };
+
+        #[derive(Default)]
+        struct EndInspector {
+            frames: usize,
+            /// Gas used and whether the contract is in the state.
+            end: Option<(u64, bool)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for EndInspector {
+            fn initialize_interp(&mut self, _interp: &mut Interpreter, _: &mut EvmContext<DB>) {
+                self.frames += 1;
+            }
+
+            fn transaction_end(
+                &mut self,
+                context: &mut EvmContext<DB>,
+                result: &Result<ResultAndState, EVMError<DB::Error>>,
+            ) {
+                // journaled state is already finalized.
+                assert!(context.journaled_state.state.is_empty());
+                if let Ok(result) = result {
+                    self.end = Some((
+                        result.result.gas_used(),
+                        result.state.contains_key(&Address::ZERO),
+                    ));
+                }
+            }
+        }
+
+        let contract_data: Bytes = Bytes::from(vec![opcode::PUSH1, 0x1, opcode::STOP]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(EndInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.frames, 1);
+        assert_eq!(inspector.end, Some((21_003, true)));
+    }
+
     #[test]
     fn test_inspector_reg() {
         let mut noop = NoOpInspector;
@@ -0,0 +1,123 @@
+//! InvariantInspector. Aborts the execution when the invariant is violated.
+
+use crate::{
+    interpreter::Interpreter,
+    primitives::{db::Database, EVMError},
+    EvmContext, Inspector,
+};
+use std::string::String;
+
+/// [Inspector] that checks the invariant after every executed instruction.
+///
+/// When the check returns an error the execution is aborted right away and the
+/// transaction returns [EVMError::Custom] with the reason.
+pub struct InvariantInspector<F> {
+    check: F,
+    violation: Option<String>,
+}
+
+impl<F> core::fmt::Debug for InvariantInspector<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InvariantInspector")
+            .field("violation", &self.violation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> InvariantInspector<F> {
+    /// Creates new inspector with the invariant check.
+    pub fn new(check: F) -> Self {
+        Self {
+            check,
+            violation: None,
+        }
+    }
+
+    /// Returns the reason of the violated invariant, if any.
+    pub fn violation(&self) -> Option<&str> {
+        self.violation.as_deref()
+    }
+}
+
+impl<DB, F> Inspector<DB> for InvariantInspector<F>
+where
+    DB: Database,
+    F: FnMut(&Interpreter, &EvmContext<DB>) -> Result<(), String>,
+{
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if let Err(reason) = (self.check)(interp, context) {
+            context.error = Err(EVMError::Custom(reason.clone()));
+            self.violation = Some(reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Address, Bytecode, Bytes, TransactTo, U256},
+        Evm,
+    };
+    use std::format;
+
+    #[test]
+    fn test_abort_on_violation() {
+        // SSTORE 1, 5 and 2 into slot 0.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x5,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let mut steps = 0;
+        let inspector =
+            InvariantInspector::new(|interp: &Interpreter, context: &EvmContext<BenchmarkDB>| {
+                steps += 1;
+                let value = context
+                    .journaled_state
+                    .get_storage(&interp.contract.address, &U256::ZERO)
+                    .unwrap_or_default();
+                if value > U256::from(2) {
+                    return Err(format!("slot 0 is {value}"));
+                }
+                Ok(())
+            });
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(inspector)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        assert_eq!(
+            evm.transact(),
+            Err(EVMError::Custom(String::from("slot 0 is 5")))
+        );
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.violation(), Some("slot 0 is 5"));
+        drop(inspector);
+        // execution stopped after the second SSTORE.
+        assert_eq!(steps, 6);
+    }
+}
@@ -0,0 +1,75 @@
+//! Human-readable labels of the addresses used by the tracers.
+
+use crate::primitives::Address;
+use core::fmt;
+use std::{collections::BTreeMap, string::String};
+
+/// Human-readable labels of the addresses, e.g. `WETH` or `Router`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AddressLabels {
+    labels: BTreeMap<Address, String>,
+}
+
+impl AddressLabels {
+    /// Creates empty labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the label of the address, returns the previous label.
+    pub fn insert(&mut self, address: Address, label: impl Into<String>) -> Option<String> {
+        self.labels.insert(address, label.into())
+    }
+
+    /// Removes the label of the address.
+    pub fn remove(&mut self, address: &Address) -> Option<String> {
+        self.labels.remove(address)
+    }
+
+    /// Returns the label of the address.
+    pub fn get(&self, address: &Address) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// Returns true if there are no labels.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns displayable address, `label (0x..)` if address has a label.
+    pub fn display(&self, address: Address) -> LabeledAddress<'_> {
+        LabeledAddress {
+            address,
+            label: self.get(&address),
+        }
+    }
+}
+
+impl<L: Into<String>> FromIterator<(Address, L)> for AddressLabels {
+    fn from_iter<T: IntoIterator<Item = (Address, L)>>(iter: T) -> Self {
+        Self {
+            labels: iter
+                .into_iter()
+                .map(|(address, label)| (address, label.into()))
+                .collect(),
+        }
+    }
+}
+
+/// Address with the optional label, see [AddressLabels::display].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LabeledAddress<'a> {
+    pub address: Address,
+    pub label: Option<&'a str>,
+}
+
+impl fmt::Display for LabeledAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label {
+            Some(label) => write!(f, "{label} ({})", self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Geth `muxTracer` compatible inspector.
+//!
+//! Output format is described in
+//! <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#mux-tracer>.
+
+use super::{
+    call_tracer::{CallTraceFrame, CallTracer, CallTracerConfig},
+    four_byte::FourByteInspector,
+    prestate_tracer::{PrestateAccounts, PrestateDiff, PrestateTracer},
+};
+use crate::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, Address, EVMError, Log, ResultAndState, State, U256},
+    EvmContext, Inspector,
+};
+use std::{collections::BTreeMap, string::String};
+
+/// Configuration of the `prestateTracer` inside the [MuxTracer].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct PrestateTracerConfig {
+    /// Return pre and post state of the changed accounts.
+    pub diff_mode: bool,
+}
+
+/// Configuration of the `4byteTracer` inside the [MuxTracer], it has no options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FourByteTracerConfig {}
+
+/// Configuration of the [MuxTracer], keyed by the geth name of the tracer.
+///
+/// Only the tracers that are set are run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MuxTracerConfig {
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "callTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub call_tracer: Option<CallTracerConfig>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "prestateTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub prestate_tracer: Option<PrestateTracerConfig>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "4byteTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub four_byte_tracer: Option<FourByteTracerConfig>,
+}
+
+/// Output of the `prestateTracer`, depends on the `diffMode`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum PrestateOutput {
+    Diff(PrestateDiff),
+    Prestate(PrestateAccounts),
+}
+
+/// Output of the [MuxTracer], keyed by the geth name of the tracer.
+///
+/// Tracers that were not configured are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MuxTraceOutput {
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "callTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub call_tracer: Option<CallTraceFrame>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "prestateTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub prestate_tracer: Option<PrestateOutput>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "4byteTracer", skip_serializing_if = "Option::is_none")
+    )]
+    pub four_byte_tracer: Option<BTreeMap<String, u64>>,
+}
+
+/// [Inspector] that runs several geth built-in tracers in a single execution.
+#[derive(Clone, Debug, Default)]
+pub struct MuxTracer {
+    config: MuxTracerConfig,
+    call_tracer: Option<CallTracer>,
+    prestate_tracer: Option<PrestateTracer>,
+    four_byte_tracer: Option<FourByteInspector>,
+}
+
+impl MuxTracer {
+    /// Creates new mux tracer that runs the tracers set in the config.
+    pub fn new(config: MuxTracerConfig) -> Self {
+        Self {
+            config,
+            call_tracer: config.call_tracer.map(CallTracer::new),
+            prestate_tracer: config.prestate_tracer.map(|_| PrestateTracer::new()),
+            four_byte_tracer: config.four_byte_tracer.map(|_| FourByteInspector::new()),
+        }
+    }
+
+    /// Returns the config of the tracer.
+    pub fn config(&self) -> &MuxTracerConfig {
+        &self.config
+    }
+
+    /// Consumes the tracer and returns outputs of all configured tracers.
+    ///
+    /// `state` is the state returned by the transaction, used by the `prestateTracer` in `diffMode`.
+    pub fn into_output(self, state: &State) -> MuxTraceOutput {
+        let diff_mode = self.config.prestate_tracer.is_some_and(|c| c.diff_mode);
+        MuxTraceOutput {
+            call_tracer: self.call_tracer.and_then(CallTracer::into_frame),
+            prestate_tracer: self.prestate_tracer.map(|tracer| {
+                if diff_mode {
+                    PrestateOutput::Diff(tracer.into_diff(state))
+                } else {
+                    PrestateOutput::Prestate(tracer.into_prestate())
+                }
+            }),
+            four_byte_tracer: self.four_byte_tracer.map(|tracer| tracer.output()),
+        }
+    }
+
+    /// Returns the configured tracers.
+    fn tracers<DB: Database>(&mut self) -> [Option<&mut dyn Inspector<DB>>; 3] {
+        [
+            self.call_tracer.as_mut().map(|t| t as _),
+            self.prestate_tracer.as_mut().map(|t| t as _),
+            self.four_byte_tracer.as_mut().map(|t| t as _),
+        ]
+    }
+}
+
+impl<DB: Database> Inspector<DB> for MuxTracer {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.initialize_interp(interp, context);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.step(interp, context);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.step_end(interp, context);
+        }
+    }
+
+    fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.log(context, log);
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        // built-in tracers never override the call.
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.call(context, inputs);
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        mut outcome: CallOutcome,
+    ) -> CallOutcome {
+        for tracer in self.tracers().into_iter().flatten() {
+            outcome = tracer.call_end(context, inputs, outcome);
+        }
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.create(context, inputs);
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        mut outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        for tracer in self.tracers().into_iter().flatten() {
+            outcome = tracer.create_end(context, inputs, outcome);
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        for tracer in self.tracers::<DB>().into_iter().flatten() {
+            tracer.selfdestruct(contract, target, value);
+        }
+    }
+
+    fn refund(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<DB>,
+        opcode: u8,
+        delta: i64,
+    ) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.refund(interp, context, opcode, delta);
+        }
+    }
+
+    fn transaction_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        result: &Result<ResultAndState, EVMError<DB::Error>>,
+    ) {
+        for tracer in self.tracers().into_iter().flatten() {
+            tracer.transaction_end(context, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_mux_tracer() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract_data: Bytes = Bytes::from(vec![opcode::PUSH1, 0x1, opcode::STOP]);
+        let config = MuxTracerConfig {
+            call_tracer: Some(CallTracerConfig::default()),
+            prestate_tracer: None,
+            four_byte_tracer: Some(FourByteTracerConfig::default()),
+        };
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(MuxTracer::new(config))
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.data = Bytes::from_static(&[0xaa, 0xbb, 0xcc, 0xdd, 0x01]);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().unwrap();
+
+        let output = evm.into_context().external.into_output(&result.state);
+        let frame = output.call_tracer.unwrap();
+        assert_eq!(frame.from, caller);
+        assert_eq!(frame.to, Some(Address::ZERO));
+        assert_eq!(output.prestate_tracer, None);
+        assert_eq!(
+            output.four_byte_tracer,
+            Some(BTreeMap::from([(String::from("0xaabbccdd-1"), 1)]))
+        );
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_mux_config_keys() {
+        let config: MuxTracerConfig = serde_json::from_str(
+            r#"{"callTracer":{"onlyTopCall":true,"withLog":false},"prestateTracer":{"diffMode":true},"4byteTracer":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            MuxTracerConfig {
+                call_tracer: Some(CallTracerConfig {
+                    only_top_call: true,
+                    with_log: false,
+                }),
+                prestate_tracer: Some(PrestateTracerConfig { diff_mode: true }),
+                four_byte_tracer: Some(FourByteTracerConfig {}),
+            }
+        );
+    }
+}
@@ -1,5 +1,10 @@
 use crate::{Database, Inspector};
 /// Dummy [Inspector], helpful as standalone replacement.
+///
+/// Inspector hooks are only called when [crate::inspector_handle_register] is appended to
+/// the handler. Execution without the register uses the plain instruction table and
+/// has no inspector overhead, so there is no need to register this inspector when
+/// execution is not traced.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NoOpInspector;
 
@@ -0,0 +1,247 @@
+//! OpcodeStatsInspector. Profiling statistics of the executed instructions.
+
+use crate::{
+    interpreter::{
+        opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+        InterpreterResult,
+    },
+    primitives::db::Database,
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// Execution statistics of the single opcode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeStat {
+    /// Number of times opcode was executed.
+    pub count: u64,
+    /// Gas spent by all executions of the opcode.
+    ///
+    /// For `CALL` and `CREATE` opcodes it includes the gas used by the child frames.
+    pub gas: u64,
+}
+
+/// [Inspector] that collects opcode frequency, cumulative gas per opcode and
+/// maximum stack and memory size over the transaction.
+#[derive(Clone, Debug)]
+pub struct OpcodeStatsInspector {
+    opcodes: [OpcodeStat; 256],
+    max_stack_len: usize,
+    max_memory_len: usize,
+    /// Gas remaining before the currently executing instruction.
+    gas_remaining: u64,
+    /// Last executed opcode.
+    last_opcode: Option<u8>,
+    /// Opcodes that started currently executing frames, `None` for the top level frame.
+    frame_opcodes: Vec<Option<u8>>,
+}
+
+impl Default for OpcodeStatsInspector {
+    fn default() -> Self {
+        Self {
+            opcodes: [OpcodeStat::default(); 256],
+            max_stack_len: 0,
+            max_memory_len: 0,
+            gas_remaining: 0,
+            last_opcode: None,
+            frame_opcodes: Vec::new(),
+        }
+    }
+}
+
+impl OpcodeStatsInspector {
+    /// Creates new opcode statistics inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns statistics of the given opcode.
+    pub fn opcode(&self, opcode: u8) -> OpcodeStat {
+        self.opcodes[opcode as usize]
+    }
+
+    /// Returns statistics of all executed opcodes, with their names.
+    pub fn executed(&self) -> impl Iterator<Item = (u8, &'static str, OpcodeStat)> + '_ {
+        self.opcodes
+            .iter()
+            .enumerate()
+            .filter(|(_, stat)| stat.count != 0)
+            .map(|(opcode, stat)| {
+                let name = opcode::OPCODE_JUMPMAP[opcode].unwrap_or("UNKNOWN");
+                (opcode as u8, name, *stat)
+            })
+    }
+
+    /// Returns the number of executed instructions.
+    pub fn total_count(&self) -> u64 {
+        self.opcodes.iter().map(|stat| stat.count).sum()
+    }
+
+    /// Returns the maximum number of items on the stack of any frame.
+    pub fn max_stack_len(&self) -> usize {
+        self.max_stack_len
+    }
+
+    /// Returns the maximum memory size in bytes of any frame.
+    pub fn max_memory_len(&self) -> usize {
+        self.max_memory_len
+    }
+
+    fn start_frame(&mut self) {
+        self.frame_opcodes.push(self.last_opcode.take());
+    }
+
+    /// Unused gas of the child frame is returned to the opcode that started it.
+    fn end_frame(&mut self, gas_limit: u64, result: &InterpreterResult) {
+        let Some(Some(opcode)) = self.frame_opcodes.pop() else {
+            return;
+        };
+        if result.is_ok() || result.is_revert() {
+            let returned = result.gas.remaining().min(gas_limit);
+            let stat = &mut self.opcodes[opcode as usize];
+            stat.gas = stat.gas.saturating_sub(returned);
+        }
+        self.last_opcode = Some(opcode);
+    }
+}
+
+impl<DB: Database> Inspector<DB> for OpcodeStatsInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        self.opcodes[opcode as usize].count += 1;
+        self.last_opcode = Some(opcode);
+        self.gas_remaining = interp.gas.remaining();
+        self.max_stack_len = self.max_stack_len.max(interp.stack.len());
+        self.max_memory_len = self.max_memory_len.max(interp.shared_memory.len());
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(opcode) = self.last_opcode {
+            self.opcodes[opcode as usize].gas += self.gas_remaining - interp.gas.remaining();
+        }
+        self.max_stack_len = self.max_stack_len.max(interp.stack.len());
+        self.max_memory_len = self.max_memory_len.max(interp.shared_memory.len());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame();
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(inputs.gas_limit, &outcome.result);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(inputs.gas_limit, &outcome.result);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_opcode_stats() {
+        // MSTORE 1 at offset 0, STATICCALL the identity precompile with 32 bytes.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x20,
+            opcode::DUP2,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(OpcodeStatsInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let stats = evm.into_context().external;
+
+        assert_eq!(stats.total_count(), 11);
+        assert_eq!(
+            stats.opcode(opcode::PUSH1),
+            OpcodeStat { count: 5, gas: 15 }
+        );
+        // MSTORE cost with memory expansion.
+        assert_eq!(
+            stats.opcode(opcode::MSTORE),
+            OpcodeStat { count: 1, gas: 6 }
+        );
+        // warm precompile access and 15 + 3 gas used by the identity precompile.
+        assert_eq!(
+            stats.opcode(opcode::STATICCALL),
+            OpcodeStat {
+                count: 1,
+                gas: 100 + 18
+            }
+        );
+        assert_eq!(stats.max_stack_len(), 6);
+        assert_eq!(stats.max_memory_len(), 32);
+        assert_eq!(
+            stats
+                .executed()
+                .map(|(_, name, _)| name)
+                .collect::<Vec<_>>(),
+            vec![
+                "STOP",
+                "MSTORE",
+                "GAS",
+                "PUSH1",
+                "DUP1",
+                "DUP2",
+                "STATICCALL"
+            ]
+        );
+    }
+}
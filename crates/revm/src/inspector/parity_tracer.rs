@@ -0,0 +1,893 @@
+//! Parity (OpenEthereum) `trace_replayTransaction` compatible inspector.
+//!
+//! Produces `trace`, `vmTrace` and `stateDiff` outputs as described in
+//! <https://openethereum.github.io/JSONRPC-trace-module>.
+
+use super::{call_tracer::geth_error_message, prestate_tracer::PrestateTracer};
+use crate::{
+    interpreter::{
+        opcode, CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome,
+        InstructionResult, Interpreter,
+    },
+    primitives::{db::Database, Address, Bytes, ResultAndState, State, B256, U256},
+    EvmContext, Inspector,
+};
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+/// Type of the traced call, `callType` field of the call action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ParityCallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+}
+
+impl From<CallScheme> for ParityCallType {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call | CallScheme::ExtCall => Self::Call,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall | CallScheme::ExtDelegateCall => Self::DelegateCall,
+            CallScheme::StaticCall | CallScheme::ExtStaticCall => Self::StaticCall,
+        }
+    }
+}
+
+/// Action of the `call` trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityCallAction {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas: u64,
+    pub input: Bytes,
+    pub call_type: ParityCallType,
+}
+
+/// Action of the `create` trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParityCreateAction {
+    pub from: Address,
+    pub value: U256,
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas: u64,
+    pub init: Bytes,
+}
+
+/// Action of the `suicide` trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParitySelfdestructAction {
+    pub address: Address,
+    pub refund_address: Address,
+    pub balance: U256,
+}
+
+/// Action of the trace, serialized as `type` and `action` fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "action", rename_all = "lowercase")
+)]
+pub enum ParityAction {
+    Call(ParityCallAction),
+    Create(ParityCreateAction),
+    #[cfg_attr(feature = "serde", serde(rename = "suicide"))]
+    Selfdestruct(ParitySelfdestructAction),
+}
+
+/// Result of the successful `call` trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityCallResult {
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas_used: u64,
+    pub output: Bytes,
+}
+
+/// Result of the successful `create` trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityCreateResult {
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_hex::u64_hex"))]
+    pub gas_used: u64,
+    pub code: Bytes,
+    pub address: Address,
+}
+
+/// Result of the successful trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum ParityTraceResult {
+    Call(ParityCallResult),
+    Create(ParityCreateResult),
+}
+
+/// Single entry of the flat `trace` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityTrace {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub action: ParityAction,
+    /// Result of the trace, `None` if it failed or it is a `suicide` trace.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub result: Option<ParityTraceResult>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<String>,
+    /// Number of direct child traces.
+    pub subtraces: usize,
+    /// Path of the trace in the call tree, empty for the top level call.
+    pub trace_address: Vec<usize>,
+}
+
+/// Memory written by the instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryDelta {
+    pub off: usize,
+    pub data: Bytes,
+}
+
+/// Storage slot written by the instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageDelta {
+    pub key: U256,
+    pub val: U256,
+}
+
+/// Effects of the executed instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VmExecutedOperation {
+    /// Gas remaining after the instruction.
+    pub used: u64,
+    /// Stack items pushed by the instruction.
+    pub push: Vec<U256>,
+    pub mem: Option<MemoryDelta>,
+    pub store: Option<StorageDelta>,
+}
+
+/// Single instruction of the `vmTrace` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VmInstruction {
+    pub pc: usize,
+    pub cost: u64,
+    /// `None` if the instruction failed.
+    pub ex: Option<VmExecutedOperation>,
+    /// Trace of the frame created by the instruction.
+    pub sub: Option<VmTrace>,
+}
+
+/// Instructions executed by the frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VmTrace {
+    pub code: Bytes,
+    pub ops: Vec<VmInstruction>,
+}
+
+/// Old and new value of the changed field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangedValue<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// Change of the single account field in the `stateDiff` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Delta<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "="))]
+    Unchanged,
+    #[cfg_attr(feature = "serde", serde(rename = "+"))]
+    Added(T),
+    #[cfg_attr(feature = "serde", serde(rename = "-"))]
+    Removed(T),
+    #[cfg_attr(feature = "serde", serde(rename = "*"))]
+    Changed(ChangedValue<T>),
+}
+
+impl<T: PartialEq> Delta<T> {
+    /// Returns [Delta::Changed] if values differ, [Delta::Unchanged] otherwise.
+    pub fn changed(from: T, to: T) -> Self {
+        if from == to {
+            Self::Unchanged
+        } else {
+            Self::Changed(ChangedValue { from, to })
+        }
+    }
+
+    /// Returns true if value is unchanged.
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Self::Unchanged)
+    }
+}
+
+/// Changes of the single account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDiff {
+    pub balance: Delta<U256>,
+    pub nonce: Delta<U256>,
+    pub code: Delta<Bytes>,
+    pub storage: BTreeMap<B256, Delta<B256>>,
+}
+
+/// Changed accounts keyed by address.
+pub type StateDiff = BTreeMap<Address, AccountDiff>;
+
+/// Output of the `trace_replayTransaction`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityTraceResults {
+    pub output: Bytes,
+    /// Flat traces, empty if not requested.
+    pub trace: Vec<ParityTrace>,
+    pub vm_trace: Option<VmTrace>,
+    pub state_diff: Option<StateDiff>,
+}
+
+/// Trace types to record, matches the trace types of `trace_replayTransaction`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ParityTracerConfig {
+    pub trace: bool,
+    pub vm_trace: bool,
+    pub state_diff: bool,
+}
+
+impl ParityTracerConfig {
+    /// Config with all trace types enabled.
+    pub fn all() -> Self {
+        Self {
+            trace: true,
+            vm_trace: true,
+            state_diff: true,
+        }
+    }
+}
+
+/// Instruction that is executing, its effects are recorded in `step_end`.
+#[derive(Clone, Debug)]
+struct PendingOp {
+    gas_remaining: u64,
+    /// Memory range that instruction writes to.
+    mem: Option<(usize, usize)>,
+    store: Option<StorageDelta>,
+}
+
+/// `vmTrace` of the currently executing frame.
+#[derive(Clone, Debug, Default)]
+struct VmFrame {
+    trace: VmTrace,
+    pending: Option<PendingOp>,
+    /// Number of stack items pushed by the last instruction.
+    ///
+    /// Pushed items are read on the next step as result of `CALL` and `CREATE`
+    /// is only known after the child frame returns.
+    pending_push: usize,
+}
+
+/// [Inspector] that records Parity style `trace`, `vmTrace` and `stateDiff`.
+///
+/// Use [ParityTracer::into_results] with the result of the transaction to get the output.
+#[derive(Clone, Debug, Default)]
+pub struct ParityTracer {
+    config: ParityTracerConfig,
+    traces: Vec<ParityTrace>,
+    /// Indices of the traces of the frames that are currently executing.
+    trace_stack: Vec<usize>,
+    vm_stack: Vec<VmFrame>,
+    vm_trace: Option<VmTrace>,
+    /// Records the pre-state for the `stateDiff`.
+    prestate: PrestateTracer,
+}
+
+impl ParityTracer {
+    /// Creates new parity tracer with the given config.
+    pub fn new(config: ParityTracerConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the config of the tracer.
+    pub fn config(&self) -> &ParityTracerConfig {
+        &self.config
+    }
+
+    /// Returns the flat traces recorded so far.
+    pub fn traces(&self) -> &[ParityTrace] {
+        &self.traces
+    }
+
+    /// Returns the `vmTrace` of the top level frame if execution is finished.
+    pub fn vm_trace(&self) -> Option<&VmTrace> {
+        self.vm_trace.as_ref()
+    }
+
+    /// Returns the changes of the accounts accessed by the transaction.
+    ///
+    /// `state` is the state returned by the transaction.
+    pub fn state_diff(&self, state: &State) -> StateDiff {
+        let mut diff = StateDiff::new();
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue;
+            }
+            let pre = self.prestate.prestate().get(address);
+            let pre_balance = pre.and_then(|pre| pre.balance).unwrap_or_default();
+            let pre_nonce = U256::from(pre.and_then(|pre| pre.nonce).unwrap_or_default());
+            let pre_code = pre.and_then(|pre| pre.code.clone()).unwrap_or_default();
+            let pre_exists =
+                pre_balance != U256::ZERO || pre_nonce != U256::ZERO || !pre_code.is_empty();
+            let post_exists = !account.is_selfdestructed() && !account.is_empty();
+
+            let post_code = account
+                .info
+                .code
+                .as_ref()
+                .map(|code| code.original_bytes())
+                .unwrap_or_default();
+            let post_nonce = U256::from(account.info.nonce);
+
+            let account_diff = match (pre_exists, post_exists) {
+                (false, false) => continue,
+                (false, true) => AccountDiff {
+                    balance: Delta::Added(account.info.balance),
+                    nonce: Delta::Added(post_nonce),
+                    code: Delta::Added(post_code),
+                    storage: account
+                        .changed_storage_slots()
+                        .filter(|(_, slot)| slot.present_value != U256::ZERO)
+                        .map(|(key, slot)| ((*key).into(), Delta::Added(slot.present_value.into())))
+                        .collect(),
+                },
+                (true, false) => AccountDiff {
+                    balance: Delta::Removed(pre_balance),
+                    nonce: Delta::Removed(pre_nonce),
+                    code: Delta::Removed(pre_code),
+                    storage: pre
+                        .map(|pre| {
+                            pre.storage
+                                .iter()
+                                .filter(|(_, value)| **value != B256::ZERO)
+                                .map(|(key, value)| (*key, Delta::Removed(*value)))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                },
+                (true, true) => AccountDiff {
+                    balance: Delta::changed(pre_balance, account.info.balance),
+                    nonce: Delta::changed(pre_nonce, post_nonce),
+                    code: Delta::changed(pre_code, post_code),
+                    storage: account
+                        .changed_storage_slots()
+                        .map(|(key, slot)| {
+                            (
+                                (*key).into(),
+                                Delta::changed(
+                                    slot.previous_or_original_value.into(),
+                                    slot.present_value.into(),
+                                ),
+                            )
+                        })
+                        .filter(|(_, delta)| !delta.is_unchanged())
+                        .collect(),
+                },
+            };
+
+            if account_diff.balance.is_unchanged()
+                && account_diff.nonce.is_unchanged()
+                && account_diff.code.is_unchanged()
+                && account_diff.storage.is_empty()
+            {
+                continue;
+            }
+            diff.insert(*address, account_diff);
+        }
+        diff
+    }
+
+    /// Consumes the tracer and returns the output of the requested trace types.
+    pub fn into_results(self, result: &ResultAndState) -> ParityTraceResults {
+        let state_diff = self
+            .config
+            .state_diff
+            .then(|| self.state_diff(&result.state));
+        ParityTraceResults {
+            output: result.result.output().cloned().unwrap_or_default(),
+            trace: self.traces,
+            vm_trace: self.vm_trace,
+            state_diff,
+        }
+    }
+
+    /// Pushes new trace as a child of the currently executing frame.
+    ///
+    /// Returns the index of the pushed trace.
+    fn push_trace(&mut self, action: ParityAction) -> usize {
+        let trace_address = match self.trace_stack.last() {
+            Some(&parent) => {
+                let parent = &mut self.traces[parent];
+                let mut trace_address = parent.trace_address.clone();
+                trace_address.push(parent.subtraces);
+                parent.subtraces += 1;
+                trace_address
+            }
+            None => Vec::new(),
+        };
+        self.traces.push(ParityTrace {
+            action,
+            result: None,
+            error: None,
+            subtraces: 0,
+            trace_address,
+        });
+        self.traces.len() - 1
+    }
+
+    fn start_frame(&mut self, action: ParityAction) {
+        if self.config.trace {
+            let index = self.push_trace(action);
+            self.trace_stack.push(index);
+        }
+        if self.config.vm_trace {
+            self.vm_stack.push(VmFrame::default());
+        }
+    }
+
+    fn end_frame(&mut self, result: InstructionResult, output: ParityTraceResult) {
+        if self.config.trace {
+            if let Some(index) = self.trace_stack.pop() {
+                let trace = &mut self.traces[index];
+                if result.is_ok() {
+                    trace.result = Some(output);
+                } else {
+                    trace.error = Some(parity_error_message(result));
+                }
+            }
+        }
+        if self.config.vm_trace {
+            if let Some(frame) = self.vm_stack.pop() {
+                match self
+                    .vm_stack
+                    .last_mut()
+                    .and_then(|parent| parent.trace.ops.last_mut())
+                {
+                    Some(op) => op.sub = Some(frame.trace),
+                    None => self.vm_trace = Some(frame.trace),
+                }
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ParityTracer {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(frame) = self.vm_stack.last_mut() {
+            frame.trace.code = interp.contract.bytecode.original_bytecode();
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.config.state_diff {
+            self.prestate.step(interp, context);
+        }
+        let Some(frame) = self.vm_stack.last_mut() else {
+            return;
+        };
+        let stack = interp.stack().data();
+        if let Some(op) = frame.trace.ops.last_mut() {
+            if let Some(ex) = op.ex.as_mut() {
+                let push = frame.pending_push.min(stack.len());
+                ex.push = stack[stack.len() - push..].to_vec();
+            }
+        }
+        frame.pending_push = 0;
+
+        let peek = |n: usize| -> usize {
+            interp
+                .stack()
+                .peek(n)
+                .map(|value| value.saturating_to())
+                .unwrap_or_default()
+        };
+        let opcode = interp.current_opcode();
+        let mem = match opcode {
+            opcode::MSTORE => Some((peek(0), 32)),
+            opcode::MSTORE8 => Some((peek(0), 1)),
+            opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY | opcode::MCOPY => {
+                Some((peek(0), peek(2)))
+            }
+            opcode::EXTCODECOPY => Some((peek(1), peek(3))),
+            _ => None,
+        };
+        let store = match (opcode, interp.stack().peek(0), interp.stack().peek(1)) {
+            (opcode::SSTORE, Ok(key), Ok(val)) => Some(StorageDelta { key, val }),
+            _ => None,
+        };
+        frame.pending = Some(PendingOp {
+            gas_remaining: interp.gas().remaining(),
+            mem,
+            store,
+        });
+        frame.trace.ops.push(VmInstruction {
+            pc: interp.program_counter(),
+            cost: 0,
+            ex: None,
+            sub: None,
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(frame) = self.vm_stack.last_mut() else {
+            return;
+        };
+        let (Some(pending), Some(op)) = (frame.pending.take(), frame.trace.ops.last_mut()) else {
+            return;
+        };
+        let remaining = interp.gas().remaining();
+        op.cost = pending.gas_remaining.saturating_sub(remaining);
+        if interp.instruction_result.is_error() {
+            return;
+        }
+        // program counter already points to the next instruction.
+        let executed = interp
+            .contract
+            .bytecode
+            .bytecode()
+            .get(op.pc)
+            .copied()
+            .unwrap_or(opcode::STOP);
+        frame.pending_push = stack_push_count(executed);
+        let memory = &interp.shared_memory;
+        op.ex = Some(VmExecutedOperation {
+            used: remaining,
+            push: Vec::new(),
+            mem: pending.mem.and_then(|(off, len)| {
+                (len != 0 && off.checked_add(len)? <= memory.len()).then(|| MemoryDelta {
+                    off,
+                    data: Bytes::copy_from_slice(memory.slice(off, len)),
+                })
+            }),
+            store: pending.store,
+        });
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if self.config.state_diff {
+            self.prestate.call(context, inputs);
+        }
+        // Parity reports the code address as `to` of `DELEGATECALL` and `CALLCODE`.
+        let from = match inputs.context.scheme {
+            CallScheme::DelegateCall | CallScheme::ExtDelegateCall | CallScheme::CallCode => {
+                inputs.context.address
+            }
+            CallScheme::Call
+            | CallScheme::ExtCall
+            | CallScheme::StaticCall
+            | CallScheme::ExtStaticCall => inputs.context.caller,
+        };
+        self.start_frame(ParityAction::Call(ParityCallAction {
+            from,
+            to: inputs.context.code_address,
+            value: inputs.context.apparent_value,
+            gas: inputs.gas_limit,
+            input: inputs.input.clone(),
+            call_type: inputs.context.scheme.into(),
+        }));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(
+            outcome.result.result,
+            ParityTraceResult::Call(ParityCallResult {
+                gas_used: outcome.result.gas.spend(),
+                output: outcome.result.output.clone(),
+            }),
+        );
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        if self.config.state_diff {
+            self.prestate.create(context, inputs);
+        }
+        self.start_frame(ParityAction::Create(ParityCreateAction {
+            from: inputs.caller,
+            value: inputs.value,
+            gas: inputs.gas_limit,
+            init: inputs.init_code.clone(),
+        }));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let outcome = if self.config.state_diff {
+            self.prestate.create_end(context, inputs, outcome)
+        } else {
+            outcome
+        };
+        self.end_frame(
+            outcome.result.result,
+            ParityTraceResult::Create(ParityCreateResult {
+                gas_used: outcome.result.gas.spend(),
+                code: outcome.result.output.clone(),
+                address: outcome.address.unwrap_or_default(),
+            }),
+        );
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if self.config.trace && !self.trace_stack.is_empty() {
+            self.push_trace(ParityAction::Selfdestruct(ParitySelfdestructAction {
+                address: contract,
+                refund_address: target,
+                balance: value,
+            }));
+        }
+    }
+}
+
+/// Returns the number of stack items the instruction pushes, as reported in `vmTrace`.
+///
+/// `DUP` and `SWAP` report all stack items they touch.
+fn stack_push_count(op: u8) -> usize {
+    match op {
+        opcode::DUP1..=opcode::DUP16 => (op - opcode::DUP1) as usize + 2,
+        opcode::SWAP1..=opcode::SWAP16 => (op - opcode::SWAP1) as usize + 2,
+        opcode::STOP
+        | opcode::CALLDATACOPY
+        | opcode::CODECOPY
+        | opcode::EXTCODECOPY
+        | opcode::RETURNDATACOPY
+        | opcode::POP
+        | opcode::MSTORE
+        | opcode::MSTORE8
+        | opcode::SSTORE
+        | opcode::JUMP
+        | opcode::JUMPI
+        | opcode::JUMPDEST
+        | opcode::TSTORE
+        | opcode::MCOPY
+        | opcode::LOG0..=opcode::LOG4
+        | opcode::RETURN
+        | opcode::REVERT
+        | opcode::INVALID
+        | opcode::SELFDESTRUCT => 0,
+        _ => 1,
+    }
+}
+
+/// Returns the error message Parity uses for the given failed result.
+fn parity_error_message(result: InstructionResult) -> String {
+    match result {
+        InstructionResult::Revert => "Reverted",
+        InstructionResult::OutOfGas
+        | InstructionResult::MemoryOOG
+        | InstructionResult::MemoryLimitOOG
+        | InstructionResult::PrecompileOOG
+        | InstructionResult::InvalidOperandOOG => "Out of gas",
+        InstructionResult::OpcodeNotFound
+        | InstructionResult::InvalidFEOpcode
+        | InstructionResult::NotActivated => "Bad instruction",
+        InstructionResult::InvalidJump => "Bad jump destination",
+        InstructionResult::StackUnderflow => "Stack underflow",
+        InstructionResult::StackOverflow => "Out of stack",
+        InstructionResult::CallNotAllowedInsideStatic
+        | InstructionResult::StateChangeDuringStaticCall => "Mutable Call In Static Context",
+        InstructionResult::OutOfOffset => "Out of bounds",
+        InstructionResult::PrecompileError => "Built-in failed",
+        _ => return geth_error_message(result),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        primitives::{address, AccountInfo, Bytecode, TransactTo},
+        Evm,
+    };
+
+    /// Calls the contract that stores `1` into slot `0` and then calls the identity precompile.
+    fn run(config: ParityTracerConfig) -> ParityTraceResults {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            // STATICCALL(gas, 0x04, 0, 0, 0, 0)
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x4,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000_000),
+                ..Default::default()
+            },
+        );
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: code.hash_slow(),
+                code: Some(code),
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(ParityTracer::new(config))
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(1);
+            })
+            .modify_block_env(|block| block.basefee = U256::ZERO)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap();
+        evm.into_context().external.into_results(&result)
+    }
+
+    #[test]
+    fn test_trace() {
+        let results = run(ParityTracerConfig {
+            trace: true,
+            ..Default::default()
+        });
+        assert!(results.vm_trace.is_none());
+        assert!(results.state_diff.is_none());
+
+        let trace = &results.trace;
+        assert_eq!(trace.len(), 2);
+        assert_eq!(trace[0].subtraces, 1);
+        assert!(trace[0].trace_address.is_empty());
+        assert!(matches!(trace[0].result, Some(ParityTraceResult::Call(_))));
+        assert_eq!(trace[1].trace_address, vec![0]);
+        let ParityAction::Call(action) = &trace[1].action else {
+            panic!("expected call action");
+        };
+        assert_eq!(action.call_type, ParityCallType::StaticCall);
+        assert_eq!(
+            action.to,
+            address!("0000000000000000000000000000000000000004")
+        );
+    }
+
+    #[test]
+    fn test_vm_trace() {
+        let results = run(ParityTracerConfig {
+            vm_trace: true,
+            ..Default::default()
+        });
+        assert!(results.trace.is_empty());
+        let vm_trace = results.vm_trace.unwrap();
+        assert_eq!(vm_trace.ops.len(), 11);
+
+        let sstore = &vm_trace.ops[2];
+        assert_eq!(sstore.pc, 4);
+        assert_eq!(
+            sstore.ex.as_ref().unwrap().store,
+            Some(StorageDelta {
+                key: U256::ZERO,
+                val: U256::from(1)
+            })
+        );
+        // DUP1 reports both items.
+        assert_eq!(vm_trace.ops[4].ex.as_ref().unwrap().push.len(), 2);
+        // STATICCALL result is success, child frame is the precompile.
+        let call = &vm_trace.ops[9];
+        assert_eq!(call.ex.as_ref().unwrap().push, vec![U256::from(1)]);
+        assert!(call.sub.as_ref().unwrap().ops.is_empty());
+    }
+
+    #[test]
+    fn test_state_diff() {
+        let results = run(ParityTracerConfig {
+            state_diff: true,
+            ..Default::default()
+        });
+        let state_diff = results.state_diff.unwrap();
+        let contract = &state_diff[&address!("2000000000000000000000000000000000000000")];
+        assert!(contract.balance.is_unchanged());
+        assert_eq!(
+            contract.storage[&B256::ZERO],
+            Delta::Changed(ChangedValue {
+                from: B256::ZERO,
+                to: B256::from(U256::from(1)),
+            })
+        );
+        let caller = &state_diff[&address!("1000000000000000000000000000000000000000")];
+        assert_eq!(
+            caller.nonce,
+            Delta::Changed(ChangedValue {
+                from: U256::ZERO,
+                to: U256::from(1),
+            })
+        );
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_json() {
+        let results = run(ParityTracerConfig::all());
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["trace"][0]["type"], "call");
+        assert_eq!(json["trace"][0]["action"]["callType"], "call");
+        assert_eq!(json["trace"][1]["traceAddress"][0], 0);
+        let contract = &json["stateDiff"]["0x2000000000000000000000000000000000000000"];
+        assert_eq!(contract["balance"], "=");
+        assert!(contract["storage"][format!("{:?}", B256::ZERO)]["*"].is_object());
+
+        let decoded: ParityTraceResults = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, results);
+    }
+}
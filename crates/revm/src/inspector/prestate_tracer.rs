@@ -0,0 +1,341 @@
+//! Geth `prestateTracer` compatible inspector.
+//!
+//! Output format is described in
+//! <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#prestate-tracer>.
+
+use crate::{
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, Address, Bytes, State, B256, KECCAK_EMPTY, U256},
+    EvmContext, Inspector,
+};
+use std::collections::{btree_map::Entry, BTreeMap};
+
+/// State of the single account.
+///
+/// Fields that are `None` or empty are omitted from the output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestateAccount {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub balance: Option<U256>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub nonce: Option<u64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub code: Option<Bytes>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+impl PrestateAccount {
+    /// Returns true if account has no fields set.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// Accounts state keyed by address.
+pub type PrestateAccounts = BTreeMap<Address, PrestateAccount>;
+
+/// Output of the tracer in `diffMode`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestateDiff {
+    /// State of the changed accounts before the transaction.
+    pub pre: PrestateAccounts,
+    /// Changed fields of the accounts after the transaction.
+    pub post: PrestateAccounts,
+}
+
+/// [Inspector] that records the state of every account and storage slot
+/// accessed by the transaction, as it was before the transaction.
+///
+/// Pre-state is read from the database when account or slot is first accessed,
+/// journaled changes are not yet committed to the database so this is the
+/// state before the transaction.
+///
+/// For `diffMode` output use [PrestateTracer::into_diff] with the state returned from the transaction.
+#[derive(Clone, Debug, Default)]
+pub struct PrestateTracer {
+    pre: PrestateAccounts,
+}
+
+impl PrestateTracer {
+    /// Creates new prestate tracer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the state of accessed accounts before the transaction.
+    pub fn prestate(&self) -> &PrestateAccounts {
+        &self.pre
+    }
+
+    /// Consumes the tracer and returns the state of accessed accounts before the transaction.
+    pub fn into_prestate(self) -> PrestateAccounts {
+        self.pre
+    }
+
+    /// Consumes the tracer returning pre and post values of the accounts that were
+    /// changed by the transaction.
+    ///
+    /// `state` is the state returned by the transaction.
+    pub fn into_diff(self, state: &State) -> PrestateDiff {
+        let mut diff = PrestateDiff::default();
+        for (address, mut pre) in self.pre {
+            let Some(account) = state.get(&address) else {
+                continue;
+            };
+            let existed = pre.nonce.unwrap_or_default() != 0
+                || pre.balance.unwrap_or_default() != U256::ZERO
+                || pre.code.is_some();
+
+            if account.is_selfdestructed() {
+                if existed {
+                    pre.storage.clear();
+                    diff.pre.insert(address, pre);
+                }
+                continue;
+            }
+
+            let mut post = PrestateAccount::default();
+            if pre.balance.unwrap_or_default() != account.info.balance {
+                post.balance = Some(account.info.balance);
+            }
+            if pre.nonce.unwrap_or_default() != account.info.nonce {
+                post.nonce = Some(account.info.nonce);
+            }
+            let post_code = account
+                .info
+                .code
+                .as_ref()
+                .map(|code| code.original_bytes())
+                .filter(|code| !code.is_empty());
+            if post_code.is_some() && pre.code != post_code {
+                post.code = post_code;
+            }
+            for (key, slot) in account.changed_storage_slots() {
+                post.storage
+                    .insert((*key).into(), slot.present_value.into());
+            }
+            pre.storage.retain(|key, _| post.storage.contains_key(key));
+
+            if post.is_empty() {
+                continue;
+            }
+            if existed {
+                diff.pre.insert(address, pre);
+            }
+            diff.post.insert(address, post);
+        }
+        diff
+    }
+
+    /// Records the account state from the database if it is not already recorded.
+    fn record_account<DB: Database>(&mut self, address: Address, db: &mut DB) {
+        if self.pre.contains_key(&address) {
+            return;
+        }
+        let account = match db.basic(address) {
+            Ok(Some(info)) => {
+                let code = match info.code {
+                    Some(code) => Some(code.original_bytes()),
+                    None if info.code_hash == KECCAK_EMPTY => None,
+                    None => db
+                        .code_by_hash(info.code_hash)
+                        .ok()
+                        .map(|code| code.original_bytes()),
+                };
+                PrestateAccount {
+                    balance: Some(info.balance),
+                    nonce: (info.nonce != 0).then_some(info.nonce),
+                    code: code.filter(|code| !code.is_empty()),
+                    storage: BTreeMap::new(),
+                }
+            }
+            _ => PrestateAccount {
+                balance: Some(U256::ZERO),
+                ..Default::default()
+            },
+        };
+        self.pre.insert(address, account);
+    }
+
+    /// Records the storage slot value from the database if it is not already recorded.
+    fn record_slot<DB: Database>(&mut self, address: Address, key: U256, db: &mut DB) {
+        self.record_account(address, db);
+        let account = self.pre.get_mut(&address).expect("account is recorded");
+        if let Entry::Vacant(entry) = account.storage.entry(key.into()) {
+            if let Ok(value) = db.storage(address, key) {
+                entry.insert(value.into());
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for PrestateTracer {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let stack = interp.stack();
+        let db = &mut context.db;
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Ok(key) = stack.peek(0) {
+                    self.record_slot(interp.contract.address, key, db);
+                }
+            }
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => {
+                if let Ok(address) = stack.peek(0) {
+                    self.record_account(Address::from_word(address.into()), db);
+                }
+            }
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                if let Ok(address) = stack.peek(1) {
+                    self.record_account(Address::from_word(address.into()), db);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if context.journaled_state.depth() == 0 {
+            let coinbase = context.env.block.coinbase;
+            self.record_account(coinbase, &mut context.db);
+        }
+        self.record_account(inputs.transfer.source, &mut context.db);
+        self.record_account(inputs.contract, &mut context.db);
+        None
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        if context.journaled_state.depth() == 0 {
+            let coinbase = context.env.block.coinbase;
+            self.record_account(coinbase, &mut context.db);
+        }
+        self.record_account(inputs.caller, &mut context.db);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some(address) = outcome.address {
+            self.record_account(address, &mut context.db);
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        primitives::{address, AccountInfo, Bytecode, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_prestate_and_diff() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        // SLOAD slot 0, SSTORE 1 into slot 1.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::SLOAD,
+            opcode::POP,
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000_000),
+                ..Default::default()
+            },
+        );
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: code.hash_slow(),
+                code: Some(code.clone()),
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(contract, U256::ZERO, U256::from(7))
+            .unwrap();
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(PrestateTracer::new())
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(1);
+            })
+            .modify_block_env(|block| block.basefee = U256::ZERO)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap();
+        let tracer = evm.into_context().external;
+
+        let prestate = tracer.prestate();
+        assert_eq!(prestate[&caller].balance, Some(U256::from(1_000_000)));
+        assert_eq!(prestate[&caller].nonce, None);
+        let contract_pre = &prestate[&contract];
+        assert_eq!(contract_pre.code, Some(code.original_bytes()));
+        assert_eq!(contract_pre.storage[&B256::ZERO], B256::from(U256::from(7)));
+        assert_eq!(contract_pre.storage[&B256::from(U256::from(1))], B256::ZERO);
+
+        let diff = tracer.into_diff(&result.state);
+        let contract_post = &diff.post[&contract];
+        assert_eq!(contract_post.balance, None);
+        assert_eq!(
+            contract_post.storage,
+            BTreeMap::from([(B256::from(U256::from(1)), B256::from(U256::from(1)))])
+        );
+        // only changed slot is present in pre.
+        assert_eq!(diff.pre[&contract].storage.len(), 1);
+        assert_eq!(diff.post[&caller].nonce, Some(1));
+    }
+}
@@ -0,0 +1,254 @@
+//! ExecutionRecorder. Periodic checkpoints of the execution for time-travel debugging.
+
+use crate::{
+    interpreter::Interpreter,
+    primitives::{db::Database, AccountInfo, Address, Bytes, U256},
+    EvmContext, Inspector,
+};
+use std::{collections::BTreeMap, vec::Vec};
+
+/// Interpreter state before the instruction of the checkpoint was executed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterpreterSnapshot {
+    pub address: Address,
+    pub pc: usize,
+    pub opcode: u8,
+    /// Depth of the frame, top level frame has depth 1.
+    pub depth: u64,
+    pub gas_remaining: u64,
+    pub gas_refunded: i64,
+    pub stack: Vec<U256>,
+    /// Memory of the current frame.
+    pub memory: Bytes,
+}
+
+/// Changes of the journaled state since the previous checkpoint.
+///
+/// `None` marks account or slot that was removed from the state, e.g. when
+/// the frame that loaded it reverted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDelta {
+    pub accounts: BTreeMap<Address, Option<AccountInfo>>,
+    pub storage: BTreeMap<(Address, U256), Option<U256>>,
+}
+
+impl StateDelta {
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storage.is_empty()
+    }
+}
+
+/// Accounts and storage present in the journaled state.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordedState {
+    pub accounts: BTreeMap<Address, AccountInfo>,
+    pub storage: BTreeMap<(Address, U256), U256>,
+}
+
+impl RecordedState {
+    /// Applies the delta to the state.
+    pub fn apply(&mut self, delta: &StateDelta) {
+        for (address, info) in &delta.accounts {
+            match info {
+                Some(info) => self.accounts.insert(*address, info.clone()),
+                None => self.accounts.remove(address),
+            };
+        }
+        for (slot, value) in &delta.storage {
+            match value {
+                Some(value) => self.storage.insert(*slot, *value),
+                None => self.storage.remove(slot),
+            };
+        }
+    }
+}
+
+/// Snapshot of the execution taken before the instruction was executed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Number of instructions executed before the checkpoint.
+    pub step: u64,
+    pub interpreter: InterpreterSnapshot,
+    /// Changes of the state since the previous checkpoint.
+    pub state: StateDelta,
+}
+
+/// [Inspector] that records a [Checkpoint] every `interval` steps, so a debugger can
+/// jump backwards in the trace without re-executing it from the start.
+///
+/// State is stored as a delta from the previous checkpoint, use
+/// [ExecutionRecorder::state_at] to get the full state at the checkpoint.
+#[derive(Clone, Debug)]
+pub struct ExecutionRecorder {
+    interval: u64,
+    steps: u64,
+    checkpoints: Vec<Checkpoint>,
+    /// State at the last checkpoint.
+    last_state: RecordedState,
+}
+
+impl ExecutionRecorder {
+    /// Creates new recorder that checkpoints every `interval` steps.
+    ///
+    /// Interval of zero is treated as one.
+    pub fn new(interval: u64) -> Self {
+        Self {
+            interval: interval.max(1),
+            steps: 0,
+            checkpoints: Vec::new(),
+            last_state: RecordedState::default(),
+        }
+    }
+
+    /// Returns all checkpoints in execution order.
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Returns the number of executed instructions.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Returns index of the last checkpoint taken at or before the step.
+    pub fn checkpoint_before(&self, step: u64) -> Option<usize> {
+        self.checkpoints
+            .partition_point(|checkpoint| checkpoint.step <= step)
+            .checked_sub(1)
+    }
+
+    /// Returns the full state at the checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if checkpoint index is out of bounds.
+    pub fn state_at(&self, index: usize) -> RecordedState {
+        let mut state = RecordedState::default();
+        for checkpoint in &self.checkpoints[..=index] {
+            state.apply(&checkpoint.state);
+        }
+        state
+    }
+
+    /// Computes changes of the journaled state since the last checkpoint.
+    fn state_delta<DB: Database>(&mut self, context: &EvmContext<DB>) -> StateDelta {
+        let state = &context.journaled_state.state;
+        let mut delta = StateDelta::default();
+        for (address, account) in state {
+            if self.last_state.accounts.get(address) != Some(&account.info) {
+                delta.accounts.insert(*address, Some(account.info.clone()));
+            }
+            for (key, slot) in &account.storage {
+                let value = slot.present_value;
+                if self.last_state.storage.get(&(*address, *key)) != Some(&value) {
+                    delta.storage.insert((*address, *key), Some(value));
+                }
+            }
+        }
+        for address in self.last_state.accounts.keys() {
+            if !state.contains_key(address) {
+                delta.accounts.insert(*address, None);
+            }
+        }
+        for (address, key) in self.last_state.storage.keys() {
+            let present = state
+                .get(address)
+                .is_some_and(|account| account.storage.contains_key(key));
+            if !present {
+                delta.storage.insert((*address, *key), None);
+            }
+        }
+        self.last_state.apply(&delta);
+        delta
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ExecutionRecorder {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let step = self.steps;
+        self.steps += 1;
+        if !step.is_multiple_of(self.interval) {
+            return;
+        }
+        let interpreter = InterpreterSnapshot {
+            address: interp.contract.address,
+            pc: interp.program_counter(),
+            opcode: interp.current_opcode(),
+            depth: context.journaled_state.depth(),
+            gas_remaining: interp.gas.remaining(),
+            gas_refunded: interp.gas.refunded(),
+            stack: interp.stack.data().clone(),
+            memory: Bytes::copy_from_slice(interp.shared_memory.context_memory()),
+        };
+        let state = self.state_delta(context);
+        self.checkpoints.push(Checkpoint {
+            step,
+            interpreter,
+            state,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_checkpoints() {
+        // SSTORE 1 into slot 0, SSTORE 2 into slot 0.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(ExecutionRecorder::new(3))
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let recorder = evm.into_context().external;
+
+        assert_eq!(recorder.steps(), 7);
+        let checkpoints = recorder.checkpoints();
+        assert_eq!(
+            checkpoints
+                .iter()
+                .map(|checkpoint| (checkpoint.step, checkpoint.interpreter.pc))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (3, 5), (6, 10)]
+        );
+        assert_eq!(recorder.checkpoint_before(5), Some(1));
+        assert_eq!(recorder.checkpoint_before(100), Some(2));
+
+        let slot = (Address::ZERO, U256::ZERO);
+        assert_eq!(recorder.state_at(0).storage.get(&slot), None);
+        assert_eq!(recorder.state_at(1).storage[&slot], U256::from(1));
+        assert_eq!(recorder.state_at(2).storage[&slot], U256::from(2));
+        // only the slot changed between last two checkpoints.
+        assert!(checkpoints[2].state.accounts.is_empty());
+        assert_eq!(checkpoints[2].state.storage.len(), 1);
+    }
+}
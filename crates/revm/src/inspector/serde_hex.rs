@@ -0,0 +1,17 @@
+//! Serde helpers that (de)serialize integers as `0x` prefixed hex quantities, as geth does.
+
+/// (De)serializes `u64` as hex quantity.
+pub(crate) mod u64_hex {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::{format, string::String};
+
+    pub(crate) fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{value:#x}"))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        u64::from_str_radix(s, 16).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,300 @@
+//! InspectorStack. Runs multiple inspectors on the same execution.
+
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterResult,
+    },
+    primitives::{db::Database, Address, EVMError, Log, ResultAndState, U256},
+    EvmContext, Inspector,
+};
+use std::{boxed::Box, vec::Vec};
+
+/// [Inspector] that forwards every hook to a list of inspectors.
+///
+/// Inspectors are called in the order they were pushed. Hooks that return
+/// a value are chained:
+///
+/// * `call` and `create` stop at the first inspector that returns an outcome,
+///   inspectors after it are not called for that frame.
+/// * `call_end` and `create_end` pass the outcome returned by one inspector to the next one.
+///
+/// All other hooks are called on every inspector.
+pub struct InspectorStack<DB: Database> {
+    inspectors: Vec<Box<dyn Inspector<DB>>>,
+}
+
+impl<DB: Database> Default for InspectorStack<DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB: Database> core::fmt::Debug for InspectorStack<DB> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InspectorStack")
+            .field("len", &self.inspectors.len())
+            .finish()
+    }
+}
+
+impl<DB: Database> InspectorStack<DB> {
+    /// Creates an empty stack.
+    pub fn new() -> Self {
+        Self {
+            inspectors: Vec::new(),
+        }
+    }
+
+    /// Pushes inspector to the end of the stack.
+    pub fn push<INSP: Inspector<DB> + 'static>(&mut self, inspector: INSP) {
+        self.inspectors.push(Box::new(inspector));
+    }
+
+    /// Pushes inspector to the end of the stack and returns the stack.
+    pub fn with<INSP: Inspector<DB> + 'static>(mut self, inspector: INSP) -> Self {
+        self.push(inspector);
+        self
+    }
+
+    /// Returns number of inspectors in the stack.
+    pub fn len(&self) -> usize {
+        self.inspectors.len()
+    }
+
+    /// Returns true if stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inspectors.is_empty()
+    }
+
+    /// Returns the inspectors of the stack.
+    pub fn inspectors(&self) -> &[Box<dyn Inspector<DB>>] {
+        &self.inspectors
+    }
+
+    /// Consumes the stack and returns the inspectors.
+    pub fn into_inner(self) -> Vec<Box<dyn Inspector<DB>>> {
+        self.inspectors
+    }
+}
+
+impl<DB: Database> Inspector<DB> for InspectorStack<DB> {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.initialize_interp(interp, context);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.step(interp, context);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.step_end(interp, context);
+        }
+    }
+
+    fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.log(context, log);
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.inspectors
+            .iter_mut()
+            .find_map(|inspector| inspector.call(context, inputs))
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.inspectors
+            .iter_mut()
+            .fold(outcome, |outcome, inspector| {
+                inspector.call_end(context, inputs, outcome)
+            })
+    }
+
+    fn precompile(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        result: &InterpreterResult,
+    ) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.precompile(context, inputs, result);
+        }
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inspectors
+            .iter_mut()
+            .find_map(|inspector| inspector.create(context, inputs))
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inspectors
+            .iter_mut()
+            .fold(outcome, |outcome, inspector| {
+                inspector.create_end(context, inputs, outcome)
+            })
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.selfdestruct(contract, target, value);
+        }
+    }
+
+    fn refund(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<DB>,
+        opcode: u8,
+        delta: i64,
+    ) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.refund(interp, context, opcode, delta);
+        }
+    }
+
+    fn transaction_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        result: &Result<ResultAndState, EVMError<DB::Error>>,
+    ) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.transaction_end(context, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::{opcode, InstructionResult},
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Records hook calls into the shared log, prefixed by its name.
+    struct Recorder {
+        name: &'static str,
+        calls: Rc<RefCell<Vec<String>>>,
+        override_call: bool,
+    }
+
+    impl<DB: Database> Inspector<DB> for Recorder {
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+            self.calls.borrow_mut().push(format!("{}:step", self.name));
+        }
+
+        fn call(
+            &mut self,
+            _context: &mut EvmContext<DB>,
+            inputs: &mut CallInputs,
+        ) -> Option<CallOutcome> {
+            self.calls.borrow_mut().push(format!("{}:call", self.name));
+            self.override_call.then(|| {
+                CallOutcome::new(
+                    crate::interpreter::InterpreterResult {
+                        result: InstructionResult::Revert,
+                        output: Bytes::new(),
+                        gas: crate::interpreter::Gas::new(inputs.gas_limit),
+                    },
+                    0..0,
+                )
+            })
+        }
+
+        fn call_end(
+            &mut self,
+            _context: &mut EvmContext<DB>,
+            _inputs: &CallInputs,
+            outcome: CallOutcome,
+        ) -> CallOutcome {
+            self.calls
+                .borrow_mut()
+                .push(format!("{}:call_end", self.name));
+            outcome
+        }
+    }
+
+    fn run(override_call: bool) -> Vec<String> {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let stack = InspectorStack::new()
+            .with(Recorder {
+                name: "a",
+                calls: calls.clone(),
+                override_call,
+            })
+            .with(Recorder {
+                name: "b",
+                calls: calls.clone(),
+                override_call: false,
+            });
+
+        let contract_data: Bytes = Bytes::from(vec![opcode::PUSH1, 0x1, opcode::STOP]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(stack)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 21100;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        drop(evm);
+
+        Rc::try_unwrap(calls).unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_hook_order() {
+        assert_eq!(
+            run(false),
+            vec![
+                "a:call",
+                "b:call",
+                "a:step",
+                "b:step",
+                "a:step",
+                "b:step",
+                "a:call_end",
+                "b:call_end"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_override_short_circuits() {
+        assert_eq!(run(true), vec!["a:call", "a:call_end", "b:call_end"]);
+    }
+}
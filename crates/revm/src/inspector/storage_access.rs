@@ -0,0 +1,296 @@
+//! StorageAccessInspector. Records every storage read and write.
+
+use crate::{
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{db::Database, Address, U256},
+    EvmContext, Inspector,
+};
+use std::{collections::BTreeMap, vec::Vec};
+
+/// Kind of the storage access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StorageAccessKind {
+    /// `SLOAD`
+    Read,
+    /// `SSTORE`
+    Write,
+}
+
+/// Single `SLOAD` or `SSTORE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageAccess {
+    pub kind: StorageAccessKind,
+    pub address: Address,
+    pub key: U256,
+    /// Value of the slot before the access.
+    pub old_value: U256,
+    /// Value of the slot after the access, same as `old_value` for reads.
+    pub new_value: U256,
+    /// True if this was the first access to the slot in the transaction.
+    pub is_cold: bool,
+    /// Depth of the frame that accessed the slot, top level frame has depth 1.
+    pub depth: u64,
+    /// True if the frame that accessed the slot, or any of its parents, reverted.
+    pub reverted: bool,
+}
+
+/// Storage access that is executing, it is completed in `step_end`.
+#[derive(Clone, Copy, Debug)]
+struct PendingAccess {
+    kind: StorageAccessKind,
+    address: Address,
+    key: U256,
+    /// `None` if the slot is not loaded yet.
+    old_value: Option<U256>,
+}
+
+/// [Inspector] that records every `SLOAD` and `SSTORE` with address, key,
+/// old and new value and whether the slot was cold.
+#[derive(Clone, Debug, Default)]
+pub struct StorageAccessInspector {
+    accesses: Vec<StorageAccess>,
+    pending: Option<PendingAccess>,
+    /// Number of accesses recorded when currently executing frames started.
+    frame_starts: Vec<usize>,
+}
+
+impl StorageAccessInspector {
+    /// Creates new storage access inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all accesses in execution order.
+    pub fn accesses(&self) -> &[StorageAccess] {
+        &self.accesses
+    }
+
+    /// Consumes the inspector and returns all accesses in execution order.
+    pub fn into_accesses(self) -> Vec<StorageAccess> {
+        self.accesses
+    }
+
+    /// Returns the storage slots changed by the transaction with their original and final value.
+    ///
+    /// Writes from reverted frames and slots whose final value equals the original are skipped.
+    pub fn changes(&self) -> BTreeMap<Address, BTreeMap<U256, (U256, U256)>> {
+        let mut changes: BTreeMap<Address, BTreeMap<U256, (U256, U256)>> = BTreeMap::new();
+        for access in &self.accesses {
+            if access.kind != StorageAccessKind::Write || access.reverted {
+                continue;
+            }
+            changes
+                .entry(access.address)
+                .or_default()
+                .entry(access.key)
+                .and_modify(|(_, new)| *new = access.new_value)
+                .or_insert((access.old_value, access.new_value));
+        }
+        changes.retain(|_, slots| {
+            slots.retain(|_, (old, new)| old != new);
+            !slots.is_empty()
+        });
+        changes
+    }
+
+    fn start_frame(&mut self) {
+        self.frame_starts.push(self.accesses.len());
+    }
+
+    fn end_frame(&mut self, success: bool) {
+        let Some(start) = self.frame_starts.pop() else {
+            return;
+        };
+        if !success {
+            for access in &mut self.accesses[start..] {
+                access.reverted = true;
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for StorageAccessInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let kind = match interp.current_opcode() {
+            opcode::SLOAD => StorageAccessKind::Read,
+            opcode::SSTORE => StorageAccessKind::Write,
+            _ => return,
+        };
+        let Ok(key) = interp.stack().peek(0) else {
+            return;
+        };
+        let address = interp.contract.address;
+        let old_value = context
+            .journaled_state
+            .state
+            .get(&address)
+            .and_then(|account| account.storage.get(&key))
+            .map(|slot| slot.present_value);
+        self.pending = Some(PendingAccess {
+            kind,
+            address,
+            key,
+            old_value,
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        if interp.instruction_result.is_error() {
+            return;
+        }
+        let Some(slot) = context
+            .journaled_state
+            .state
+            .get(&pending.address)
+            .and_then(|account| account.storage.get(&pending.key))
+        else {
+            return;
+        };
+        // slot loaded by this access has its value from the database as the original value.
+        let old_value = pending.old_value.unwrap_or(slot.previous_or_original_value);
+        self.accesses.push(StorageAccess {
+            kind: pending.kind,
+            address: pending.address,
+            key: pending.key,
+            old_value,
+            new_value: slot.present_value,
+            is_cold: pending.old_value.is_none(),
+            depth: context.journaled_state.depth(),
+            reverted: false,
+        });
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame();
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(outcome.result.is_ok());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        primitives::{address, AccountInfo, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_storage_access() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        // SLOAD slot 0, SSTORE 2 into slot 0, SSTORE 7 into slot 0, SSTORE 0 into slot 1.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::SLOAD,
+            opcode::POP,
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x7,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: code.hash_slow(),
+                code: Some(code),
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(contract, U256::ZERO, U256::from(1))
+            .unwrap();
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(StorageAccessInspector::new())
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .modify_block_env(|block| block.basefee = U256::ZERO)
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+        let inspector = evm.into_context().external;
+
+        let access = |kind, key: u64, old: u64, new: u64, is_cold| StorageAccess {
+            kind,
+            address: contract,
+            key: U256::from(key),
+            old_value: U256::from(old),
+            new_value: U256::from(new),
+            is_cold,
+            depth: 1,
+            reverted: false,
+        };
+        assert_eq!(
+            inspector.accesses(),
+            &[
+                access(StorageAccessKind::Read, 0, 1, 1, true),
+                access(StorageAccessKind::Write, 0, 1, 2, false),
+                access(StorageAccessKind::Write, 0, 2, 7, false),
+                access(StorageAccessKind::Write, 1, 0, 0, true),
+            ]
+        );
+        assert_eq!(
+            inspector.changes(),
+            BTreeMap::from([(
+                contract,
+                BTreeMap::from([(U256::ZERO, (U256::from(1), U256::from(7)))])
+            )])
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! StreamingTracer. Hands every trace event to the sink as soon as it happens.
+
+use super::call_tracer::CallKind;
+use crate::{
+    interpreter::{
+        opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, InstructionResult,
+        Interpreter,
+    },
+    primitives::{db::Database, Address, Bytes, Log, U256},
+    EvmContext, Inspector,
+};
+use core::fmt;
+
+/// Event emitted by the [StreamingTracer].
+///
+/// Events borrow the data of the execution, sink copies what it needs to keep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamEvent<'a> {
+    /// Call or create frame started. `to` is the created address for creates.
+    FrameStart {
+        depth: u64,
+        kind: CallKind,
+        from: Address,
+        to: Address,
+        value: U256,
+        gas_limit: u64,
+        input: &'a Bytes,
+    },
+    /// Instruction is about to be executed.
+    Step {
+        depth: u64,
+        pc: usize,
+        opcode: u8,
+        gas_remaining: u64,
+        stack: &'a [U256],
+    },
+    /// Log was emitted.
+    Log { depth: u64, log: &'a Log },
+    /// Frame ended.
+    FrameEnd {
+        depth: u64,
+        result: InstructionResult,
+        gas_used: u64,
+        output: &'a Bytes,
+    },
+}
+
+impl fmt::Display for StreamEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrameStart {
+                depth,
+                kind,
+                from,
+                to,
+                value,
+                gas_limit,
+                input,
+            } => write!(
+                f,
+                "{depth} {} from:{from} to:{to} value:{value} gas:{gas_limit} input:{input}",
+                kind.as_str()
+            ),
+            Self::Step {
+                depth,
+                pc,
+                opcode,
+                gas_remaining,
+                stack,
+            } => write!(
+                f,
+                "{depth} {pc} {} gas:{gas_remaining} stack:{}",
+                opcode::OPCODE_JUMPMAP[*opcode as usize].unwrap_or("UNKNOWN"),
+                stack.len()
+            ),
+            Self::Log { depth, log } => {
+                write!(
+                    f,
+                    "{depth} LOG {} topics:{}",
+                    log.address,
+                    log.topics().len()
+                )
+            }
+            Self::FrameEnd {
+                depth,
+                result,
+                gas_used,
+                output,
+            } => write!(
+                f,
+                "{depth} END {result:?} gas_used:{gas_used} output:{output}"
+            ),
+        }
+    }
+}
+
+/// Receiver of the [StreamEvent]s.
+///
+/// Implemented for closures taking the event.
+pub trait TraceSink {
+    /// Called for every event, in execution order.
+    fn event(&mut self, event: &StreamEvent<'_>);
+}
+
+impl<F: FnMut(&StreamEvent<'_>)> TraceSink for F {
+    fn event(&mut self, event: &StreamEvent<'_>) {
+        self(event)
+    }
+}
+
+/// [TraceSink] that writes every event as a line to the [core::fmt::Write] writer.
+///
+/// Write errors are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct LineSink<W> {
+    writer: W,
+}
+
+impl<W> LineSink<W> {
+    /// Creates new sink that writes to the writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Consumes the sink and returns the writer.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: fmt::Write> TraceSink for LineSink<W> {
+    fn event(&mut self, event: &StreamEvent<'_>) {
+        let _ = writeln!(self.writer, "{event}");
+    }
+}
+
+/// [Inspector] that hands frame, step and log events to the [TraceSink] as soon as
+/// they happen, without buffering the trace in memory.
+#[derive(Clone, Debug, Default)]
+pub struct StreamingTracer<S> {
+    sink: S,
+}
+
+impl<S: TraceSink> StreamingTracer<S> {
+    /// Creates new tracer with the sink.
+    pub fn new(sink: S) -> Self {
+        Self { sink }
+    }
+
+    /// Returns mutable reference to the sink.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes the tracer and returns the sink.
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+}
+
+impl<DB: Database, S: TraceSink> Inspector<DB> for StreamingTracer<S> {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.sink.event(&StreamEvent::Step {
+            depth: context.journaled_state.depth(),
+            pc: interp.program_counter(),
+            opcode: interp.current_opcode(),
+            gas_remaining: interp.gas.remaining(),
+            stack: interp.stack.data(),
+        });
+    }
+
+    fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
+        self.sink.event(&StreamEvent::Log {
+            depth: context.journaled_state.depth(),
+            log,
+        });
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.sink.event(&StreamEvent::FrameStart {
+            depth: context.journaled_state.depth() + 1,
+            kind: inputs.context.scheme.into(),
+            from: inputs.context.caller,
+            to: inputs.contract,
+            value: inputs.transfer.value,
+            gas_limit: inputs.gas_limit,
+            input: &inputs.input,
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.sink.event(&StreamEvent::FrameEnd {
+            depth: context.journaled_state.depth() + 1,
+            result: outcome.result.result,
+            gas_used: outcome.result.gas.spend(),
+            output: &outcome.result.output,
+        });
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let nonce = context
+            .journaled_state
+            .get_account(&inputs.caller)
+            .map_or(0, |account| account.info.nonce);
+        self.sink.event(&StreamEvent::FrameStart {
+            depth: context.journaled_state.depth() + 1,
+            kind: inputs.scheme.into(),
+            from: inputs.caller,
+            to: inputs.created_address(nonce),
+            value: inputs.value,
+            gas_limit: inputs.gas_limit,
+            input: &inputs.init_code,
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.sink.event(&StreamEvent::FrameEnd {
+            depth: context.journaled_state.depth() + 1,
+            result: outcome.result.result,
+            gas_used: outcome.result.gas.spend(),
+            output: &outcome.result.output,
+        });
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        primitives::{address, Bytecode, TransactTo},
+        Evm,
+    };
+    use std::{string::String, vec::Vec};
+
+    #[test]
+    fn test_stream_lines() {
+        let contract_data: Bytes = Bytes::from(vec![opcode::PUSH1, 0x1, opcode::STOP]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(StreamingTracer::new(LineSink::new(String::new())))
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let output = evm.into_context().external.into_sink().into_writer();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "1 CALL from:0x1000000000000000000000000000000000000000 \
+                 to:0x0000000000000000000000000000000000000000 value:0 gas:79000 input:0x",
+                "1 0 PUSH1 gas:79000 stack:0",
+                "1 2 STOP gas:78997 stack:1",
+                "1 END Stop gas_used:3 output:0x",
+            ]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Configuration of the data captured by step tracers.
+
+/// Controls which parts of the interpreter state step tracers capture on every step.
+///
+/// Copying memory and storage on every step is expensive, only enable what is needed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct TracerConfig {
+    /// Capture content of the memory.
+    pub memory: bool,
+    /// Capture stack items.
+    pub stack: bool,
+    /// Capture storage slots of the executing contract that were accessed so far.
+    pub storage: bool,
+    /// Capture return data of the last call.
+    pub return_data: bool,
+}
+
+impl Default for TracerConfig {
+    /// Only the stack is captured.
+    fn default() -> Self {
+        Self {
+            memory: false,
+            stack: true,
+            storage: false,
+            return_data: false,
+        }
+    }
+}
+
+impl TracerConfig {
+    /// Config that captures everything.
+    pub fn all() -> Self {
+        Self {
+            memory: true,
+            stack: true,
+            storage: true,
+            return_data: true,
+        }
+    }
+
+    /// Config that captures nothing.
+    pub fn none() -> Self {
+        Self {
+            memory: false,
+            stack: false,
+            storage: false,
+            return_data: false,
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! ValueTransferInspector. Records every movement of ether.
+
+use crate::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::{db::Database, Address, EVMError, ResultAndState, SpecId, U256},
+    EvmContext, Inspector,
+};
+use std::vec::Vec;
+
+/// What moved the ether.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueTransferKind {
+    /// `CALL` or `CALLCODE` with value, or the transaction itself.
+    Call,
+    /// Endowment of the created contract.
+    Create,
+    /// Balance sent to the target of `SELFDESTRUCT`.
+    SelfDestruct,
+    /// Priority fee paid by the caller to the beneficiary of the block.
+    BeneficiaryReward,
+}
+
+/// Single movement of ether.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueTransfer {
+    pub kind: ValueTransferKind,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    /// Depth of the frame that moved the ether, zero for the beneficiary reward.
+    pub depth: u64,
+    /// True if the frame of the transfer, or any of its parents, reverted.
+    pub reverted: bool,
+}
+
+/// [Inspector] that records every ether transfer with non zero value, including
+/// the priority fee paid to the block beneficiary at the end of the transaction.
+#[derive(Clone, Debug, Default)]
+pub struct ValueTransferInspector {
+    transfers: Vec<ValueTransfer>,
+    /// Number of transfers recorded when currently executing frames started.
+    frame_starts: Vec<usize>,
+}
+
+impl ValueTransferInspector {
+    /// Creates new value transfer inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all transfers in execution order.
+    pub fn transfers(&self) -> &[ValueTransfer] {
+        &self.transfers
+    }
+
+    /// Consumes the inspector and returns all transfers in execution order.
+    pub fn into_transfers(self) -> Vec<ValueTransfer> {
+        self.transfers
+    }
+
+    fn record(&mut self, kind: ValueTransferKind, from: Address, to: Address, value: U256) {
+        if value == U256::ZERO {
+            return;
+        }
+        self.transfers.push(ValueTransfer {
+            kind,
+            from,
+            to,
+            value,
+            depth: self.frame_starts.len() as u64,
+            reverted: false,
+        });
+    }
+
+    fn end_frame(&mut self, success: bool) {
+        let Some(start) = self.frame_starts.pop() else {
+            return;
+        };
+        if !success {
+            for transfer in &mut self.transfers[start..] {
+                transfer.reverted = true;
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ValueTransferInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.frame_starts.push(self.transfers.len());
+        // `DELEGATECALL` and `STATICCALL` have zero value transfer.
+        self.record(
+            ValueTransferKind::Call,
+            inputs.transfer.source,
+            inputs.transfer.target,
+            inputs.transfer.value,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_starts.push(self.transfers.len());
+        let nonce = context
+            .journaled_state
+            .get_account(&inputs.caller)
+            .map(|account| account.info.nonce)
+            .unwrap_or_default();
+        self.record(
+            ValueTransferKind::Create,
+            inputs.caller,
+            inputs.created_address(nonce),
+            inputs.value,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.record(ValueTransferKind::SelfDestruct, contract, target, value);
+    }
+
+    fn transaction_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        result: &Result<ResultAndState, EVMError<DB::Error>>,
+    ) {
+        let Ok(result) = result else {
+            return;
+        };
+        let env = &context.env;
+        let mut price = env.effective_gas_price();
+        // base fee is burned after London.
+        if context.journaled_state.spec.is_enabled_in(SpecId::LONDON) {
+            price = price.saturating_sub(env.block.basefee);
+        }
+        let reward = price * U256::from(result.result.gas_used());
+        self.record(
+            ValueTransferKind::BeneficiaryReward,
+            env.tx.caller,
+            env.block.coinbase,
+            reward,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TransactTo},
+        Evm,
+    };
+
+    #[test]
+    fn test_value_transfers() {
+        // BenchmarkDB funds 0x01.
+        let caller = Address::with_last_byte(1);
+        let coinbase = address!("2000000000000000000000000000000000000000");
+        // CALL 0x42 with value 5 and SELFDESTRUCT to 0x02.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x5,
+            opcode::PUSH1,
+            0x42,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x2,
+            opcode::SELFDESTRUCT,
+        ]);
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(ValueTransferInspector::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = caller;
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(3);
+            })
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.basefee = U256::from(1);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().unwrap();
+        let gas_used = result.result.gas_used();
+
+        let transfer = |kind, from, to, value: U256, depth| ValueTransfer {
+            kind,
+            from,
+            to,
+            value,
+            depth,
+            reverted: false,
+        };
+        assert_eq!(
+            evm.into_context().external.into_transfers(),
+            vec![
+                transfer(
+                    ValueTransferKind::Call,
+                    Address::ZERO,
+                    Address::with_last_byte(0x42),
+                    U256::from(5),
+                    2
+                ),
+                transfer(
+                    ValueTransferKind::SelfDestruct,
+                    Address::ZERO,
+                    Address::with_last_byte(2),
+                    U256::from(10_000_000 - 5),
+                    1
+                ),
+                transfer(
+                    ValueTransferKind::BeneficiaryReward,
+                    caller,
+                    coinbase,
+                    U256::from(2 * gas_used),
+                    0
+                ),
+            ]
+        );
+    }
+}
@@ -1,12 +1,15 @@
-use crate::interpreter::{InstructionResult, SelfDestructResult};
+use crate::interpreter::{gas::AccessWitness, InstructionResult, SelfDestructResult};
+use crate::precompile::address_to_u64;
 use crate::primitives::{
-    db::Database, hash_map::Entry, Account, Address, Bytecode, EVMError, HashMap, HashSet, Log,
-    SpecId::*, State, StorageSlot, TransientStorage, KECCAK_EMPTY, PRECOMPILE3, U256,
+    db::Database, hash_map::Entry, Account, AccountInfo, AccountStatus, Address, Bytecode,
+    CallDepthExceeded, ChainConfig, EVMError, HashMap, HashSet, JournalError, Log,
+    SelfDestructToSelf, SpecId::*, State, StorageSlot, TransactTo, TransientStorage, TxEnv,
+    KECCAK_EMPTY, PRECOMPILE3, U256,
 };
-use core::mem;
+use core::{mem, ops::RangeInclusive};
 use revm_interpreter::primitives::SpecId;
 use revm_interpreter::SStoreResult;
-use std::vec::Vec;
+use std::{collections::BTreeMap, string::String, vec::Vec};
 
 /// JournalState is internal EVM state that is used to contain state and track changes to that state.
 /// It contains journal of changes that happened to state so that they can be reverted.
@@ -16,9 +19,15 @@ pub struct JournaledState {
     /// Current state.
     pub state: State,
     /// EIP 1153 transient storage
+    ///
+    /// Serialized as a list of entries, as keys are not strings.
+    #[cfg_attr(feature = "serde", serde(with = "transient_storage_serde"))]
     pub transient_storage: TransientStorage,
     /// logs
     pub logs: Vec<Log>,
+    /// Depth of the frame that emitted the log, one entry for every log in `logs`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub log_depths: Vec<usize>,
     /// how deep are we in call stack.
     pub depth: usize,
     /// journal with changes that happened between calls.
@@ -34,6 +43,133 @@ pub struct JournaledState {
     /// Note that this not include newly loaded accounts, account and storage
     /// is considered warm if it is found in the `State`.
     pub warm_preloaded_addresses: HashSet<Address>,
+    /// Ranges of the addresses made by [`u64_to_address`](crate::precompile::u64_to_address)
+    /// that are warm same as [Self::warm_preloaded_addresses], used by precompile ranges.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub warm_preloaded_ranges: Vec<RangeInclusive<u64>>,
+    /// Info of the accounts when they were first loaded, `None` if account did not exist.
+    ///
+    /// Used as previous info of the [AccountTransition]s, only recorded if
+    /// [JournaledState::track_transitions] is set.
+    pub original_info: HashMap<Address, Option<AccountInfo>>,
+    /// Records the info of loaded accounts in [JournaledState::original_info], it needs to be
+    /// set before the accounts are loaded to use [JournaledState::finalize_with_transitions].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub track_transitions: bool,
+    /// Named snapshots of the journal, see [JournaledState::snapshot].
+    pub snapshots: HashMap<String, JournalCheckpoint>,
+    /// Addresses that stay touched when the frame that touched them is reverted, after Spurious
+    /// Dragon (EIP-161).
+    ///
+    /// By default it contains the RIPEMD-160 precompile, touch of it was not reverted in
+    /// mainnet block 2675119 and clients kept that behavior for consensus. The block is in
+    /// Spurious Dragon and clients apply the quirk to every block since then, so it is gated by
+    /// the spec and not by the block number. Other chains can clear it.
+    #[cfg_attr(feature = "serde", serde(default = "default_persistent_touches"))]
+    pub persistent_touches: HashSet<Address>,
+    /// Maximum call depth, checkpoint can't be made when the depth is above it.
+    ///
+    /// Default is [crate::CALL_STACK_LIMIT], it is set from the
+    /// [crate::primitives::ChainConfig::call_depth_limit] when the transaction accounts are loaded.
+    #[cfg_attr(feature = "serde", serde(default = "default_call_depth_limit"))]
+    pub call_depth_limit: usize,
+    /// Disables EIP-161 state clearing even if Spurious Dragon is enabled, touched empty accounts
+    /// are kept and account exists if it is in the trie or was touched.
+    ///
+    /// It is set from the [crate::primitives::ChainConfig::disable_state_clearing] when the
+    /// transaction accounts are loaded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disable_state_clearing: bool,
+    /// What happens to the balance of the account that is destroyed with itself as the target.
+    ///
+    /// It is set from the [crate::primitives::ChainConfig::selfdestruct_to_self] when the
+    /// transaction accounts are loaded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selfdestruct_to_self: SelfDestructToSelf,
+    /// EIP-4762: Witness of the transaction, it is not journaled as reverted accesses are
+    /// still part of the witness.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub access_witness: AccessWitness,
+}
+
+/// Default [JournaledState::persistent_touches] of the Ethereum mainnet.
+fn default_persistent_touches() -> HashSet<Address> {
+    HashSet::from_iter([PRECOMPILE3])
+}
+
+/// Default [JournaledState::call_depth_limit].
+fn default_call_depth_limit() -> usize {
+    crate::CALL_STACK_LIMIT as usize
+}
+
+/// See [JournaledState::is_warm_preloaded], takes the fields so the state can stay borrowed.
+#[inline]
+fn is_warm_preloaded(
+    addresses: &HashSet<Address>,
+    ranges: &[RangeInclusive<u64>],
+    address: &Address,
+) -> bool {
+    addresses.contains(address)
+        || address_to_u64(address)
+            .is_some_and(|index| ranges.iter().any(|range| range.contains(&index)))
+}
+
+/// Change of a single account made by the transaction, see [JournaledState::finalize_with_transitions].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountTransition {
+    /// Info before the transaction, `None` if account did not exist.
+    pub previous_info: Option<AccountInfo>,
+    /// Info after the transaction, `None` if account was destroyed or cleared as empty
+    /// account (EIP-161).
+    pub info: Option<AccountInfo>,
+    /// Changed storage slots with their original and present value.
+    ///
+    /// If account was destroyed all of its storage is wiped.
+    pub storage: HashMap<U256, StorageSlot>,
+    /// Account was created in the transaction.
+    pub created: bool,
+    /// Account was selfdestructed in the transaction.
+    pub destroyed: bool,
+    /// Slots of the destroyed account that had non zero value before the transaction, with
+    /// that value.
+    ///
+    /// Only slots loaded by the transaction are known, commit layer still needs to delete
+    /// all storage of the destroyed account.
+    pub wiped_storage: HashMap<U256, U256>,
+}
+
+/// [Account] with storage sorted by the slot key, see [JournaledState::finalize_ordered].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderedAccount {
+    /// Balance, nonce, and code.
+    pub info: AccountInfo,
+    /// Storage cache sorted by the slot key.
+    pub storage: BTreeMap<U256, StorageSlot>,
+    /// Account status flags.
+    pub status: AccountStatus,
+}
+
+/// [Log] with its position in the transaction, see [JournaledState::indexed_logs].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedLog {
+    pub log: Log,
+    /// Index of the log in the transaction, logs of the reverted frames are not counted.
+    pub index: u64,
+    /// Depth of the frame that emitted the log, top level frame has depth 1.
+    pub depth: u64,
+}
+
+impl From<Account> for OrderedAccount {
+    fn from(account: Account) -> Self {
+        Self {
+            info: account.info,
+            storage: account.storage.into_iter().collect(),
+            status: account.status,
+        }
+    }
 }
 
 impl JournaledState {
@@ -53,10 +189,20 @@ impl JournaledState {
             state: HashMap::new(),
             transient_storage: TransientStorage::default(),
             logs: Vec::new(),
+            log_depths: Vec::new(),
             journal: vec![vec![]],
             depth: 0,
             spec,
             warm_preloaded_addresses,
+            warm_preloaded_ranges: Vec::new(),
+            original_info: HashMap::new(),
+            track_transitions: false,
+            snapshots: HashMap::new(),
+            persistent_touches: default_persistent_touches(),
+            call_depth_limit: default_call_depth_limit(),
+            disable_state_clearing: false,
+            selfdestruct_to_self: SelfDestructToSelf::default(),
+            access_witness: AccessWitness::default(),
         }
     }
 
@@ -66,12 +212,40 @@ impl JournaledState {
         &mut self.state
     }
 
+    /// Returns `true` if the `address` is warm before it is loaded, it is one of the
+    /// [Self::warm_preloaded_addresses] or is in one of the [Self::warm_preloaded_ranges].
+    #[inline]
+    pub fn is_warm_preloaded(&self, address: &Address) -> bool {
+        is_warm_preloaded(
+            &self.warm_preloaded_addresses,
+            &self.warm_preloaded_ranges,
+            address,
+        )
+    }
+
     /// Sets SpecId.
     #[inline]
     pub fn set_spec_id(&mut self, spec: SpecId) {
         self.spec = spec;
     }
 
+    /// Sets the call depth limit, state clearing and selfdestruct to self policy of the
+    /// journal from the chain config.
+    #[inline]
+    pub fn set_chain_config(&mut self, chain: &ChainConfig) {
+        self.call_depth_limit = chain.call_depth_limit;
+        self.disable_state_clearing = chain.disable_state_clearing;
+        self.selfdestruct_to_self = chain.selfdestruct_to_self;
+    }
+
+    /// Returns `true` if touched empty accounts are cleared, see EIP-161.
+    ///
+    /// It is enabled from Spurious Dragon unless [JournaledState::disable_state_clearing] is set.
+    #[inline]
+    pub fn is_state_clearing_enabled(&self) -> bool {
+        SpecId::enabled(self.spec, SPURIOUS_DRAGON) && !self.disable_state_clearing
+    }
+
     /// Mark account as touched as only touched accounts will be added to state.
     /// This is especially important for state clear where touched empty accounts needs to
     /// be removed from state.
@@ -92,16 +266,142 @@ impl JournaledState {
     }
 
     /// Does cleanup and returns modified state.
+    ///
+    /// Transient storage and access witness are cleared as they only live for the duration of
+    /// the transaction.
     #[inline]
     pub fn finalize(&mut self) -> (State, Vec<Log>) {
         let state = mem::take(&mut self.state);
+        self.transient_storage.clear();
+        self.access_witness.clear();
 
         let logs = mem::take(&mut self.logs);
+        self.log_depths.clear();
+        self.original_info.clear();
+        self.snapshots.clear();
         self.journal = vec![vec![]];
         self.depth = 0;
         (state, logs)
     }
 
+    /// Removes the accounts from the state and returns them as [JournaledState::finalize] would,
+    /// without touching the rest of the state, logs and journal.
+    ///
+    /// Accounts that are not loaded are skipped. Returned accounts are no longer tracked and
+    /// next access loads them from the database, so their changes need to be final: it errors
+    /// if a checkpoint is not committed or if a snapshot can still revert a change of one of
+    /// the accounts. Journal entries of the accounts are dropped as nothing can revert them.
+    /// Nothing is removed on error.
+    #[inline]
+    pub fn finalize_accounts<'a>(
+        &mut self,
+        addresses: impl IntoIterator<Item = &'a Address>,
+    ) -> Result<State, JournalError> {
+        if self.depth != 0 {
+            return Err(JournalError::UncommittedCheckpoint { depth: self.depth });
+        }
+        let addresses: HashSet<&Address> = addresses.into_iter().collect();
+        // journal from the first snapshot on can still be reverted.
+        let revertible = self
+            .snapshots
+            .values()
+            .map(|snapshot| snapshot.journal_i)
+            .min()
+            .unwrap_or(self.journal.len());
+        if let Some(address) = self.journal[revertible..]
+            .iter()
+            .flatten()
+            .flat_map(JournalEntry::addresses)
+            .find(|address| addresses.contains(address))
+        {
+            return Err(JournalError::AccountJournaled(*address));
+        }
+        for entries in &mut self.journal[..revertible] {
+            entries.retain(|entry| !entry.addresses().any(|address| addresses.contains(address)));
+        }
+        Ok(addresses
+            .into_iter()
+            .filter_map(|address| {
+                self.original_info.remove(address);
+                self.state
+                    .remove(address)
+                    .map(|account| (*address, account))
+            })
+            .collect())
+    }
+
+    /// Does cleanup like [JournaledState::finalize] and additionally returns the change set
+    /// of every touched account, with previous and new info and changed storage slots.
+    ///
+    /// Previous info is only known if [JournaledState::track_transitions] was set when the
+    /// accounts were loaded.
+    #[inline]
+    pub fn finalize_with_transitions(
+        &mut self,
+    ) -> (State, Vec<Log>, HashMap<Address, AccountTransition>) {
+        debug_assert!(
+            self.track_transitions,
+            "transitions are finalized without tracking"
+        );
+        let is_state_clearing_enabled = self.is_state_clearing_enabled();
+        let transitions = self
+            .state
+            .iter()
+            .filter(|(_, account)| account.is_touched())
+            .map(|(address, account)| {
+                let destroyed = account.is_selfdestructed();
+                let cleared = is_state_clearing_enabled && account.is_empty();
+                let transition = AccountTransition {
+                    previous_info: self.original_info.get(address).cloned().flatten(),
+                    info: (!destroyed && !cleared).then(|| account.info.clone()),
+                    storage: account
+                        .changed_storage_slots()
+                        .map(|(key, slot)| (*key, slot.clone()))
+                        .collect(),
+                    created: account.is_created(),
+                    destroyed,
+                    wiped_storage: if destroyed {
+                        account
+                            .storage
+                            .iter()
+                            .filter(|(_, slot)| slot.previous_or_original_value != U256::ZERO)
+                            .map(|(key, slot)| (*key, slot.previous_or_original_value))
+                            .collect()
+                    } else {
+                        HashMap::default()
+                    },
+                };
+                (*address, transition)
+            })
+            .collect();
+        let (state, logs) = self.finalize();
+        (state, logs, transitions)
+    }
+
+    /// Does cleanup like [JournaledState::finalize] but returns the state sorted by the address,
+    /// and storage of every account sorted by the slot key.
+    ///
+    /// Iteration order of the returned state does not depend on the hasher, so it can be
+    /// used where output needs to be reproducible.
+    #[inline]
+    pub fn finalize_ordered(&mut self) -> (BTreeMap<Address, OrderedAccount>, Vec<Log>) {
+        let (state, logs) = self.finalize();
+        let state = state
+            .into_iter()
+            .map(|(address, account)| (address, account.into()))
+            .collect();
+        (state, logs)
+    }
+
+    /// Does cleanup like [JournaledState::finalize] and returns the logs with their
+    /// position in the transaction.
+    #[inline]
+    pub fn finalize_with_indexed_logs(&mut self) -> (State, Vec<IndexedLog>) {
+        let logs = self.indexed_logs().collect();
+        let (state, _) = self.finalize();
+        (state, logs)
+    }
+
     /// Returns the _loaded_ [Account] for the given address.
     ///
     /// This assumes that the account has already been loaded.
@@ -110,23 +410,155 @@ impl JournaledState {
     ///
     /// Panics if the account has not been loaded and is missing from the state set.
     #[inline]
+    #[deprecated = "use `get_account` instead"]
     pub fn account(&self, address: Address) -> &Account {
         self.state
             .get(&address)
             .expect("Account expected to be loaded") // Always assume that acc is already loaded
     }
 
+    /// Returns the [Account] for the given address if it is loaded.
+    ///
+    /// Unlike [JournaledState::load_account] it does not load the account from the database,
+    /// so it can be used to read the state, e.g. from the inspector hooks.
+    #[inline]
+    pub fn get_account(&self, address: &Address) -> Option<&Account> {
+        self.state.get(address)
+    }
+
+    /// Returns the present value of the storage slot if it is loaded.
+    ///
+    /// Unlike [JournaledState::sload] it does not load the slot from the database.
+    #[inline]
+    pub fn get_storage(&self, address: &Address, key: &U256) -> Option<U256> {
+        self.state
+            .get(address)
+            .and_then(|account| account.storage.get(key))
+            .map(|slot| slot.present_value)
+    }
+
+    /// Returns the logs emitted so far, including logs of the frames that are still executing.
+    #[inline]
+    pub fn logs(&self) -> &[Log] {
+        &self.logs
+    }
+
+    /// Returns the logs emitted so far with their index in the transaction and depth of
+    /// the frame that emitted them.
+    #[inline]
+    pub fn indexed_logs(&self) -> impl Iterator<Item = IndexedLog> + '_ {
+        self.logs
+            .iter()
+            .zip(&self.log_depths)
+            .enumerate()
+            .map(|(index, (log, depth))| IndexedLog {
+                log: log.clone(),
+                index: index as u64,
+                depth: *depth as u64,
+            })
+    }
+
+    /// Returns the journal layers in the order they were created.
+    ///
+    /// First layer holds entries made before the first checkpoint and every checkpoint
+    /// adds a new layer. Layers of the committed checkpoints are kept until the state is
+    /// finalized, reverted layers are removed.
+    #[inline]
+    pub fn journal_layers(&self) -> impl Iterator<Item = &[JournalEntry]> {
+        self.journal.iter().map(Vec::as_slice)
+    }
+
+    /// Returns all journal entries made after the checkpoint, in the order they were made.
+    #[inline]
+    pub fn entries_since(
+        &self,
+        checkpoint: &JournalCheckpoint,
+    ) -> impl Iterator<Item = &JournalEntry> {
+        self.journal
+            .iter()
+            .skip(checkpoint.journal_i)
+            .flat_map(|layer| layer.iter())
+    }
+
+    /// Applies the changes recorded by the journal entries to the external state.
+    ///
+    /// Entries only hold the values needed to revert them, so the changed account info,
+    /// status and storage slots are copied from the current state. Accounts that are no
+    /// longer loaded are removed from the external state. Transient storage is not replayed.
+    ///
+    /// Used to mirror in-flight changes, e.g. with entries of [JournaledState::entries_since].
+    pub fn replay_onto<'a>(
+        &self,
+        entries: impl IntoIterator<Item = &'a JournalEntry>,
+        target: &mut State,
+    ) {
+        let state = &self.state;
+        for entry in entries {
+            match entry {
+                JournalEntry::AccountLoaded { address }
+                | JournalEntry::AccountTouched { address }
+                | JournalEntry::NonceChange { address }
+                | JournalEntry::BalanceChange { address, .. }
+                | JournalEntry::NonceSet { address, .. }
+                | JournalEntry::AccountCreated { address }
+                | JournalEntry::CodeChange { address } => {
+                    Self::mirror_account(state, target, address);
+                }
+                JournalEntry::AccountDestroyed {
+                    address,
+                    target: beneficiary,
+                    ..
+                } => {
+                    Self::mirror_account(state, target, address);
+                    Self::mirror_account(state, target, beneficiary);
+                }
+                JournalEntry::BalanceTransfer { from, to, .. } => {
+                    Self::mirror_account(state, target, from);
+                    Self::mirror_account(state, target, to);
+                }
+                JournalEntry::StorageChange { address, key, .. } => {
+                    if let Some((account, mirrored)) = Self::mirror_account(state, target, address)
+                    {
+                        match account.storage.get(key) {
+                            Some(slot) => mirrored.storage.insert(*key, slot.clone()),
+                            None => mirrored.storage.remove(key),
+                        };
+                    }
+                }
+                JournalEntry::TransientStorageChange { .. } => {}
+            }
+        }
+    }
+
+    /// Copies info and status of the account to the external state, or removes it if the
+    /// account is not loaded.
+    fn mirror_account<'s, 't>(
+        state: &'s State,
+        target: &'t mut State,
+        address: &Address,
+    ) -> Option<(&'s Account, &'t mut Account)> {
+        let Some(account) = state.get(address) else {
+            target.remove(address);
+            return None;
+        };
+        let mirrored = target.entry(*address).or_default();
+        mirrored.info = account.info.clone();
+        mirrored.status = account.status;
+        Some((account, mirrored))
+    }
+
     /// Returns call depth.
     #[inline]
     pub fn depth(&self) -> u64 {
         self.depth as u64
     }
 
-    /// use it only if you know that acc is warm
-    /// Assume account is warm
+    /// Sets code of the loaded account.
+    ///
+    /// Returns error if account is not loaded.
     #[inline]
-    pub fn set_code(&mut self, address: Address, code: Bytecode) {
-        let account = self.state.get_mut(&address).unwrap();
+    pub fn set_code(&mut self, address: Address, code: Bytecode) -> Result<(), JournalError> {
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
         Self::touch_account(self.journal.last_mut().unwrap(), &address, account);
 
         self.journal
@@ -136,14 +568,18 @@ impl JournaledState {
 
         account.info.code_hash = code.hash_slow();
         account.info.code = Some(code);
+        Ok(())
     }
 
+    /// Increments nonce of the loaded account, returns the new nonce or `None` on overflow.
+    ///
+    /// Returns error if account is not loaded.
     #[inline]
-    pub fn inc_nonce(&mut self, address: Address) -> Option<u64> {
-        let account = self.state.get_mut(&address).unwrap();
+    pub fn inc_nonce(&mut self, address: Address) -> Result<Option<u64>, JournalError> {
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
         // Check if nonce is going to overflow.
         if account.info.nonce == u64::MAX {
-            return None;
+            return Ok(None);
         }
         Self::touch_account(self.journal.last_mut().unwrap(), &address, account);
         self.journal
@@ -153,7 +589,18 @@ impl JournaledState {
 
         account.info.nonce += 1;
 
-        Some(account.info.nonce)
+        Ok(Some(account.info.nonce))
+    }
+
+    /// Returns the loaded account or [JournalError::AccountNotLoaded].
+    #[inline]
+    fn loaded_account_mut<'a>(
+        state: &'a mut State,
+        address: &Address,
+    ) -> Result<&'a mut Account, JournalError> {
+        state
+            .get_mut(address)
+            .ok_or(JournalError::AccountNotLoaded(*address))
     }
 
     /// Transfers balance from two accounts. Returns error if sender balance is not enough.
@@ -170,7 +617,7 @@ impl JournaledState {
         self.load_account(*to, db)?;
 
         // sub balance from
-        let from_account = &mut self.state.get_mut(from).unwrap();
+        let from_account = Self::loaded_account_mut(&mut self.state, from)?;
         Self::touch_account(self.journal.last_mut().unwrap(), from, from_account);
         let from_balance = &mut from_account.info.balance;
 
@@ -180,7 +627,7 @@ impl JournaledState {
         *from_balance = from_balance_incr;
 
         // add balance to
-        let to_account = &mut self.state.get_mut(to).unwrap();
+        let to_account = Self::loaded_account_mut(&mut self.state, to)?;
         Self::touch_account(self.journal.last_mut().unwrap(), to, to_account);
         let to_balance = &mut to_account.info.balance;
         let Some(to_balance_decr) = to_balance.checked_add(balance) else {
@@ -205,17 +652,18 @@ impl JournaledState {
     ///
     /// There are few steps done:
     /// 1. Make created account warm loaded (AccessList) and this should
-    ///     be done before subroutine checkpoint is created.
+    ///    be done before subroutine checkpoint is created.
     /// 2. Check if there is collision of newly created account with existing one.
     /// 3. Mark created account as created.
     /// 4. Add fund to created account
     /// 5. Increment nonce of created account if SpuriousDragon is active
     /// 6. Decrease balance of caller account.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the caller is not loaded inside of the EVM state.
-    /// This is should have been done inside `create_inner`.
+    /// Returns [CreateError::Failed] with the result of the frame if the account can't be
+    /// created, and [CreateError::Journal] if the caller or the created account is not loaded
+    /// inside of the EVM state. Loading should have been done inside `make_create_frame`.
     #[inline]
     pub fn create_account_checkpoint(
         &mut self,
@@ -223,24 +671,30 @@ impl JournaledState {
         address: Address,
         balance: U256,
         spec_id: SpecId,
-    ) -> Result<JournalCheckpoint, InstructionResult> {
+    ) -> Result<JournalCheckpoint, CreateError> {
+        for address in [caller, address] {
+            if !self.state.contains_key(&address) {
+                return Err(JournalError::AccountNotLoaded(address).into());
+            }
+        }
+
         // Enter subroutine
-        let checkpoint = self.checkpoint();
+        let Ok(checkpoint) = self.checkpoint() else {
+            return Err(CreateError::Failed(InstructionResult::CallTooDeep));
+        };
 
+        let is_precompile = self.is_warm_preloaded(&address);
         // Newly created account is present, as we just loaded it.
-        let account = self.state.get_mut(&address).unwrap();
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
         let last_journal = self.journal.last_mut().unwrap();
 
         // New account can be created if:
         // Bytecode is not empty.
         // Nonce is not zero
         // Account is not precompile.
-        if account.info.code_hash != KECCAK_EMPTY
-            || account.info.nonce != 0
-            || self.warm_preloaded_addresses.contains(&address)
-        {
+        if account.info.code_hash != KECCAK_EMPTY || account.info.nonce != 0 || is_precompile {
             self.checkpoint_revert(checkpoint);
-            return Err(InstructionResult::CreateCollision);
+            return Err(CreateError::Failed(InstructionResult::CreateCollision));
         }
 
         // set account status to created.
@@ -266,7 +720,7 @@ impl JournaledState {
         // Add balance to created account, as we already have target here.
         let Some(new_balance) = account.info.balance.checked_add(balance) else {
             self.checkpoint_revert(checkpoint);
-            return Err(InstructionResult::OverflowPayment);
+            return Err(CreateError::Failed(InstructionResult::OverflowPayment));
         };
         account.info.balance = new_balance;
 
@@ -277,7 +731,7 @@ impl JournaledState {
         }
 
         // Sub balance from caller
-        let caller_account = self.state.get_mut(&caller).unwrap();
+        let caller_account = Self::loaded_account_mut(&mut self.state, &caller)?;
         // Balance is already checked in `create_inner`, so it is safe to just subtract.
         caller_account.info.balance -= balance;
 
@@ -292,24 +746,32 @@ impl JournaledState {
     }
 
     /// Revert all changes that happened in given journal entries.
-    #[inline]
+    ///
+    /// Entries of the accounts that are missing from the state are skipped.
+    /// Accounts in `persistent_touches` stay loaded and touched.
     fn journal_revert(
         state: &mut State,
         transient_storage: &mut TransientStorage,
         journal_entries: Vec<JournalEntry>,
-        is_spurious_dragon_enabled: bool,
+        persistent_touches: &HashSet<Address>,
     ) {
         for entry in journal_entries.into_iter().rev() {
             match entry {
                 JournalEntry::AccountLoaded { address } => {
-                    state.remove(&address);
+                    let keep = persistent_touches.contains(&address)
+                        && state.get(&address).is_some_and(|acc| acc.is_touched());
+                    if !keep {
+                        state.remove(&address);
+                    }
                 }
                 JournalEntry::AccountTouched { address } => {
-                    if is_spurious_dragon_enabled && address == PRECOMPILE3 {
+                    if persistent_touches.contains(&address) {
                         continue;
                     }
                     // remove touched status
-                    state.get_mut(&address).unwrap().unmark_touch();
+                    if let Some(account) = state.get_mut(&address) {
+                        account.unmark_touch();
+                    }
                 }
                 JournalEntry::AccountDestroyed {
                     address,
@@ -317,7 +779,9 @@ impl JournaledState {
                     was_destroyed,
                     had_balance,
                 } => {
-                    let account = state.get_mut(&address).unwrap();
+                    let Some(account) = state.get_mut(&address) else {
+                        continue;
+                    };
                     // set previous state of selfdestructed flag, as there could be multiple
                     // selfdestructs in one transaction.
                     if was_destroyed {
@@ -330,35 +794,58 @@ impl JournaledState {
                     account.info.balance += had_balance;
 
                     if address != target {
-                        let target = state.get_mut(&target).unwrap();
-                        target.info.balance -= had_balance;
+                        if let Some(target) = state.get_mut(&target) {
+                            target.info.balance -= had_balance;
+                        }
                     }
                 }
                 JournalEntry::BalanceTransfer { from, to, balance } => {
                     // we don't need to check overflow and underflow when adding and subtracting the balance.
-                    let from = state.get_mut(&from).unwrap();
-                    from.info.balance += balance;
-                    let to = state.get_mut(&to).unwrap();
-                    to.info.balance -= balance;
+                    if let Some(from) = state.get_mut(&from) {
+                        from.info.balance += balance;
+                    }
+                    if let Some(to) = state.get_mut(&to) {
+                        to.info.balance -= balance;
+                    }
                 }
                 JournalEntry::NonceChange { address } => {
-                    state.get_mut(&address).unwrap().info.nonce -= 1;
+                    if let Some(account) = state.get_mut(&address) {
+                        account.info.nonce -= 1;
+                    }
+                }
+                JournalEntry::BalanceChange {
+                    address,
+                    had_balance,
+                } => {
+                    if let Some(account) = state.get_mut(&address) {
+                        account.info.balance = had_balance;
+                    }
+                }
+                JournalEntry::NonceSet { address, had_nonce } => {
+                    if let Some(account) = state.get_mut(&address) {
+                        account.info.nonce = had_nonce;
+                    }
                 }
                 JournalEntry::AccountCreated { address } => {
-                    let account = &mut state.get_mut(&address).unwrap();
-                    account.unmark_created();
-                    account.info.nonce = 0;
+                    if let Some(account) = state.get_mut(&address) {
+                        account.unmark_created();
+                        account.info.nonce = 0;
+                    }
                 }
                 JournalEntry::StorageChange {
                     address,
                     key,
                     had_value,
                 } => {
-                    let storage = &mut state.get_mut(&address).unwrap().storage;
+                    let Some(account) = state.get_mut(&address) else {
+                        continue;
+                    };
                     if let Some(had_value) = had_value {
-                        storage.get_mut(&key).unwrap().present_value = had_value;
+                        if let Some(slot) = account.storage.get_mut(&key) {
+                            slot.present_value = had_value;
+                        }
                     } else {
-                        storage.remove(&key);
+                        account.storage.remove(&key);
                     }
                 }
                 JournalEntry::TransientStorageChange {
@@ -376,20 +863,42 @@ impl JournaledState {
                     }
                 }
                 JournalEntry::CodeChange { address } => {
-                    let acc = state.get_mut(&address).unwrap();
-                    acc.info.code_hash = KECCAK_EMPTY;
-                    acc.info.code = None;
+                    if let Some(acc) = state.get_mut(&address) {
+                        acc.info.code_hash = KECCAK_EMPTY;
+                        acc.info.code = None;
+                    }
                 }
             }
         }
     }
 
+    /// Returns error if the current call depth is above [JournaledState::call_depth_limit].
+    #[inline]
+    pub fn check_depth(&self) -> Result<(), CallDepthExceeded> {
+        if self.depth > self.call_depth_limit {
+            return Err(CallDepthExceeded {
+                limit: self.call_depth_limit,
+            });
+        }
+        Ok(())
+    }
+
     /// Makes a checkpoint that in case of Revert can bring back state to this point.
+    ///
+    /// Returns error if the call depth is above the limit, see [JournaledState::check_depth].
+    #[inline]
+    pub fn checkpoint(&mut self) -> Result<JournalCheckpoint, CallDepthExceeded> {
+        self.check_depth()?;
+        Ok(self.push_checkpoint())
+    }
+
+    /// Makes a checkpoint without checking the call depth.
     #[inline]
-    pub fn checkpoint(&mut self) -> JournalCheckpoint {
+    fn push_checkpoint(&mut self) -> JournalCheckpoint {
         let checkpoint = JournalCheckpoint {
             log_i: self.logs.len(),
             journal_i: self.journal.len(),
+            depth: self.depth,
         };
         self.depth += 1;
         self.journal.push(Default::default());
@@ -403,12 +912,23 @@ impl JournaledState {
     }
 
     /// Reverts all changes to state until given checkpoint.
+    ///
+    /// Checkpoint does not need to be the last one, reverting to an earlier checkpoint
+    /// discards all checkpoints and snapshots made after it and they should not be used again.
     #[inline]
     pub fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
-        let is_spurious_dragon_enabled = SpecId::enabled(self.spec, SPURIOUS_DRAGON);
+        let no_persistent_touches = HashSet::default();
+        // touch is not tracked before EIP-161.
+        let persistent_touches = if self.is_state_clearing_enabled() {
+            &self.persistent_touches
+        } else {
+            &no_persistent_touches
+        };
         let state = &mut self.state;
         let transient_storage = &mut self.transient_storage;
-        self.depth -= 1;
+        self.depth = checkpoint.depth;
+        self.snapshots
+            .retain(|_, snapshot| snapshot.journal_i < checkpoint.journal_i);
         // iterate over last N journals sets and revert our global state
         let leng = self.journal.len();
         self.journal
@@ -416,18 +936,39 @@ impl JournaledState {
             .rev()
             .take(leng - checkpoint.journal_i)
             .for_each(|cs| {
-                Self::journal_revert(
-                    state,
-                    transient_storage,
-                    mem::take(cs),
-                    is_spurious_dragon_enabled,
-                )
+                Self::journal_revert(state, transient_storage, mem::take(cs), persistent_touches)
             });
 
         self.logs.truncate(checkpoint.log_i);
+        self.log_depths.truncate(checkpoint.log_i);
         self.journal.truncate(checkpoint.journal_i);
     }
 
+    /// Takes the named snapshot of the current state, replacing the snapshot with the same name.
+    ///
+    /// Unlike [JournaledState::checkpoint] it does not change the call depth. Snapshots live
+    /// until the state is finalized, see [JournaledState::revert_to_snapshot].
+    #[inline]
+    pub fn snapshot(&mut self, name: impl Into<String>) -> JournalCheckpoint {
+        let checkpoint = self.push_checkpoint();
+        self.checkpoint_commit();
+        self.snapshots.insert(name.into(), checkpoint);
+        checkpoint
+    }
+
+    /// Reverts all changes made after the named snapshot.
+    ///
+    /// The snapshot and all snapshots taken after it are removed. Returns false if there is
+    /// no snapshot with the name.
+    #[inline]
+    pub fn revert_to_snapshot(&mut self, name: &str) -> bool {
+        let Some(checkpoint) = self.snapshots.get(name).copied() else {
+            return false;
+        };
+        self.checkpoint_revert(checkpoint);
+        true
+    }
+
     /// Performans selfdestruct action.
     /// Transfers balance from address to target. Check if target exist/is_cold
     ///
@@ -451,20 +992,28 @@ impl JournaledState {
         if address != target {
             // Both accounts are loaded before this point, `address` as we execute its contract.
             // and `target` at the beginning of the function.
-            let acc_balance = self.state.get_mut(&address).unwrap().info.balance;
+            let acc_balance = Self::loaded_account_mut(&mut self.state, &address)?
+                .info
+                .balance;
 
-            let target_account = self.state.get_mut(&target).unwrap();
+            let target_account = Self::loaded_account_mut(&mut self.state, &target)?;
             Self::touch_account(self.journal.last_mut().unwrap(), &target, target_account);
             target_account.info.balance += acc_balance;
         }
 
-        let acc = self.state.get_mut(&address).unwrap();
+        let acc = Self::loaded_account_mut(&mut self.state, &address)?;
         let balance = acc.info.balance;
         let previously_destroyed = acc.is_selfdestructed();
         let is_cancun_enabled = SpecId::enabled(self.spec, CANCUN);
 
+        let keep_to_self =
+            address == target && self.selfdestruct_to_self == SelfDestructToSelf::Keep;
+
         // EIP-6780 (Cancun hard-fork): selfdestruct only if contract is created in the same tx
-        let journal_entry = if acc.is_created() || !is_cancun_enabled {
+        let journal_entry = if keep_to_self {
+            // State is not changed, the chain keeps the account that selfdestructs to itself.
+            None
+        } else if acc.is_created() || !is_cancun_enabled {
             acc.mark_selfdestruct();
             acc.info.balance = U256::ZERO;
             Some(JournalEntry::AccountDestroyed {
@@ -511,16 +1060,24 @@ impl JournaledState {
         // load or get account.
         let account = match self.state.entry(address) {
             Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(vac) => vac.insert(
-                db.basic(address)
-                    .map_err(EVMError::Database)?
-                    .map(|i| i.into())
-                    .unwrap_or(Account::new_not_existing()),
-            ),
+            Entry::Vacant(vac) => {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(target: "revm::db", %address, "basic");
+                let info = db.basic(address).map_err(EVMError::Database)?;
+                if self.track_transitions {
+                    self.original_info.insert(address, info.clone());
+                }
+                vac.insert(
+                    info.map(|i| i.into())
+                        .unwrap_or(Account::new_not_existing()),
+                )
+            }
         };
         // preload storages.
         for slot in slots {
             if let Entry::Vacant(entry) = account.storage.entry(*slot) {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(target: "revm::db", %address, %slot, "storage");
                 let storage = db.storage(address, *slot).map_err(EVMError::Database)?;
                 entry.insert(StorageSlot::new(storage));
             }
@@ -528,6 +1085,27 @@ impl JournaledState {
         Ok(account)
     }
 
+    /// Loads accounts and storage slots that are warm from the start of the transaction.
+    ///
+    /// Caller, callee and the addresses and slots of the access list are warm (EIP-2929 and
+    /// EIP-2930). They are loaded before the first checkpoint so loading is not journaled.
+    /// Precompiles are warm through `warm_preloaded_addresses`.
+    #[inline]
+    pub fn initial_account_and_storage_load<DB: Database>(
+        &mut self,
+        tx: &TxEnv,
+        db: &mut DB,
+    ) -> Result<(), EVMError<DB::Error>> {
+        self.initial_account_load(tx.caller, &[], db)?;
+        if let TransactTo::Call(address) = tx.transact_to {
+            self.initial_account_load(address, &[], db)?;
+        }
+        for (address, slots) in tx.access_list.iter() {
+            self.initial_account_load(*address, slots, db)?;
+        }
+        Ok(())
+    }
+
     /// load account into memory. return if it is cold or warm accessed
     #[inline]
     pub fn load_account<DB: Database>(
@@ -538,12 +1116,17 @@ impl JournaledState {
         Ok(match self.state.entry(address) {
             Entry::Occupied(entry) => (entry.into_mut(), false),
             Entry::Vacant(vac) => {
-                let account =
-                    if let Some(account) = db.basic(address).map_err(EVMError::Database)? {
-                        account.into()
-                    } else {
-                        Account::new_not_existing()
-                    };
+                #[cfg(feature = "tracing")]
+                tracing::trace!(target: "revm::db", %address, "basic");
+                let info = db.basic(address).map_err(EVMError::Database)?;
+                if self.track_transitions {
+                    self.original_info.insert(address, info.clone());
+                }
+                let account = if let Some(account) = info {
+                    account.into()
+                } else {
+                    Account::new_not_existing()
+                };
 
                 // journal loading of account. AccessList touch.
                 self.journal
@@ -552,7 +1135,11 @@ impl JournaledState {
                     .push(JournalEntry::AccountLoaded { address });
 
                 // precompiles are warm loaded so we need to take that into account
-                let is_cold = !self.warm_preloaded_addresses.contains(&address);
+                let is_cold = !is_warm_preloaded(
+                    &self.warm_preloaded_addresses,
+                    &self.warm_preloaded_ranges,
+                    &address,
+                );
 
                 (vac.insert(account), is_cold)
             }
@@ -562,16 +1149,21 @@ impl JournaledState {
     /// Load account from database to JournaledState.
     ///
     /// Return boolean pair where first is `is_cold` second bool `is_exists`.
+    ///
+    /// Before Spurious Dragon account exists if it is in the trie or was touched,
+    /// after it (EIP-161) account exists if it is not empty.
+    ///
+    /// See [JournaledState::is_state_clearing_enabled].
     #[inline]
     pub fn load_account_exist<DB: Database>(
         &mut self,
         address: Address,
         db: &mut DB,
     ) -> Result<(bool, bool), EVMError<DB::Error>> {
-        let is_spurious_dragon_enabled = SpecId::enabled(self.spec, SPURIOUS_DRAGON);
+        let is_state_clearing_enabled = self.is_state_clearing_enabled();
         let (acc, is_cold) = self.load_account(address, db)?;
 
-        let exist = if is_spurious_dragon_enabled {
+        let exist = if is_state_clearing_enabled {
             !acc.is_empty()
         } else {
             let is_existing = !acc.is_loaded_as_not_existing();
@@ -594,6 +1186,8 @@ impl JournaledState {
                 let empty = Bytecode::new();
                 acc.info.code = Some(empty);
             } else {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(target: "revm::db", code_hash = %acc.info.code_hash, "code_by_hash");
                 let code = db
                     .code_by_hash(acc.info.code_hash)
                     .map_err(EVMError::Database)?;
@@ -615,8 +1209,8 @@ impl JournaledState {
         key: U256,
         db: &mut DB,
     ) -> Result<(U256, bool), EVMError<DB::Error>> {
-        let account = self.state.get_mut(&address).unwrap(); // assume acc is warm
-                                                             // only if account is created in this tx we can assume that storage is empty.
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
+        // only if account is created in this tx we can assume that storage is empty.
         let is_newly_created = account.is_created();
         let load = match account.storage.entry(key) {
             Entry::Occupied(occ) => (occ.get().present_value, false),
@@ -625,6 +1219,8 @@ impl JournaledState {
                 let value = if is_newly_created {
                     U256::ZERO
                 } else {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(target: "revm::db", %address, %key, "storage");
                     db.storage(address, key).map_err(EVMError::Database)?
                 };
                 // add it to journal as cold loaded.
@@ -661,10 +1257,13 @@ impl JournaledState {
     ) -> Result<SStoreResult, EVMError<DB::Error>> {
         // assume that acc exists and load the slot.
         let (present, is_cold) = self.sload(address, key, db)?;
-        let acc = self.state.get_mut(&address).unwrap();
+        let acc = Self::loaded_account_mut(&mut self.state, &address)?;
 
         // if there is no original value in dirty return present value, that is our original.
-        let slot = acc.storage.get_mut(&key).unwrap();
+        let slot = acc
+            .storage
+            .get_mut(&key)
+            .ok_or(JournalError::StorageNotLoaded { address, key })?;
 
         // new value is same as present, we don't need to do anything
         if present == new {
@@ -694,6 +1293,68 @@ impl JournaledState {
         })
     }
 
+    /// Sets balance of the account, loading it if needed.
+    ///
+    /// Change is journaled and reverted together with the current frame,
+    /// used for cheatcode-like state edits.
+    #[inline]
+    pub fn set_balance<DB: Database>(
+        &mut self,
+        address: Address,
+        balance: U256,
+        db: &mut DB,
+    ) -> Result<(), EVMError<DB::Error>> {
+        self.load_account(address, db)?;
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
+        let journal = self.journal.last_mut().unwrap();
+        Self::touch_account(journal, &address, account);
+        journal.push(JournalEntry::BalanceChange {
+            address,
+            had_balance: account.info.balance,
+        });
+        account.info.balance = balance;
+        Ok(())
+    }
+
+    /// Sets nonce of the account, loading it if needed.
+    ///
+    /// Change is journaled and reverted together with the current frame.
+    #[inline]
+    pub fn set_nonce<DB: Database>(
+        &mut self,
+        address: Address,
+        nonce: u64,
+        db: &mut DB,
+    ) -> Result<(), EVMError<DB::Error>> {
+        self.load_account(address, db)?;
+        let account = Self::loaded_account_mut(&mut self.state, &address)?;
+        let journal = self.journal.last_mut().unwrap();
+        Self::touch_account(journal, &address, account);
+        journal.push(JournalEntry::NonceSet {
+            address,
+            had_nonce: account.info.nonce,
+        });
+        account.info.nonce = nonce;
+        Ok(())
+    }
+
+    /// Sets storage slot of the account, loading the account and the slot if needed.
+    ///
+    /// Change is journaled like [JournaledState::sstore] and reverted together with the current frame.
+    #[inline]
+    pub fn set_storage<DB: Database>(
+        &mut self,
+        address: Address,
+        key: U256,
+        value: U256,
+        db: &mut DB,
+    ) -> Result<(), EVMError<DB::Error>> {
+        self.load_account(address, db)?;
+        self.touch(&address);
+        self.sstore(address, key, value, db)?;
+        Ok(())
+    }
+
     /// Read transient storage tied to the account.
     ///
     /// EIP-1153: Transient storage opcodes
@@ -751,6 +1412,7 @@ impl JournaledState {
     #[inline]
     pub fn log(&mut self, log: Log) {
         self.logs.push(log);
+        self.log_depths.push(self.depth);
     }
 }
 
@@ -790,6 +1452,14 @@ pub enum JournalEntry {
     NonceChange {
         address: Address, //geth has nonce value,
     },
+    /// Set balance of the account
+    /// Action: Set balance
+    /// Revert: Set balance back to the previous value
+    BalanceChange { address: Address, had_balance: U256 },
+    /// Set nonce of the account
+    /// Action: Set nonce
+    /// Revert: Set nonce back to the previous value
+    NonceSet { address: Address, had_nonce: u64 },
     /// Create account:
     /// Actions: Mark account as created
     /// Revert: Unmart account as created and reset nonce to zero.
@@ -817,9 +1487,803 @@ pub enum JournalEntry {
     CodeChange { address: Address },
 }
 
+impl JournalEntry {
+    /// Returns the accounts that the entry changes.
+    fn addresses(&self) -> impl Iterator<Item = &Address> {
+        let (address, other) = match self {
+            JournalEntry::BalanceTransfer { from, to, .. } => (from, Some(to)),
+            JournalEntry::AccountDestroyed {
+                address, target, ..
+            } => (address, Some(target)),
+            JournalEntry::AccountLoaded { address }
+            | JournalEntry::AccountTouched { address }
+            | JournalEntry::NonceChange { address }
+            | JournalEntry::BalanceChange { address, .. }
+            | JournalEntry::NonceSet { address, .. }
+            | JournalEntry::AccountCreated { address }
+            | JournalEntry::StorageChange { address, .. }
+            | JournalEntry::TransientStorageChange { address, .. }
+            | JournalEntry::CodeChange { address } => (address, None),
+        };
+        core::iter::once(address).chain(other)
+    }
+}
+
+/// Serializes [TransientStorage] as a list of `((address, key), value)` entries.
+#[cfg(feature = "serde")]
+mod transient_storage_serde {
+    use super::{Address, TransientStorage, U256};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::vec::Vec;
+
+    pub(super) fn serialize<S: Serializer>(
+        storage: &TransientStorage,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(storage.iter())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TransientStorage, D::Error> {
+        let entries = Vec::<((Address, U256), U256)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Error of [JournaledState::create_account_checkpoint].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CreateError {
+    /// Account can't be created, the result is returned from the create frame.
+    Failed(InstructionResult),
+    /// Caller or created account is not loaded.
+    Journal(JournalError),
+}
+
+impl From<JournalError> for CreateError {
+    fn from(error: JournalError) -> Self {
+        Self::Journal(error)
+    }
+}
+
 /// SubRoutine checkpoint that will help us to go back from this
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JournalCheckpoint {
     log_i: usize,
     journal_i: usize,
+    /// Call depth before the checkpoint was made.
+    depth: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_storage_revert_and_finalize() {
+        let address = Address::with_last_byte(1);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.tstore(address, U256::ZERO, U256::from(1));
+
+        let checkpoint = journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(2));
+        assert_eq!(journal.tload(address, U256::ZERO), U256::from(2));
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.tload(address, U256::ZERO), U256::from(1));
+
+        journal.finalize();
+        assert_eq!(journal.tload(address, U256::ZERO), U256::ZERO);
+    }
+
+    #[test]
+    fn test_finalize_with_transitions() {
+        let from = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let loaded = Address::with_last_byte(3);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(from, AccountInfo::from_balance(U256::from(10)));
+        db.insert_account_storage(from, U256::ZERO, U256::from(7))
+            .unwrap();
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.track_transitions = true;
+        journal
+            .transfer(&from, &to, U256::from(4), &mut db)
+            .unwrap();
+        journal
+            .sstore(from, U256::ZERO, U256::from(8), &mut db)
+            .unwrap();
+        journal.load_account(loaded, &mut db).unwrap();
+
+        let (state, _, transitions) = journal.finalize_with_transitions();
+        assert_eq!(state.len(), 3);
+        // only touched accounts are reported.
+        assert_eq!(transitions.len(), 2);
+
+        let from_transition = &transitions[&from];
+        assert_eq!(
+            from_transition.previous_info.as_ref().map(|i| i.balance),
+            Some(U256::from(10))
+        );
+        assert_eq!(
+            from_transition.info.as_ref().map(|i| i.balance),
+            Some(U256::from(6))
+        );
+        assert_eq!(
+            from_transition.storage,
+            HashMap::from([(
+                U256::ZERO,
+                StorageSlot::new_changed(U256::from(7), U256::from(8))
+            )])
+        );
+
+        let to_transition = &transitions[&to];
+        assert_eq!(to_transition.previous_info, None);
+        assert_eq!(
+            to_transition.info.as_ref().map(|i| i.balance),
+            Some(U256::from(4))
+        );
+        assert!(!to_transition.created && !to_transition.destroyed);
+        assert!(journal.original_info.is_empty());
+    }
+
+    #[test]
+    fn test_original_info_not_tracked() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(10)));
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(address, &mut db).unwrap();
+        journal
+            .initial_account_load(Address::with_last_byte(2), &[], &mut db)
+            .unwrap();
+        assert!(journal.original_info.is_empty());
+    }
+
+    #[test]
+    fn test_not_loaded_account_errors() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        let not_loaded = JournalError::AccountNotLoaded(address);
+
+        assert_eq!(journal.inc_nonce(address), Err(not_loaded));
+        assert_eq!(journal.set_code(address, Bytecode::new()), Err(not_loaded));
+        assert_eq!(
+            journal.sload(address, U256::ZERO, &mut db),
+            Err(EVMError::Journal(not_loaded))
+        );
+        assert_eq!(
+            journal.create_account_checkpoint(address, address, U256::ZERO, SpecId::CANCUN),
+            Err(CreateError::Journal(not_loaded))
+        );
+        // no checkpoint is left behind.
+        assert_eq!(journal.depth(), 0);
+    }
+
+    #[test]
+    fn test_account_exist_depends_on_spec() {
+        let empty = Address::with_last_byte(1);
+        let missing = Address::with_last_byte(2);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(empty, AccountInfo::default());
+
+        // before Spurious Dragon, account exists if it is in the trie.
+        let mut journal = JournaledState::new(SpecId::HOMESTEAD, HashSet::new());
+        assert_eq!(journal.load_account_exist(empty, &mut db), Ok((true, true)));
+        assert_eq!(
+            journal.load_account_exist(missing, &mut db),
+            Ok((true, false))
+        );
+
+        // after it, account exists if it is not empty.
+        let mut journal = JournaledState::new(SpecId::SPURIOUS_DRAGON, HashSet::new());
+        assert_eq!(
+            journal.load_account_exist(empty, &mut db),
+            Ok((true, false))
+        );
+        assert_eq!(
+            journal.load_account_exist(missing, &mut db),
+            Ok((true, false))
+        );
+    }
+
+    #[test]
+    fn test_disable_state_clearing() {
+        let missing = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+
+        let mut journal = JournaledState::new(SpecId::LATEST, HashSet::new());
+        journal.track_transitions = true;
+        journal.disable_state_clearing = true;
+        assert!(!journal.is_state_clearing_enabled());
+        assert_eq!(
+            journal.load_account_exist(missing, &mut db),
+            Ok((true, false))
+        );
+
+        // touched empty account exists and is kept.
+        journal.touch(&missing);
+        assert_eq!(
+            journal.load_account_exist(missing, &mut db),
+            Ok((false, true))
+        );
+        let (_, _, transitions) = journal.finalize_with_transitions();
+        assert_eq!(transitions[&missing].info, Some(AccountInfo::default()));
+    }
+
+    #[test]
+    fn test_selfdestruct_eip6780() {
+        let existing = Address::with_last_byte(1);
+        let created = Address::with_last_byte(2);
+        let target = Address::with_last_byte(3);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(existing, AccountInfo::from_balance(U256::from(5)));
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(existing, &mut db).unwrap();
+        journal.load_account(created, &mut db).unwrap();
+        journal
+            .create_account_checkpoint(existing, created, U256::from(2), SpecId::CANCUN)
+            .unwrap();
+
+        // existing account only sends its balance.
+        journal.selfdestruct(existing, target, &mut db).unwrap();
+        assert!(!journal.state[&existing].is_selfdestructed());
+        assert_eq!(journal.state[&existing].info.balance, U256::ZERO);
+        assert_eq!(journal.state[&target].info.balance, U256::from(3));
+
+        // account created in the same transaction is destroyed.
+        journal.selfdestruct(created, target, &mut db).unwrap();
+        assert!(journal.state[&created].is_selfdestructed());
+        assert_eq!(journal.state[&target].info.balance, U256::from(5));
+    }
+
+    #[test]
+    fn test_selfdestruct_to_self_policy() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(5)));
+
+        for (policy, balance) in [
+            (SelfDestructToSelf::Burn, U256::ZERO),
+            (SelfDestructToSelf::Keep, U256::from(5)),
+        ] {
+            let mut journal = JournaledState::new(SpecId::SHANGHAI, HashSet::new());
+            journal.selfdestruct_to_self = policy;
+            journal.load_account(address, &mut db).unwrap();
+            let checkpoint = journal.checkpoint().unwrap();
+            journal.selfdestruct(address, address, &mut db).unwrap();
+            assert_eq!(
+                journal.state[&address].is_selfdestructed(),
+                policy == SelfDestructToSelf::Burn
+            );
+            assert_eq!(journal.state[&address].info.balance, balance);
+
+            journal.checkpoint_revert(checkpoint);
+            assert_eq!(journal.state[&address].info.balance, U256::from(5));
+        }
+    }
+
+    #[test]
+    fn test_journal_entries_since_checkpoint() {
+        let address = Address::with_last_byte(1);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.tstore(address, U256::ZERO, U256::from(1));
+
+        let checkpoint = journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(2));
+        let inner = journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(3));
+        journal.checkpoint_commit();
+
+        let change = |had_value: u64| JournalEntry::TransientStorageChange {
+            address,
+            key: U256::ZERO,
+            had_value: U256::from(had_value),
+        };
+        assert_eq!(journal.journal_layers().count(), 3);
+        assert_eq!(
+            journal.entries_since(&checkpoint).collect::<Vec<_>>(),
+            vec![&change(1), &change(2)]
+        );
+        assert_eq!(
+            journal.entries_since(&inner).collect::<Vec<_>>(),
+            vec![&change(2)]
+        );
+    }
+
+    #[test]
+    fn test_revert_to_earlier_checkpoint() {
+        let address = Address::with_last_byte(1);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        let first = journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(1));
+        let _second = journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(2));
+        assert_eq!(journal.depth(), 2);
+
+        journal.checkpoint_revert(first);
+        assert_eq!(journal.depth(), 0);
+        assert_eq!(journal.tload(address, U256::ZERO), U256::ZERO);
+        assert_eq!(journal.journal_layers().count(), 1);
+    }
+
+    #[test]
+    fn test_named_snapshots() {
+        let address = Address::with_last_byte(1);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.snapshot("one");
+        journal.tstore(address, U256::ZERO, U256::from(1));
+        journal.snapshot("two");
+        journal.tstore(address, U256::ZERO, U256::from(2));
+        assert_eq!(journal.depth(), 0);
+
+        assert!(journal.revert_to_snapshot("two"));
+        assert_eq!(journal.tload(address, U256::ZERO), U256::from(1));
+        assert!(journal.revert_to_snapshot("one"));
+        assert_eq!(journal.tload(address, U256::ZERO), U256::ZERO);
+        assert!(!journal.revert_to_snapshot("two"));
+        assert!(journal.snapshots.is_empty());
+    }
+
+    #[test]
+    fn test_initial_account_and_storage_load() {
+        let caller = Address::with_last_byte(1);
+        let callee = Address::with_last_byte(2);
+        let listed = Address::with_last_byte(3);
+        let tx = TxEnv {
+            caller,
+            transact_to: TransactTo::Call(callee),
+            access_list: vec![(listed, vec![U256::from(5)])],
+            ..Default::default()
+        };
+        let mut db = crate::db::EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal
+            .initial_account_and_storage_load(&tx, &mut db)
+            .unwrap();
+        // loading is not journaled.
+        assert_eq!(journal.journal, vec![vec![]]);
+
+        for address in [caller, callee, listed] {
+            let (_, is_cold) = journal.load_account(address, &mut db).unwrap();
+            assert!(!is_cold);
+        }
+        let (_, is_cold) = journal.sload(listed, U256::from(5), &mut db).unwrap();
+        assert!(!is_cold);
+        let (_, is_cold) = journal.sload(listed, U256::from(6), &mut db).unwrap();
+        assert!(is_cold);
+        let (_, is_cold) = journal
+            .load_account(Address::with_last_byte(4), &mut db)
+            .unwrap();
+        assert!(is_cold);
+    }
+
+    #[test]
+    fn test_finalize_ordered() {
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        let addresses = [0x30, 0x10, 0x20].map(Address::with_last_byte);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        for address in addresses {
+            journal.load_account(address, &mut db).unwrap();
+            for key in [3, 1, 2] {
+                journal
+                    .sstore(address, U256::from(key), U256::from(1), &mut db)
+                    .unwrap();
+            }
+        }
+
+        let (state, _) = journal.finalize_ordered();
+        assert_eq!(
+            state.keys().copied().collect::<Vec<_>>(),
+            [0x10, 0x20, 0x30].map(Address::with_last_byte)
+        );
+        for account in state.values() {
+            assert_eq!(
+                account.storage.keys().copied().collect::<Vec<_>>(),
+                [1, 2, 3].map(U256::from)
+            );
+        }
+        assert!(journal.state.is_empty());
+    }
+
+    #[test]
+    fn test_setters_revert_with_frame() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(10)));
+        db.insert_account_storage(address, U256::from(1), U256::from(5))
+            .unwrap();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+
+        let checkpoint = journal.checkpoint().unwrap();
+        journal
+            .set_balance(address, U256::from(20), &mut db)
+            .unwrap();
+        journal.set_nonce(address, 7, &mut db).unwrap();
+        journal
+            .set_storage(address, U256::from(1), U256::from(6), &mut db)
+            .unwrap();
+        let account = journal.get_account(&address).unwrap();
+        assert_eq!(account.info.balance, U256::from(20));
+        assert_eq!(account.info.nonce, 7);
+        assert_eq!(
+            journal.get_storage(&address, &U256::from(1)),
+            Some(U256::from(6))
+        );
+        assert!(account.is_touched());
+
+        journal.checkpoint_revert(checkpoint);
+        assert!(journal.get_account(&address).is_none());
+
+        journal.set_nonce(address, 3, &mut db).unwrap();
+        let checkpoint = journal.checkpoint().unwrap();
+        journal.set_nonce(address, 4, &mut db).unwrap();
+        journal.set_balance(address, U256::ZERO, &mut db).unwrap();
+        journal.checkpoint_revert(checkpoint);
+        let account = journal.get_account(&address).unwrap();
+        assert_eq!(account.info.nonce, 3);
+        assert_eq!(account.info.balance, U256::from(10));
+    }
+
+    #[test]
+    fn test_indexed_logs() {
+        let log =
+            |byte| Log::new_unchecked(Address::with_last_byte(byte), vec![], Default::default());
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.checkpoint().unwrap();
+        journal.log(log(1));
+        let reverted = journal.checkpoint().unwrap();
+        journal.log(log(2));
+        journal.checkpoint_revert(reverted);
+        journal.checkpoint().unwrap();
+        journal.log(log(3));
+        journal.checkpoint_commit();
+        journal.log(log(4));
+
+        let (_, logs) = journal.finalize_with_indexed_logs();
+        assert_eq!(
+            logs,
+            vec![
+                IndexedLog {
+                    log: log(1),
+                    index: 0,
+                    depth: 1
+                },
+                IndexedLog {
+                    log: log(3),
+                    index: 1,
+                    depth: 2
+                },
+                IndexedLog {
+                    log: log(4),
+                    index: 2,
+                    depth: 1
+                },
+            ]
+        );
+        assert!(journal.log_depths.is_empty());
+    }
+
+    /// Database that fails to load any storage slot.
+    struct FailingStorageDB(crate::db::CacheDB<crate::db::EmptyDB>);
+
+    impl Database for FailingStorageDB {
+        type Error = &'static str;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(self.0.basic(address).unwrap())
+        }
+
+        fn code_by_hash(
+            &mut self,
+            code_hash: crate::primitives::B256,
+        ) -> Result<Bytecode, Self::Error> {
+            Ok(self.0.code_by_hash(code_hash).unwrap())
+        }
+
+        fn storage(&mut self, _address: Address, _index: U256) -> Result<U256, Self::Error> {
+            Err("storage")
+        }
+
+        fn block_hash(&mut self, number: U256) -> Result<crate::primitives::B256, Self::Error> {
+            Ok(self.0.block_hash(number).unwrap())
+        }
+    }
+
+    #[test]
+    fn test_database_error_is_transaction_error() {
+        use crate::{interpreter::opcode, primitives::TransactTo, Evm};
+
+        let contract = Address::with_last_byte(0x42);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        let code = Bytecode::new_raw([opcode::PUSH0, opcode::SLOAD, opcode::STOP].into());
+        db.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+        let mut db = FailingStorageDB(db);
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(contract, &mut db).unwrap();
+        assert_eq!(
+            journal.sstore(contract, U256::ZERO, U256::from(1), &mut db),
+            Err(EVMError::Database("storage"))
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        assert_eq!(evm.transact(), Err(EVMError::Database("storage")));
+    }
+
+    #[test]
+    fn test_precompiles_are_warm_and_not_preloaded() {
+        use crate::{interpreter::opcode, primitives::TransactTo, Evm};
+
+        let contract = Address::with_last_byte(0x42);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        // BALANCE of the identity precompile.
+        let code = Bytecode::new_raw([opcode::PUSH1, 0x04, opcode::BALANCE, opcode::STOP].into());
+        db.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.transact_to = TransactTo::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        let result = evm.transact().unwrap();
+
+        // intrinsic gas, PUSH1 and warm BALANCE.
+        assert_eq!(result.result.gas_used(), 21_000 + 3 + 100);
+        let identity = Address::with_last_byte(0x04);
+        assert!(!result.state[&identity].is_touched());
+        for precompile in (1..=10).map(Address::with_last_byte) {
+            if precompile != identity {
+                assert!(!result.state.contains_key(&precompile));
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_touch_revert() {
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        let other = Address::with_last_byte(1);
+        for (spec, persistent) in [(SpecId::HOMESTEAD, false), (SpecId::CANCUN, true)] {
+            let mut journal = JournaledState::new(spec, HashSet::new());
+            journal.checkpoint().unwrap();
+            let reverted = journal.checkpoint().unwrap();
+            for address in [PRECOMPILE3, other] {
+                journal.load_account(address, &mut db).unwrap();
+                journal.touch(&address);
+            }
+            journal.checkpoint_revert(reverted);
+
+            assert_eq!(
+                journal.get_account(&PRECOMPILE3).map(Account::is_touched),
+                persistent.then_some(true)
+            );
+            assert!(journal.get_account(&other).is_none());
+        }
+
+        // accounts loaded before the reverted frame stay loaded, only the persistent one stays
+        // touched.
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        for address in [PRECOMPILE3, other] {
+            journal.load_account(address, &mut db).unwrap();
+        }
+        let reverted = journal.checkpoint().unwrap();
+        for address in [PRECOMPILE3, other] {
+            journal.touch(&address);
+        }
+        journal.checkpoint_revert(reverted);
+        assert!(journal.get_account(&PRECOMPILE3).unwrap().is_touched());
+        assert!(!journal.get_account(&other).unwrap().is_touched());
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.persistent_touches.clear();
+        let reverted = journal.checkpoint().unwrap();
+        journal.load_account(PRECOMPILE3, &mut db).unwrap();
+        journal.touch(&PRECOMPILE3);
+        journal.checkpoint_revert(reverted);
+        assert!(journal.get_account(&PRECOMPILE3).is_none());
+    }
+
+    #[test]
+    fn test_replay_onto_external_state() {
+        let from = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(from, AccountInfo::from_balance(U256::from(10)));
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(from, &mut db).unwrap();
+
+        let mut mirror = State::default();
+        let checkpoint = journal.checkpoint().unwrap();
+        journal
+            .transfer(&from, &to, U256::from(4), &mut db)
+            .unwrap();
+        journal
+            .sstore(from, U256::from(1), U256::from(7), &mut db)
+            .unwrap();
+        journal.replay_onto(journal.entries_since(&checkpoint), &mut mirror);
+        assert_eq!(mirror[&from].info.balance, U256::from(6));
+        assert_eq!(mirror[&to].info.balance, U256::from(4));
+        assert!(mirror[&to].is_touched());
+        assert_eq!(
+            mirror[&from].storage[&U256::from(1)].present_value,
+            U256::from(7)
+        );
+
+        // replaying the reverted entries brings the mirror back to the journal state.
+        let reverted: Vec<_> = journal.entries_since(&checkpoint).cloned().collect();
+        journal.checkpoint_revert(checkpoint);
+        journal.replay_onto(&reverted, &mut mirror);
+        assert_eq!(mirror[&from].info.balance, U256::from(10));
+        assert!(!mirror.contains_key(&to));
+        assert!(mirror[&from].storage.is_empty());
+    }
+
+    #[test]
+    fn test_transition_wiped_storage() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(1)));
+        for key in 1..=3 {
+            db.insert_account_storage(address, U256::from(key), U256::from(key * 10))
+                .unwrap();
+        }
+        let mut journal = JournaledState::new(SpecId::SHANGHAI, HashSet::new());
+        journal.track_transitions = true;
+        journal.load_account(address, &mut db).unwrap();
+        journal.touch(&address);
+        journal.sload(address, U256::from(1), &mut db).unwrap();
+        journal
+            .sstore(address, U256::from(2), U256::ZERO, &mut db)
+            .unwrap();
+        journal
+            .selfdestruct(address, Address::with_last_byte(2), &mut db)
+            .unwrap();
+
+        let (_, _, transitions) = journal.finalize_with_transitions();
+        assert_eq!(
+            transitions[&address].wiped_storage,
+            HashMap::from_iter([
+                (U256::from(1), U256::from(10)),
+                (U256::from(2), U256::from(20))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_call_depth_limit() {
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        assert_eq!(journal.call_depth_limit, 1024);
+        journal.call_depth_limit = 1;
+        journal.checkpoint().unwrap();
+        journal.checkpoint().unwrap();
+        assert_eq!(journal.checkpoint(), Err(CallDepthExceeded { limit: 1 }));
+        assert_eq!(journal.depth(), 2);
+
+        // snapshots do not change the depth.
+        journal.snapshot("top");
+        assert_eq!(journal.depth(), 2);
+        journal.checkpoint_commit();
+        journal.checkpoint().unwrap();
+    }
+
+    #[test]
+    fn test_finalize_accounts() {
+        let from = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(from, AccountInfo::from_balance(U256::from(10)));
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(from, &mut db).unwrap();
+        journal.checkpoint().unwrap();
+        journal
+            .transfer(&from, &to, U256::from(4), &mut db)
+            .unwrap();
+        journal
+            .sstore(from, U256::ZERO, U256::from(1), &mut db)
+            .unwrap();
+        journal.checkpoint_commit();
+
+        let state = journal
+            .finalize_accounts(&[from, Address::with_last_byte(3)])
+            .unwrap();
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[&from].info.balance, U256::from(6));
+        assert_eq!(
+            state[&from].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+        assert!(journal.get_account(&from).is_none());
+        assert_eq!(
+            journal.get_account(&to).unwrap().info.balance,
+            U256::from(4)
+        );
+        // entries of the finalized account are dropped, the transfer is final for both.
+        assert!(journal
+            .journal
+            .iter()
+            .flatten()
+            .all(|entry| !entry.addresses().any(|address| *address == from)));
+    }
+
+    #[test]
+    fn test_finalize_accounts_revertible() {
+        let from = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(from, AccountInfo::from_balance(U256::from(10)));
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(to, &mut db).unwrap();
+
+        journal.checkpoint().unwrap();
+        assert_eq!(
+            journal.finalize_accounts(&[to]),
+            Err(JournalError::UncommittedCheckpoint { depth: 1 })
+        );
+        journal.checkpoint_commit();
+
+        // transfer can still be reverted to the snapshot.
+        journal.snapshot("transfer");
+        journal
+            .transfer(&from, &to, U256::from(4), &mut db)
+            .unwrap();
+        assert_eq!(
+            journal.finalize_accounts(&[to]),
+            Err(JournalError::AccountJournaled(to))
+        );
+        assert!(journal.get_account(&to).is_some());
+
+        assert!(journal.revert_to_snapshot("transfer"));
+        let state = journal.finalize_accounts(&[to]).unwrap();
+        assert_eq!(state[&to].info.balance, U256::ZERO);
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let address = Address::with_last_byte(1);
+        let mut db = crate::db::CacheDB::new(crate::db::EmptyDB::default());
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(5)));
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+        journal.load_account(address, &mut db).unwrap();
+        journal.snapshot("start");
+        journal.checkpoint().unwrap();
+        journal.tstore(address, U256::ZERO, U256::from(1));
+        journal
+            .sstore(address, U256::ZERO, U256::from(2), &mut db)
+            .unwrap();
+
+        let json = serde_json::to_string(&journal).unwrap();
+        let mut resumed: JournaledState = serde_json::from_str(&json).unwrap();
+        assert_eq!(resumed, journal);
+
+        // resumed state can still be reverted.
+        assert!(resumed.revert_to_snapshot("start"));
+        assert_eq!(resumed.tload(address, U256::ZERO), U256::ZERO);
+        assert_eq!(resumed.get_storage(&address, &U256::ZERO), None);
+        assert_eq!(resumed.depth(), 0);
+    }
 }
@@ -29,7 +29,10 @@ pub mod optimism;
 // Export items.
 
 pub use builder::EvmBuilder;
-pub use context::{Context, ContextWithHandlerCfg, EvmContext};
+pub use context::{
+    AddressRangePrecompile, Context, ContextPrecompile, ContextStatefulPrecompile,
+    ContextWithHandlerCfg, EvmContext, PrecompileRange,
+};
 pub use db::{
     CacheState, DBBox, State, StateBuilder, StateDBBox, TransitionAccount, TransitionState,
 };
@@ -40,7 +43,10 @@ pub use handler::Handler;
 pub use inspector::{
     inspector_handle_register, inspector_instruction, inspectors, GetInspector, Inspector,
 };
-pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState};
+pub use journaled_state::{
+    AccountTransition, CreateError, IndexedLog, JournalCheckpoint, JournalEntry, JournaledState,
+    OrderedAccount,
+};
 // export Optimism types, helpers, and constants
 #[cfg(feature = "optimism")]
 pub use optimism::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
@@ -1,10 +1,13 @@
 //! Optimism-specific constants, types, and helpers.
 
+mod fast_lz;
 mod handler_register;
 mod l1block;
+mod receipt;
 
 pub use handler_register::{
     deduct_caller, end, last_frame_return, load_accounts, optimism_handle_register, output,
     reward_beneficiary, validate_env, validate_tx_against_state,
 };
 pub use l1block::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
+pub use receipt::{OptimismReceiptFields, DEPOSIT_RECEIPT_VERSION};
@@ -133,7 +133,7 @@ pub fn last_frame_return<SPEC: Spec, EXT, DB: Database>(
     // Prior to Regolith, deposit transactions did not receive gas refunds.
     let is_gas_refund_disabled = env.cfg.is_gas_refund_disabled() || (is_deposit && !is_regolith);
     if !is_gas_refund_disabled {
-        gas.set_final_refund::<SPEC>();
+        gas.set_final_refund_with_quotient(env.cfg.chain.refund_quotient_for(SPEC::SPEC_ID));
     }
     Ok(())
 }
@@ -384,7 +384,7 @@ mod tests {
         gas: Gas,
     ) -> Gas {
         let mut ctx = Context::new_empty();
-        ctx.evm.env = Box::new(env);
+        *ctx.evm.env = env;
         let mut first_frame = FrameResult::Call(CallOutcome::new(
             InterpreterResult {
                 result: instruction_result,
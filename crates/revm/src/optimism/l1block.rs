@@ -1,3 +1,4 @@
+use super::fast_lz::flz_compress_len;
 use crate::primitives::{address, db::Database, Address, SpecId, U256};
 use core::ops::Mul;
 
@@ -22,6 +23,13 @@ const ECOTONE_L1_BLOB_BASE_FEE_SLOT: U256 = U256::from_limbs([7u64, 0, 0, 0]);
 /// offsets [BASE_FEE_SCALAR_OFFSET] and [BLOB_BASE_FEE_SCALAR_OFFSET] respectively.
 const ECOTONE_L1_FEE_SCALARS_SLOT: U256 = U256::from_limbs([3u64, 0, 0, 0]);
 
+/// Fjord: Minimum estimated L1 size of the transaction in bytes.
+const MIN_TX_SIZE: u64 = 100;
+/// Fjord: Intercept of the linear regression of the L1 size, scaled by 1e6.
+const L1_COST_INTERCEPT: i64 = -42_585_600;
+/// Fjord: Coefficient of the FastLZ size in the linear regression of the L1 size, scaled by 1e6.
+const L1_COST_FASTLZ_COEF: u64 = 836_500;
+
 /// An empty 64-bit set of scalar values.
 const EMPTY_SCALARS: [u8; 8] = [0u8; 8];
 
@@ -121,7 +129,16 @@ impl L1BlockInfo {
     ///
     /// Prior to regolith, an extra 68 non-zero bytes were included in the rollup data costs to
     /// account for the empty signature.
+    ///
+    /// Since Fjord, it is the estimated size of the transaction times the non-zero byte cost.
     pub fn data_gas(&self, input: &[u8], spec_id: SpecId) -> U256 {
+        if spec_id.is_enabled_in(SpecId::FJORD) {
+            return self
+                .tx_estimated_size_fjord(input)
+                .saturating_mul(U256::from(NON_ZERO_BYTE_COST))
+                .wrapping_div(U256::from(1_000_000));
+        }
+
         let mut rollup_data_gas_cost = U256::from(input.iter().fold(0, |acc, byte| {
             acc + if *byte == 0x00 {
                 ZERO_BYTE_COST
@@ -145,7 +162,9 @@ impl L1BlockInfo {
             return U256::ZERO;
         }
 
-        if spec_id.is_enabled_in(SpecId::ECOTONE) {
+        if spec_id.is_enabled_in(SpecId::FJORD) {
+            self.calculate_tx_l1_cost_fjord(input)
+        } else if spec_id.is_enabled_in(SpecId::ECOTONE) {
             self.calculate_tx_l1_cost_ecotone(input, spec_id)
         } else {
             self.calculate_tx_l1_cost_bedrock(input, spec_id)
@@ -195,6 +214,37 @@ impl L1BlockInfo {
             .saturating_mul(rollup_data_gas_cost)
             .wrapping_div(U256::from(1_000_000 * 16))
     }
+
+    /// Calculate the gas cost of a transaction based on L1 block data posted on L2, post-Fjord.
+    ///
+    /// [SpecId::FJORD] L1 cost function:
+    /// `estimatedSize*(baseFeeScalar*l1BaseFee*16 + blobFeeScalar*l1BlobBaseFee)/1e12`
+    ///
+    /// Estimated size is scaled by 1e6, see [L1BlockInfo::tx_estimated_size_fjord].
+    fn calculate_tx_l1_cost_fjord(&self, input: &[u8]) -> U256 {
+        let l1_fee_scaled = self
+            .l1_base_fee_scalar
+            .saturating_mul(self.l1_base_fee)
+            .saturating_mul(U256::from(16))
+            .saturating_add(
+                self.l1_blob_base_fee_scalar
+                    .unwrap_or_default()
+                    .saturating_mul(self.l1_blob_base_fee.unwrap_or_default()),
+            );
+        self.tx_estimated_size_fjord(input)
+            .saturating_mul(l1_fee_scaled)
+            .wrapping_div(U256::from(1_000_000_000_000u64))
+    }
+
+    /// Estimated L1 size of the transaction scaled by 1e6, it is the linear regression of the
+    /// FastLZ compressed size with the minimum of [MIN_TX_SIZE] bytes.
+    ///
+    /// The compressed size includes 68 bytes of the signature.
+    fn tx_estimated_size_fjord(&self, input: &[u8]) -> U256 {
+        let fastlz_size = u64::from(flz_compress_len(input)) + 68;
+        let estimated_size = (L1_COST_FASTLZ_COEF * fastlz_size) as i64 + L1_COST_INTERCEPT;
+        U256::from((estimated_size.max(0) as u64).max(MIN_TX_SIZE * 1_000_000))
+    }
 }
 
 #[cfg(test)]
@@ -310,4 +360,36 @@ mod tests {
         let gas_cost = l1_block_info.calculate_tx_l1_cost(&input, SpecId::ECOTONE);
         assert_eq!(gas_cost, U256::from(1048));
     }
+
+    #[test]
+    fn test_calculate_tx_l1_cost_fjord() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_base_fee_scalar: U256::from(1_000),
+            l1_blob_base_fee: Some(U256::from(1_000)),
+            l1_blob_base_fee_scalar: Some(U256::from(1_000)),
+            ..Default::default()
+        };
+
+        // fastlzSize = 4 + 68 = 72
+        // estimatedSize = max(100e6, -42_585_600 + 836_500 * 72) = 100e6
+        // l1Cost = estimatedSize * (1000 * 1000 * 16 + 1000 * 1000) / 1e12 = 1700
+        let input = bytes!("FACADE");
+        let gas_cost = l1_block_info.calculate_tx_l1_cost(&input, SpecId::FJORD);
+        assert_eq!(gas_cost, U256::from(1700));
+        let data_gas = l1_block_info.data_gas(&input, SpecId::FJORD);
+        assert_eq!(data_gas, U256::from(1600));
+
+        // fastlzSize = 256 + 8 + 68 = 332
+        // estimatedSize = -42_585_600 + 836_500 * 332 = 235_132_400
+        // l1Cost = 235_132_400 * 17_000_000 / 1e12 = 3997
+        let input: Vec<u8> = (0..=255).collect();
+        let gas_cost = l1_block_info.calculate_tx_l1_cost(&input, SpecId::FJORD);
+        assert_eq!(gas_cost, U256::from(3997));
+
+        // Deposit transactions with the EIP-2718 type of 0x7F should result in zero
+        let input = bytes!("7FFACADE");
+        let gas_cost = l1_block_info.calculate_tx_l1_cost(&input, SpecId::FJORD);
+        assert_eq!(gas_cost, U256::ZERO);
+    }
 }
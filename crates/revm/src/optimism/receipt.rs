@@ -0,0 +1,114 @@
+use super::L1BlockInfo;
+use crate::primitives::{Env, SpecId, U256};
+
+/// Receipt version of the deposit transactions since Canyon.
+pub const DEPOSIT_RECEIPT_VERSION: u64 = 1;
+
+/// Optimism fields of the transaction receipt that are not part of the Ethereum receipt.
+///
+/// Deposit fields are only set for the deposit transactions and L1 fee fields only for the
+/// other transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptimismReceiptFields {
+    /// Nonce of the deposit sender before the transaction, since Regolith.
+    pub deposit_nonce: Option<u64>,
+    /// Version of the deposit receipt, since Canyon.
+    pub deposit_receipt_version: Option<u64>,
+    /// L1 data fee that was charged from the sender.
+    pub l1_fee: Option<U256>,
+    /// Data gas of the transaction on L1.
+    pub l1_gas_used: Option<U256>,
+    /// Base fee of the L1 origin block.
+    pub l1_gas_price: Option<U256>,
+    /// Blob base fee of the L1 origin block, since Ecotone.
+    pub l1_blob_base_fee: Option<U256>,
+    /// L1 base fee scalar.
+    pub l1_base_fee_scalar: Option<U256>,
+    /// L1 blob base fee scalar, since Ecotone.
+    pub l1_blob_base_fee_scalar: Option<U256>,
+}
+
+impl OptimismReceiptFields {
+    /// Creates the receipt fields of the transaction in `env`.
+    ///
+    /// `caller_nonce` is the nonce of the caller before the transaction and `l1_block_info` is
+    /// the info the transaction was charged with, it is left in the context after the
+    /// transaction. Without the info or the enveloped transaction L1 fee fields are not set.
+    pub fn new(
+        env: &Env,
+        spec_id: SpecId,
+        l1_block_info: Option<&L1BlockInfo>,
+        caller_nonce: u64,
+    ) -> Self {
+        if env.tx.optimism.source_hash.is_some() {
+            return Self {
+                deposit_nonce: spec_id
+                    .is_enabled_in(SpecId::REGOLITH)
+                    .then_some(caller_nonce),
+                deposit_receipt_version: spec_id
+                    .is_enabled_in(SpecId::CANYON)
+                    .then_some(DEPOSIT_RECEIPT_VERSION),
+                ..Default::default()
+            };
+        }
+        let (Some(info), Some(enveloped_tx)) = (l1_block_info, &env.tx.optimism.enveloped_tx)
+        else {
+            return Self::default();
+        };
+        Self {
+            l1_fee: Some(info.calculate_tx_l1_cost(enveloped_tx, spec_id)),
+            l1_gas_used: Some(info.data_gas(enveloped_tx, spec_id)),
+            l1_gas_price: Some(info.l1_base_fee),
+            l1_blob_base_fee: info.l1_blob_base_fee,
+            l1_base_fee_scalar: Some(info.l1_base_fee_scalar),
+            l1_blob_base_fee_scalar: info.l1_blob_base_fee_scalar,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{bytes, B256};
+
+    #[test]
+    fn test_deposit_receipt_fields() {
+        let mut env = Env::default();
+        env.tx.optimism.source_hash = Some(B256::ZERO);
+
+        let fields = OptimismReceiptFields::new(&env, SpecId::BEDROCK, None, 5);
+        assert_eq!(fields, OptimismReceiptFields::default());
+
+        let fields = OptimismReceiptFields::new(&env, SpecId::REGOLITH, None, 5);
+        assert_eq!(fields.deposit_nonce, Some(5));
+        assert_eq!(fields.deposit_receipt_version, None);
+
+        let fields = OptimismReceiptFields::new(&env, SpecId::CANYON, None, 5);
+        assert_eq!(fields.deposit_nonce, Some(5));
+        assert_eq!(
+            fields.deposit_receipt_version,
+            Some(DEPOSIT_RECEIPT_VERSION)
+        );
+    }
+
+    #[test]
+    fn test_l1_fee_receipt_fields() {
+        let mut env = Env::default();
+        env.tx.optimism.enveloped_tx = Some(bytes!("FACADE"));
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_fee_overhead: Some(U256::from(1_000)),
+            l1_base_fee_scalar: U256::from(1_000),
+            ..Default::default()
+        };
+
+        let fields = OptimismReceiptFields::new(&env, SpecId::REGOLITH, Some(&l1_block_info), 5);
+        assert_eq!(fields.deposit_nonce, None);
+        assert_eq!(fields.l1_fee, Some(U256::from(1048)));
+        assert_eq!(fields.l1_gas_used, Some(U256::from(48)));
+        assert_eq!(fields.l1_gas_price, Some(U256::from(1_000)));
+        assert_eq!(fields.l1_blob_base_fee, None);
+    }
+}